@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 
 fn step(c: char, lvl: i32) -> i32 {
     match c {
@@ -25,14 +25,12 @@ fn reach_basement(instructions: &str) -> Option<usize> {
 }
 
 #[aoc(2015, 1)]
-pub fn main() {
-    let data = aoc_input!(2015, 1).unwrap();
-
+pub fn main(data: &str) {
     // Part I
-    let ans1 = dest_floor(&data);
+    let ans1 = dest_floor(data);
     println!("{}", ans1);
 
     // Part II
-    let ans2 = reach_basement(&data);
+    let ans2 = reach_basement(data);
     println!("{}", ans2.unwrap());
 }
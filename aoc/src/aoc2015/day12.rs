@@ -1,6 +1,6 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use regex_lite::Regex;
-use serde_json::{json, Value};
+use serde_json::{Value, json};
 
 fn find_sum(data: &str) -> i64 {
     let re: Regex = Regex::new(r"([-]*[[:digit:]]+)").unwrap();
@@ -11,10 +11,10 @@ fn find_sum(data: &str) -> i64 {
 
 fn has_red(val: &Value) -> bool {
     for (_, v) in val.as_object().unwrap_or(json!({}).as_object().unwrap()) {
-        if let Value::String(s) = v {
-            if s == "red" {
-                return true;
-            }
+        if let Value::String(s) = v
+            && s == "red"
+        {
+            return true;
         }
     }
     false
@@ -24,11 +24,9 @@ fn sum_node(val: &Value) -> i64 {
     let mut sum = 0;
 
     match val {
-        Value::Object(obj) => {
-            if !has_red(val) {
-                for (_, v) in obj {
-                    sum += sum_node(v);
-                }
+        Value::Object(obj) if !has_red(val) => {
+            for (_, v) in obj {
+                sum += sum_node(v);
             }
         }
         Value::Array(arr) => {
@@ -43,14 +41,12 @@ fn sum_node(val: &Value) -> i64 {
 }
 
 #[aoc(2015, 12)]
-pub fn main() {
-    let data = aoc_input!(2015, 12).unwrap();
-
+pub fn main(data: &str) {
     // Part I
-    println!("{}", find_sum(&data));
+    println!("{}", find_sum(data));
 
     // Part II
-    let json: Value = serde_json::from_str(&data).unwrap();
+    let json: Value = serde_json::from_str(data).unwrap();
     println!("{}", sum_node(&json));
 }
 
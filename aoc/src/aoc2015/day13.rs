@@ -1,6 +1,6 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
-use petgraph::{prelude::*, Graph};
+use petgraph::{Graph, prelude::*};
 use std::collections::HashMap;
 
 #[derive(Clone)]
@@ -123,8 +123,7 @@ impl Attendees {
 }
 
 #[aoc(2015, 13)]
-pub fn main() {
-    let data = aoc_input!(2015, 13).unwrap();
+pub fn main(data: &str) {
     let mut attendees = Attendees::new();
 
     for line in data.split('\n').filter(|x| !x.is_empty()) {
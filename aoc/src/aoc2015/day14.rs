@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use regex_lite::Regex;
 use rustc_hash::FxHashMap;
@@ -38,8 +38,7 @@ impl From<&str> for Reindeer {
 }
 
 #[aoc(2015, 14)]
-pub fn main() {
-    let data = aoc_input!(2015, 14).unwrap();
+pub fn main(data: &str) {
     let reindeers = data
         .lines()
         .filter(|x| !x.is_empty())
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use regex_lite::Regex;
 use std::cmp::max;
 
@@ -89,7 +89,7 @@ fn queue(length: i64, total: i64) -> Vec<Vec<i64>> {
     acc
 }
 
-fn search(ingredients: &Vec<Property>, calories: Option<i64>) -> i64 {
+fn search(ingredients: &[Property], calories: Option<i64>) -> i64 {
     let n = ingredients.len();
     let mut max_score: i64 = 0;
     let mut q = queue(n as i64, 100);
@@ -112,8 +112,7 @@ fn search(ingredients: &Vec<Property>, calories: Option<i64>) -> i64 {
 }
 
 #[aoc(2015, 15)]
-pub fn main() {
-    let data = aoc_input!(2015, 15).unwrap();
+pub fn main(data: &str) {
     let ingredients = data
         .lines()
         .filter(|x| !x.is_empty())
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 
 #[derive(Debug, Default)]
 struct Compounds {
@@ -104,8 +104,7 @@ fn parse_line(line: &str) -> Vec<(&str, usize)> {
 }
 
 #[aoc(2015, 16)]
-pub fn main() {
-    let data = aoc_input!(2015, 16).unwrap();
+pub fn main(data: &str) {
     let sues: Vec<Compounds> = data
         .lines()
         .filter(|x| !x.is_empty())
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use std::collections::HashSet;
 
 struct Containers {
@@ -67,8 +67,7 @@ impl<'a> Iterator for ContainersPartitionsIter<'a> {
 }
 
 #[aoc(2015, 17)]
-pub fn main() {
-    let data = aoc_input!(2015, 17).unwrap();
+pub fn main(data: &str) {
     let containers = Containers::from(
         data.lines()
             .flat_map(|x| x.parse::<i32>())
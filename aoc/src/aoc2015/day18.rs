@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use std::fmt;
 
 struct Grid {
@@ -128,9 +128,8 @@ impl Grid {
 }
 
 #[aoc(2015, 18)]
-pub fn main() {
-    let data = aoc_input!(2015, 18).unwrap();
-    let mut grid = Grid::from(&data);
+pub fn main(data: &str) {
+    let mut grid = Grid::from(data);
     let n = 100;
 
     // Part I
@@ -138,7 +137,7 @@ pub fn main() {
     println!("{}", grid.count());
 
     // Part II
-    grid = Grid::from(&data);
+    grid = Grid::from(data);
     grid.fix_corners();
 
     for _ in 0..n {
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use std::collections::HashSet;
 
@@ -62,8 +62,7 @@ fn search(start: &str, end: &str, rules: &[(&str, &str)]) -> usize {
 }
 
 #[aoc(2015, 19)]
-pub fn main() {
-    let data = aoc_input!(2015, 19).unwrap();
+pub fn main(data: &str) {
     let lines = data.lines().filter(|x| !x.is_empty()).collect::<Vec<_>>();
     let rules = lines
         .iter()
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 
 #[derive(Debug)]
 struct Box {
@@ -75,8 +75,7 @@ fn total_ribbon_len(boxes: &[Box]) -> usize {
 }
 
 #[aoc(2015, 2)]
-pub fn main() {
-    let data = aoc_input!(2015, 2).unwrap();
+pub fn main(data: &str) {
     let boxes: Vec<Box> = data
         .lines()
         .filter(|&x| !x.is_empty())
@@ -212,7 +212,7 @@ static SPELLS: [Spell; 5] = [
     Spell::Recharge(229, 5),
 ];
 
-#[aoc(2015, 22)]
+#[aoc(2015, 22, title = "Wizard Simulator 20XX", tags = ["dijkstra", "simulation"])]
 pub fn main() {
     let (boss_hp, damage): (i16, i16) = (58, 9);
 
@@ -1,116 +1,86 @@
-use aoc::{aoc, aoc_input};
-use std::fmt::{Display, Formatter, Result};
+use aoc::aoc;
+use aoc::vm::{Cpu, Instruction, Jump, Machine, parse_program, register, tokens};
+use std::str::FromStr;
 
 #[derive(Debug)]
-enum Instructions {
+enum Op {
     Hlf(usize),
     Tpl(usize),
     Inc(usize),
     Jmp(i64),
     Jie(usize, i64),
     Jio(usize, i64),
-    Invalid,
 }
 
-impl<S: Into<String>> From<S> for Instructions {
-    fn from(value: S) -> Self {
-        let value = value.into();
-        let parts = value
-            .split(' ')
-            .map(|x| x.strip_suffix(',').unwrap_or(x))
-            .collect::<Vec<&str>>();
-        let register = (parts[1].chars().nth(0).unwrap() as u8).saturating_sub(b'a') as usize;
-        let as_num = |x: &str| x.parse::<i64>().unwrap();
+impl FromStr for Op {
+    type Err = String;
 
-        match parts[0] {
-            "hlf" => Self::Hlf(register),
-            "tpl" => Self::Tpl(register),
-            "inc" => Self::Inc(register),
-            "jmp" => Self::Jmp(as_num(parts[1])),
-            "jie" => Self::Jie(register, as_num(parts[2])),
-            "jio" => Self::Jio(register, as_num(parts[2])),
-            _ => Self::Invalid,
-        }
-    }
-}
-
-struct Program<'a> {
-    pos: i64,
-    registers: Vec<i64>,
-    tape: &'a Vec<Instructions>,
-}
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (op, args) = tokens(s);
+        let reg = |i: usize| {
+            args.get(i)
+                .and_then(|x| register(x))
+                .ok_or(format!("Invalid register in: {s}"))
+        };
+        let offset = |i: usize| {
+            args.get(i)
+                .and_then(|x| x.parse().ok())
+                .ok_or(format!("Invalid offset in: {s}"))
+        };
 
-impl<'a> Display for Program<'a> {
-    fn fmt(&self, f: &mut Formatter) -> Result {
-        write!(f, "{}", self.registers.get(1).unwrap_or(&0))
+        match op {
+            "hlf" => Ok(Self::Hlf(reg(0)?)),
+            "tpl" => Ok(Self::Tpl(reg(0)?)),
+            "inc" => Ok(Self::Inc(reg(0)?)),
+            "jmp" => Ok(Self::Jmp(offset(0)?)),
+            "jie" => Ok(Self::Jie(reg(0)?, offset(1)?)),
+            "jio" => Ok(Self::Jio(reg(0)?, offset(1)?)),
+            _ => Err(format!("Unknown instruction: {s}")),
+        }
     }
 }
 
-impl<'a> Program<'a> {
-    fn new(tape: &'a Vec<Instructions>) -> Self {
-        Self {
-            pos: 0,
-            registers: vec![0, 0],
-            tape,
-        }
-    }
+impl Instruction for Op {
+    type Word = i64;
 
-    fn eat(&mut self) -> Option<()> {
-        match self.tape.get(self.pos as usize) {
-            Some(Instructions::Hlf(reg)) => {
-                self.registers[*reg] /= 2;
-                self.pos += 1;
-            }
-            Some(Instructions::Tpl(reg)) => {
-                self.registers[*reg] *= 3;
-                self.pos += 1;
-            }
-            Some(Instructions::Inc(reg)) => {
-                self.registers[*reg] += 1;
-                self.pos += 1;
-            }
-            Some(Instructions::Jmp(off)) => self.pos += *off,
-            Some(Instructions::Jie(reg, off)) => match self.registers[*reg] % 2 == 0 {
-                true => self.pos += *off,
-                false => self.pos += 1,
-            },
-            Some(Instructions::Jio(reg, off)) => match self.registers[*reg] == 1 {
-                true => self.pos += *off,
-                false => self.pos += 1,
-            },
-            _ => {
-                return None;
-            }
+    fn execute(&self, cpu: &mut Cpu<i64>) -> Jump {
+        let r = &mut cpu.registers;
+        match *self {
+            Self::Hlf(reg) => r[reg] /= 2,
+            Self::Tpl(reg) => r[reg] *= 3,
+            Self::Inc(reg) => r[reg] += 1,
+            Self::Jmp(off) => return Jump::Relative(off),
+            Self::Jie(reg, off) if r[reg] % 2 == 0 => return Jump::Relative(off),
+            Self::Jio(reg, off) if r[reg] == 1 => return Jump::Relative(off),
+            Self::Jie(..) | Self::Jio(..) => {}
         }
-        Some(())
-    }
-
-    fn run(&mut self) {
-        while self.eat().is_some() {}
-    }
-
-    fn reset(&mut self, registers: &[i64]) {
-        self.pos = 0;
-        self.registers = registers.to_owned();
+        Jump::Next
     }
 }
 
 #[aoc(2015, 23)]
-pub fn main() {
-    let data = aoc_input!(2015, 23).unwrap();
-    let tape = data
-        .lines()
-        .filter(|x| !x.is_empty())
-        .map(Instructions::from)
-        .collect::<Vec<Instructions>>();
+pub fn main(data: &str) {
+    let mut machine = Machine::new(parse_program::<Op>(data).unwrap(), 2);
 
     // Part I
-    let mut prog = Program::new(&tape);
-    prog.run();
-    println!("{}", prog);
+    machine.run();
+    println!("{}", machine.register(1));
 
-    // Part I
-    prog.reset(&[1, 0]);
-    prog.run();
-    println!("{}", prog);
+    // Part II
+    machine.reset(&[1, 0]);
+    machine.run();
+    println!("{}", machine.register(1));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let program = parse_program::<Op>("inc a\njio a, +2\ntpl a\ninc a").unwrap();
+        let mut machine = Machine::new(program, 2);
+        machine.run();
+        assert_eq!(machine.register(0), 2);
+    }
 }
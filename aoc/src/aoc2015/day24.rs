@@ -1,7 +1,7 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 
-fn min_qe(packages: &Vec<i64>, n: i64) -> Option<i64> {
+fn min_qe(packages: &[i64], n: i64) -> Option<i64> {
     let weight: i64 = packages.iter().sum::<i64>() / n;
 
     for i in 1..(packages.len() - 2) {
@@ -20,8 +20,7 @@ fn min_qe(packages: &Vec<i64>, n: i64) -> Option<i64> {
 }
 
 #[aoc(2015, 24)]
-pub fn main() {
-    let data = aoc_input!(2015, 24).unwrap();
+pub fn main(data: &str) {
     let packages = data
         .lines()
         .filter(|x| !x.is_empty())
@@ -1,10 +1,10 @@
 use aoc::aoc;
+use aoc::math::{mod_pow, mul_mod};
 
 fn get_term(x0: u64, n: u64) -> u64 {
     let a: u64 = 252533;
     let b: u64 = 33554393;
-    let c = (1..n).fold(1, |acc, _| acc * a % b);
-    x0 * c % b
+    mul_mod(x0, mod_pow(a, n - 1, b), b)
 }
 
 fn iloc(row: u64, col: u64) -> u64 {
@@ -19,3 +19,17 @@ pub fn main() {
     let i = iloc(row, col);
     println!("{}", get_term(x0, i));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_term() {
+        // Codes at (row, col) = (1, 1), (2, 1), (1, 2) and (6, 6) from the puzzle's table
+        assert_eq!(get_term(20151125, iloc(1, 1)), 20151125);
+        assert_eq!(get_term(20151125, iloc(2, 1)), 31916031);
+        assert_eq!(get_term(20151125, iloc(1, 2)), 18749137);
+        assert_eq!(get_term(20151125, iloc(6, 6)), 27995004);
+    }
+}
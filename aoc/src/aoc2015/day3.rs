@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use rustc_hash::FxHashMap;
 
 type Visits = FxHashMap<(i32, i32), usize>;
@@ -76,14 +76,12 @@ fn work_with_robo_santa(path: &str) -> usize {
 }
 
 #[aoc(2015, 3)]
-pub fn main() {
-    let data = aoc_input!(2015, 3).unwrap();
-
+pub fn main(data: &str) {
     // Part I
-    println!("{}", at_least_one_present(&data));
+    println!("{}", at_least_one_present(data));
 
     // Part II
-    println!("{}", work_with_robo_santa(&data));
+    println!("{}", work_with_robo_santa(data));
 }
 
 #[cfg(test)]
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use rustc_hash::FxHashMap;
 
 static VOWELS: &str = "aeiou";
@@ -62,8 +62,7 @@ fn ans2(data: &[&str]) -> usize {
 }
 
 #[aoc(2015, 5)]
-pub fn main() {
-    let data = aoc_input!(2015, 5).unwrap();
+pub fn main(data: &str) {
     let data = data.lines().filter(|x| !x.is_empty()).collect::<Vec<_>>();
 
     // Part I
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 
 #[derive(PartialEq, Debug)]
@@ -102,8 +102,7 @@ fn parse_line(line: &str) -> (Action, Range) {
 }
 
 #[aoc(2015, 6)]
-pub fn main() {
-    let data = aoc_input!(2015, 6).unwrap();
+pub fn main(data: &str) {
     let commands = data
         .lines()
         .filter(|&x| !x.is_empty())
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use petgraph::prelude::*;
 use std::collections::HashMap;
@@ -27,8 +27,7 @@ fn shortest_path(graph: &Graph<&str, i32, Undirected>) -> Option<i32> {
 }
 
 #[aoc(2015, 9)]
-pub fn main() {
-    let data = aoc_input!(2015, 9).unwrap();
+pub fn main(data: &str) {
     let mut graph = Graph::<&str, i32, Undirected>::new_undirected();
     let mut map: HashMap<&str, NodeIndex> = HashMap::new();
 
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use glam::i32::IVec2 as Point;
 use std::sync::LazyLock;
 
@@ -60,8 +60,7 @@ fn find_duplicate(path: &[Point]) -> Option<Point> {
 }
 
 #[aoc(2016, 1)]
-pub fn main() {
-    let data = aoc_input!(2016, 1).unwrap();
+pub fn main(data: &str) {
     let tokens = data.split(',').map(|x| x.trim()).collect::<Vec<_>>();
 
     // Part I
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use rustc_hash::FxHashMap;
 
 struct Keypad {
@@ -11,13 +11,12 @@ impl Keypad {
         let grid = grid
             .lines()
             .enumerate()
-            .map(|(row, line)| {
+            .flat_map(|(row, line)| {
                 line.chars()
                     .enumerate()
                     .filter(|(_, ch)| *ch != ' ')
                     .map(move |(col, ch)| ((row as i8, col as i8), ch))
             })
-            .flatten()
             .collect::<FxHashMap<(i8, i8), char>>();
         Self { grid, pos }
     }
@@ -50,18 +49,16 @@ impl Keypad {
 }
 
 #[aoc(2016, 2)]
-pub fn main() {
-    let data = aoc_input!(2016, 2).unwrap();
-
+pub fn main(data: &str) {
     // Part I
     let grid: &str = "123\n456\n789";
-    let mut keypad = Keypad::create(&grid, (1, 1));
-    let code = keypad.eval(&data);
+    let mut keypad = Keypad::create(grid, (1, 1));
+    let code = keypad.eval(data);
     println!("{code}");
 
     // Part II
     let grid: &str = "  1\n 234\n56789\n ABC\n  D";
-    let mut keypad = Keypad::create(&grid, (2, 2));
-    let code = keypad.eval(&data);
+    let mut keypad = Keypad::create(grid, (2, 2));
+    let code = keypad.eval(data);
     println!("{code}");
 }
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 
 fn is_triangle(a: u32, b: u32, c: u32) -> bool {
@@ -18,9 +18,8 @@ fn parse(data: &str) -> Vec<(u32, u32, u32)> {
 }
 
 #[aoc(2016, 3)]
-pub fn main() {
-    let data = aoc_input!(2016, 3).unwrap();
-    let nums = parse(&data);
+pub fn main(data: &str) {
+    let nums = parse(data);
 
     // Part I
     let n = nums.iter().filter(|t| is_triangle(t.0, t.1, t.2)).count();
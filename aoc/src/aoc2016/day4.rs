@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use rustc_hash::FxHashMap;
 use std::rc::Rc;
@@ -14,14 +14,14 @@ impl<'a> TryFrom<&'a str> for Entry<'a> {
     type Error = ();
 
     fn try_from(value: &'a str) -> Result<Self, Self::Error> {
-        if let Some((name_sector, checksum)) = value.trim_end_matches(']').split_once('[') {
-            if let Some((name, sector)) = name_sector.rsplit_once('-') {
-                return Ok(Entry {
-                    name: Rc::from(name),
-                    sector: sector.parse::<usize>().or_else(|_| Err(()))?,
-                    checksum,
-                });
-            }
+        if let Some((name_sector, checksum)) = value.trim_end_matches(']').split_once('[')
+            && let Some((name, sector)) = name_sector.rsplit_once('-')
+        {
+            return Ok(Entry {
+                name: Rc::from(name),
+                sector: sector.parse::<usize>().map_err(|_| ())?,
+                checksum,
+            });
         }
         Err(())
     }
@@ -47,7 +47,7 @@ impl<'a> Entry<'a> {
             .take(5)
             .map(|x| x.1)
             .collect::<String>();
-        &hash == self.checksum
+        hash == self.checksum
     }
 
     fn decrypt(&self) -> Entry<'a> {
@@ -70,12 +70,8 @@ impl<'a> Entry<'a> {
 }
 
 #[aoc(2016, 4)]
-pub fn main() {
-    let data = aoc_input!(2016, 4).unwrap();
-    let entries = data
-        .lines()
-        .flat_map(|l| Entry::try_from(l))
-        .collect::<Vec<_>>();
+pub fn main(data: &str) {
+    let entries = data.lines().flat_map(Entry::try_from).collect::<Vec<_>>();
     let mut rooms = entries.iter().filter(|e| e.is_room());
 
     // Part I
@@ -13,7 +13,7 @@ fn check5(digest: &md5::Digest) -> bool {
 
 fn hex_to_char(x: u8) -> char {
     let shift = if x < 10 { 48 } else { 87 };
-    return (shift + x) as char;
+    (shift + x) as char
 }
 
 #[aoc(2016, 5)]
@@ -1,34 +1,64 @@
-use aoc::{aoc, aoc_input};
-use itertools::Itertools;
-
-fn correct_error(grid: &[u8], key_fn: impl Fn(usize) -> usize) -> String {
-    let width = *grid.iter().find(|ch| ch.is_ascii_whitespace()).unwrap() as usize - 2;
-
-    (0..width)
-        .filter_map(|i| {
-            grid.iter()
-                .skip(i)
-                .filter(|ch| !ch.is_ascii_whitespace())
-                .step_by(width)
-                .map(|ch| *ch as char)
-                .counts()
-                .iter()
-                .max_by_key(|(_, v)| key_fn(**v))
-                .map(|(k, _)| *k)
-        })
-        .collect()
+use aoc::{aoc, counter::Counter};
+
+/// Character frequencies of every column of the repeated message.
+fn columns(data: &str) -> Vec<Counter<char>> {
+    let mut columns = vec![];
+
+    for line in data.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        columns.resize_with(line.len(), Counter::new);
+        for (column, ch) in columns.iter_mut().zip(line.chars()) {
+            column.increment(ch);
+        }
+    }
+
+    columns
+}
+
+fn correct_error(
+    columns: &[Counter<char>],
+    pick: impl Fn(&Counter<char>) -> Vec<(&char, usize)>,
+) -> String {
+    columns.iter().map(|column| *pick(column)[0].0).collect()
 }
 
 #[aoc(2016, 6)]
-pub fn main() {
-    let data = aoc_input!(2016, 6).unwrap();
-    let grid = data.as_bytes();
+pub fn main(data: &str) {
+    let columns = columns(data);
 
     // Part I
-    let msg = correct_error(grid, |x| x);
+    let msg = correct_error(&columns, |c| c.most_common(1));
     println!("{msg}");
 
     // Part II
-    let msg = correct_error(grid, |x| usize::MAX - x);
+    let msg = correct_error(&columns, |c| c.least_common(1));
     println!("{msg}");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = "eedadn
+drvtee
+eandsr
+raavrd
+atevrs
+tsrnev
+sdttsa
+rasrtv
+nssdts
+ntnada
+svetve
+tesnvt
+vntsnd
+vrdear
+dvrsen
+enarar";
+
+    #[test]
+    fn test_correct_error() {
+        let columns = columns(EXAMPLE);
+        assert_eq!(correct_error(&columns, |c| c.most_common(1)), "easter");
+        assert_eq!(correct_error(&columns, |c| c.least_common(1)), "advent");
+    }
+}
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use rustc_hash::FxHashSet;
 
 fn is_abba(txt: &[u8]) -> bool {
@@ -58,9 +58,7 @@ fn is_ssl(txt: &[u8]) -> bool {
 }
 
 #[aoc(2016, 7)]
-pub fn main() {
-    let data = aoc_input!(2016, 7).unwrap();
-
+pub fn main(data: &str) {
     // Part I
     let n = data
         .trim()
@@ -84,7 +82,7 @@ mod test {
 
     #[test]
     fn test_part1() {
-        let examples = vec![
+        let examples = [
             "abba[mnop]qrst",
             "abcd[bddb]xyyx",
             "aaaa[qwer]tyui",
@@ -99,7 +97,7 @@ mod test {
 
     #[test]
     fn test_part2() {
-        let examples = vec!["aba[bab]xyz", "xyx[xyx]xyx", "aaa[kek]eke", "zazbz[bzb]cdb"];
+        let examples = ["aba[bab]xyz", "xyx[xyx]xyx", "aaa[kek]eke", "zazbz[bzb]cdb"];
         let expected = [true, false, true, true];
 
         for (case, exp) in examples.iter().zip(expected) {
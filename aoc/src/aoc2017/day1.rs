@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 
 fn solve_captcha(data: &str, shift: usize) -> usize {
     let line = data.lines().next().unwrap().as_bytes();
@@ -15,16 +15,14 @@ fn solve_captcha(data: &str, shift: usize) -> usize {
 }
 
 #[aoc(2017, 1)]
-pub fn main() {
-    let data = aoc_input!(2017, 1).unwrap();
-
+pub fn main(data: &str) {
     // Part I
-    let sum = solve_captcha(&data, 1);
+    let sum = solve_captcha(data, 1);
     println!("{sum}");
 
     // Part II
     let n = data.find('\n').unwrap();
-    let sum = solve_captcha(&data, n / 2);
+    let sum = solve_captcha(data, n / 2);
     println!("{sum}");
 }
 
@@ -35,20 +33,20 @@ mod test {
 
     #[test]
     fn test_part1() {
-        let data = vec!["1122", "1111", "1234", "91212129"];
+        let data = ["1122", "1111", "1234", "91212129"];
         let expected = vec![3, 4, 0, 9];
 
-        for (txt, count) in data.iter().zip(expected.into_iter()) {
+        for (txt, count) in data.iter().zip(expected) {
             assert_eq!(solve_captcha(txt, 1), count);
         }
     }
 
     #[test]
     fn test_part2() {
-        let data = vec!["1212", "1221", "123425", "123123", "12131415"];
+        let data = ["1212", "1221", "123425", "123123", "12131415"];
         let expected = vec![6, 0, 4, 12, 4];
 
-        for (txt, count) in data.iter().zip(expected.into_iter()) {
+        for (txt, count) in data.iter().zip(expected) {
             let n = txt.len();
             assert_eq!(solve_captcha(txt, n / 2), count);
         }
@@ -1,9 +1,8 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::{Itertools, MinMaxResult};
 
 #[aoc(2017, 2)]
-pub fn main() {
-    let data = aoc_input!(2017, 2).unwrap();
+pub fn main(data: &str) {
     let spreadsheet: Vec<Vec<i32>> = data
         .trim()
         .lines()
@@ -19,7 +19,7 @@ pub fn main() {
     let (mut i, mut j) = (0, 0);
 
     'outer: for s in (1..).step_by(2) {
-        for (ds, di, dj) in vec![(0, 1, 0), (0, 0, -1), (1, -1, 0), (1, 0, 1)] {
+        for (ds, di, dj) in [(0, 1, 0), (0, 0, -1), (1, -1, 0), (1, 0, 1)] {
             for _ in 0..s + ds {
                 (i, j) = (i + di, j + dj);
 
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use rustc_hash::FxHashSet;
 use std::hash::Hash;
@@ -23,8 +23,7 @@ fn char_counts(word: &str) -> Vec<(char, usize)> {
 }
 
 #[aoc(2017, 4)]
-pub fn main() {
-    let data = aoc_input!(2017, 4).unwrap();
+pub fn main(data: &str) {
     let lines = data.trim().lines();
 
     // Part I
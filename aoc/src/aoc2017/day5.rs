@@ -1,10 +1,10 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 
-fn exit<F>(offsets: &Vec<i32>, f: F) -> usize
+fn exit<F>(offsets: &[i32], f: F) -> usize
 where
     F: Fn(&mut i32),
 {
-    let mut offsets = offsets.clone();
+    let mut offsets = offsets.to_vec();
     let (mut i, mut s) = (0, 0);
 
     while let Some(offset) = offsets.get_mut(i as usize) {
@@ -17,8 +17,7 @@ where
 }
 
 #[aoc(2017, 5)]
-pub fn main() {
-    let data = aoc_input!(2017, 5).unwrap();
+pub fn main(data: &str) {
     let offsets = data
         .trim()
         .lines()
@@ -1,9 +1,8 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use rustc_hash::FxHashSet;
 
 #[aoc(2018, 1)]
-pub fn main() {
-    let data = aoc_input!(2018, 1).unwrap();
+pub fn main(data: &str) {
     let shifts = data
         .lines()
         .flat_map(|x| x.parse::<i32>())
@@ -1,10 +1,8 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 
 #[aoc(2018, 2)]
-pub fn main() {
-    let data = aoc_input!(2018, 2).unwrap();
-
+pub fn main(data: &str) {
     // Part I
     let check = data
         .trim()
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use rustc_hash::FxHashMap;
 
 struct Claim {
@@ -41,9 +41,8 @@ fn claim(grid: &mut FxHashMap<(usize, usize), Vec<usize>>, claim: &Claim) {
 }
 
 #[aoc(2018, 3)]
-pub fn main() {
-    let data = aoc_input!(2018, 3).unwrap();
-    let claims = parse(&data);
+pub fn main(data: &str) {
+    let claims = parse(data);
     let mut grid = FxHashMap::default();
 
     // Part I
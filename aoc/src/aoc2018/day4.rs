@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input, counter::Counter};
+use aoc::{aoc, counter::Counter};
 use itertools::Itertools;
 use std::{fmt::Debug, num::ParseIntError};
 
@@ -14,9 +14,9 @@ impl TryFrom<&str> for Event {
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         if value.contains("wakes") {
-            return Ok(Self::WakeUp);
+            Ok(Self::WakeUp)
         } else if value.contains("falls") {
-            return Ok(Self::FallAsleep);
+            Ok(Self::FallAsleep)
         } else {
             let (_, line) = value.split_once('#').ok_or(())?;
             let (num, _) = line.split_once(' ').ok_or(())?;
@@ -98,7 +98,7 @@ impl Ord for DateTime {
 
 impl PartialOrd for DateTime {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.tuple().partial_cmp(&other.tuple())
+        Some(self.cmp(other))
     }
 }
 
@@ -176,11 +176,10 @@ fn solve(records: &[Record], strategy: Strategy) -> Option<usize> {
 }
 
 #[aoc(2018, 4)]
-pub fn main() {
-    let data = aoc_input!(2018, 4).unwrap();
+pub fn main(data: &str) {
     let records = data
         .lines()
-        .flat_map(|line| Record::try_from(line))
+        .flat_map(Record::try_from)
         .sorted_unstable_by_key(|r| r.datetime.clone())
         .collect::<Vec<_>>();
 
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 
 static DIFF: u8 = 32;
 
@@ -59,8 +59,7 @@ fn improved(polymer: &str) -> Option<usize> {
 }
 
 #[aoc(2018, 5)]
-pub fn main() {
-    let data = aoc_input!(2018, 5).unwrap();
+pub fn main(data: &str) {
     let polymer = data.trim();
 
     // Part I
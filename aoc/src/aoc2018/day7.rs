@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::{cmp::Reverse, collections::BinaryHeap};
 
@@ -167,9 +167,8 @@ fn time_to_complete(g: &mut Graph) -> usize {
 }
 
 #[aoc(2018, 7)]
-pub fn main() {
-    let data = aoc_input!(2018, 7).unwrap();
-    let mut graph = Graph::try_from(data.as_str()).unwrap();
+pub fn main(data: &str) {
+    let mut graph = Graph::try_from(data).unwrap();
 
     // Part I
     println!("{}", str::from_utf8(&lexicographic_sort(&graph)).unwrap());
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 
 #[inline(always)]
 fn reduce_fuel(x: &u32) -> u32 {
@@ -19,8 +19,7 @@ fn reduce_fuel_total(x: &u32) -> u32 {
 }
 
 #[aoc(2019, 1)]
-pub fn main() {
-    let data = aoc_input!(2019, 1).unwrap();
+pub fn main(data: &str) {
     let fuels = data
         .lines()
         .flat_map(|x| x.parse::<u32>())
@@ -1,36 +1,19 @@
-use aoc::{aoc, aoc_input};
-
-fn eval(input: &mut [usize]) -> usize {
-    let mut i = 0;
-
-    loop {
-        let op = input[i];
-
-        if op == 99 {
-            break;
-        }
-
-        let (j, k, m) = (input[i + 1], input[i + 2], input[i + 3]);
-        input[m] = match op {
-            1 => input[j] + input[k],
-            2 => input[j] * input[k],
-            _ => unreachable!("invalid op code"),
-        };
-
-        i += 4;
-    }
-
-    input[0]
+use aoc::aoc;
+use aoc::intcode::IntcodeComputer;
+
+/// Run the gravity assist program with the given noun and verb and return address 0.
+fn eval(program: &IntcodeComputer, noun: i64, verb: i64) -> i64 {
+    let mut computer = program.clone();
+    computer.write(1, noun);
+    computer.write(2, verb);
+    computer.run();
+    computer.read(0)
 }
 
-fn find(input: &[usize], solution: usize) -> Option<(usize, usize)> {
+fn find(program: &IntcodeComputer, solution: i64) -> Option<(i64, i64)> {
     for a in 0..99 {
         for b in 0..99 {
-            let mut tmp = input.to_vec();
-            tmp[1] = a;
-            tmp[2] = b;
-
-            if eval(&mut tmp) == solution {
+            if eval(program, a, b) == solution {
                 return Some((a, b));
             }
         }
@@ -39,21 +22,14 @@ fn find(input: &[usize], solution: usize) -> Option<(usize, usize)> {
 }
 
 #[aoc(2019, 2)]
-pub fn main() {
-    let data = aoc_input!(2019, 2).unwrap();
-    let input = data
-        .split(',')
-        .flat_map(|x| x.parse::<usize>())
-        .collect::<Vec<_>>();
+pub fn main(data: &str) {
+    let program = data.parse::<IntcodeComputer>().unwrap();
 
     // Part I
-    let mut input1 = input.clone();
-    input1[1] = 12;
-    input1[2] = 2;
-    println!("{}", eval(&mut input1));
+    println!("{}", eval(&program, 12, 2));
 
     // Part II
     let output = 19690720;
-    let (noun, verb) = find(&input, output).unwrap();
+    let (noun, verb) = find(&program, output).unwrap();
     println!("{:?}", 100 * noun + verb);
 }
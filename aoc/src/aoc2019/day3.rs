@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use glam::I16Vec2 as Point;
 use rustc_hash::{FxHashMap, FxHashSet};
 
@@ -25,9 +25,7 @@ fn make_wire(path: &Vec<&str>) -> FxHashMap<Point, usize> {
         for _ in 0..n {
             c += ds;
 
-            if !covered.contains_key(&c) {
-                covered.insert(c, i);
-            }
+            covered.entry(c).or_insert(i);
 
             i += 1;
         }
@@ -37,9 +35,8 @@ fn make_wire(path: &Vec<&str>) -> FxHashMap<Point, usize> {
 }
 
 #[aoc(2019, 3)]
-pub fn main() {
-    let data = aoc_input!(2019, 3).unwrap();
-    let (left, right) = parse(&data);
+pub fn main(data: &str) {
+    let (left, right) = parse(data);
     let (wire1, wire2) = (make_wire(&left), make_wire(&right));
     let crossings = wire1
         .keys()
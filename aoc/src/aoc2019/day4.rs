@@ -51,10 +51,10 @@ pub fn main() {
     let (start, end) = (347312, 805915);
 
     // Part I
-    let count = (start..=end).into_iter().filter(|x| x.is_valid()).count();
+    let count = (start..=end).filter(|x| x.is_valid()).count();
     println!("{count}");
 
     // Part II
-    let count = (start..=end).into_iter().filter(|x| x.is_valid2()).count();
+    let count = (start..=end).filter(|x| x.is_valid2()).count();
     println!("{count}");
 }
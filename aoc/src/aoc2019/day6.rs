@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use petgraph::algo::dijkstra;
 use petgraph::graphmap::{DiGraphMap, UnGraphMap};
 
@@ -20,10 +20,9 @@ fn minimum_transfers<'a>(graph: &'a DiGraphMap<&'a str, ()>) -> Option<usize> {
     res.get("SAN").map(|x: &usize| x.saturating_sub(2))
 }
 
-#[aoc(2019, 6)]
-pub fn main() {
-    let data = aoc_input!(2019, 6).unwrap();
-    let graph = parse(&data);
+#[aoc(2019, 6, title = "Universal Orbit Map", tags = ["dijkstra", "graph"])]
+pub fn main(data: &str) {
+    let graph = parse(data);
 
     // Part I
     let n_orbits = count_orbits(&graph);
@@ -66,20 +65,20 @@ I)SAN";
 
     #[test]
     fn test_parse() {
-        let graph = parse(&EXAMPLE1);
+        let graph = parse(EXAMPLE1);
         assert_eq!(graph.node_count(), 12);
     }
 
     #[test]
     fn test_part1() {
-        let graph = parse(&EXAMPLE1);
+        let graph = parse(EXAMPLE1);
         let n = count_orbits(&graph);
         assert_eq!(n, 42);
     }
 
     #[test]
     fn test_part2() {
-        let graph = parse(&EXAMPLE2);
+        let graph = parse(EXAMPLE2);
         let n = minimum_transfers(&graph);
         assert_eq!(n, Some(4));
     }
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 
 fn search(nums: &[i32], total: i32, n: usize) -> Option<i32> {
@@ -9,8 +9,7 @@ fn search(nums: &[i32], total: i32, n: usize) -> Option<i32> {
 }
 
 #[aoc(2020, 1)]
-pub fn main() {
-    let data = aoc_input!(2020, 1).unwrap();
+pub fn main(data: &str) {
     let nums = data.lines().flat_map(|x| x.parse::<i32>()).collect_vec();
 
     // Part I
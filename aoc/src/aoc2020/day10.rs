@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 
 fn fib(x: usize) -> usize {
@@ -9,8 +9,7 @@ fn fib(x: usize) -> usize {
 }
 
 #[aoc(2020, 10)]
-pub fn main() {
-    let data = aoc_input!(2020, 10).unwrap();
+pub fn main(data: &str) {
     let mut adapters = data
         .lines()
         .flat_map(|x| x.parse::<i32>())
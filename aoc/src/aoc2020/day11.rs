@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use rustc_hash::FxHashMap;
 use std::fmt;
@@ -163,13 +163,12 @@ where
 }
 
 #[aoc(2020, 11)]
-pub fn main() {
-    let data = aoc_input!(2020, 11).unwrap();
-    let mut layout = Layout::from(data.as_str());
+pub fn main(data: &str) {
+    let mut layout = Layout::from(data);
     let seat_count1 = solver(&mut layout, |l| l.simulate1());
     println!("{}", seat_count1);
 
-    let mut layout = Layout::from(data.as_str());
+    let mut layout = Layout::from(data);
     let seat_count2 = solver(&mut layout, |l| l.simulate2());
     println!("{}", seat_count2);
 }
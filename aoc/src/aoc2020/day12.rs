@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use glam::IVec2;
 use std::num::ParseIntError;
 
@@ -115,9 +115,8 @@ fn parse(data: &str) -> Vec<Action> {
 }
 
 #[aoc(2020, 12)]
-pub fn main() {
-    let data = aoc_input!(2020, 12).unwrap();
-    let actions = parse(&data);
+pub fn main(data: &str) {
+    let actions = parse(data);
 
     // Part I
     let mut ship = Ship::new();
@@ -1,4 +1,5 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
+use aoc::math::crt;
 
 fn parse(data: &str) -> (u64, Vec<Option<u64>>) {
     let mut lines = data.lines();
@@ -25,36 +26,25 @@ fn find_bus(timestamp: u64, ids: &[Option<u64>]) -> Option<u64> {
         .map(|(id, t)| id * (t - timestamp))
 }
 
-// Part II - Chinese remainder theorem
-fn crt_find(eqs: &[(u64, u64)]) -> Option<u64> {
-    let mut iter = eqs.iter();
-    let (mut a, mut b) = *iter.next()?;
-
-    for &(c, d) in iter {
-        let t = (0..).find(|&i| (a + b * i) % d == c)?;
-        (a, b) = (a + b * t, b * d);
-    }
+// Part II - Chinese remainder theorem: bus at index i departs at t + i
+fn earliest_timestamp(ids: &[Option<u64>]) -> Option<i64> {
+    let eqs = ids
+        .iter()
+        .enumerate()
+        .filter_map(|(i, id)| id.map(|id| (-(i as i64), id as i64)));
 
-    Some(a)
+    crt(eqs).map(|(t, _)| t)
 }
 
 #[aoc(2020, 13)]
-pub fn main() {
-    let data = aoc_input!(2020, 13).unwrap();
-    let (timestamp, ids) = parse(&data);
+pub fn main(data: &str) {
+    let (timestamp, ids) = parse(data);
 
     // Part I
     println!("{}", find_bus(timestamp, &ids).unwrap());
 
     // Part II
-    let eqs = ids
-        .iter()
-        .enumerate()
-        .filter(|x| x.1.is_some())
-        .map(|(i, x)| (i as u64, x.unwrap()))
-        .map(|(i, x)| ((x - (i % x)) % x, x))
-        .collect::<Vec<_>>();
-    println!("{}", crt_find(&eqs).unwrap());
+    println!("{}", earliest_timestamp(&ids).unwrap());
 }
 
 #[cfg(test)]
@@ -73,16 +63,6 @@ mod tests {
     #[test]
     fn test_part2() {
         let (_, ids) = parse(EXAMPLE);
-        let data = ids
-            .iter()
-            .enumerate()
-            .filter(|x| x.1.is_some())
-            .map(|(i, x)| {
-                let x = x.unwrap();
-                ((x - i as u64) % x, x)
-            })
-            .collect::<Vec<_>>();
-
-        assert_eq!(1068781, crt_find(&data).unwrap());
+        assert_eq!(earliest_timestamp(&ids), Some(1068781));
     }
 }
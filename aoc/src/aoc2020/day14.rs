@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::{Itertools, MultiProduct};
 use rustc_hash::FxHashMap;
 use std::{num::ParseIntError, str::FromStr};
@@ -8,9 +8,7 @@ where
     Self::Item: Clone,
 {
     fn product_repeat(self, repeat: usize) -> MultiProduct<Self> {
-        std::iter::repeat(self)
-            .take(repeat)
-            .multi_cartesian_product()
+        std::iter::repeat_n(self, repeat).multi_cartesian_product()
     }
 }
 
@@ -50,8 +48,8 @@ impl BitMask {
 
 #[derive(Debug)]
 enum InputLine {
-    MASK(BitMask),
-    PROG(Program),
+    Mask(BitMask),
+    Prog(Program),
 }
 
 impl FromStr for InputLine {
@@ -60,7 +58,7 @@ impl FromStr for InputLine {
     fn from_str(line: &str) -> Result<Self, Self::Err> {
         if line.starts_with("mask") {
             let (_, bit_mask) = line.split_once(" = ").unwrap();
-            Ok(InputLine::MASK(bit_mask.parse().unwrap()))
+            Ok(InputLine::Mask(bit_mask.parse().unwrap()))
         } else {
             let (addr, val) = line.split_once(" = ").unwrap();
             let addr: u64 = addr
@@ -68,7 +66,7 @@ impl FromStr for InputLine {
                 .trim_end_matches("]")
                 .parse()?;
             let val = val.parse()?;
-            Ok(InputLine::PROG(Program {
+            Ok(InputLine::Prog(Program {
                 value: val,
                 address: addr,
             }))
@@ -77,8 +75,7 @@ impl FromStr for InputLine {
 }
 
 #[aoc(2020, 14)]
-pub fn main() {
-    let data = aoc_input!(2020, 14).unwrap();
+pub fn main(data: &str) {
     let instructions = data
         .trim()
         .lines()
@@ -91,8 +88,8 @@ pub fn main() {
 
     for instruction in &instructions {
         match instruction {
-            InputLine::MASK(m) => mask = m,
-            InputLine::PROG(prog) => {
+            InputLine::Mask(m) => mask = m,
+            InputLine::Prog(prog) => {
                 let val = mask.apply(prog.value);
                 memory.insert(prog.address, val);
             }
@@ -108,11 +105,11 @@ pub fn main() {
 
     for instruction in &instructions {
         match instruction {
-            InputLine::MASK(m) => {
+            InputLine::Mask(m) => {
                 mask = m;
                 x_mask = m.ones ^ m.zeros;
             }
-            InputLine::PROG(prog) => {
+            InputLine::Prog(prog) => {
                 let mut addr0 = prog.address | mask.zeros;
                 let mut x_mask = x_mask;
                 let mut addresses = vec![];
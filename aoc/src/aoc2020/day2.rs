@@ -1,9 +1,8 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 
 #[aoc(2020, 2)]
-pub fn main() {
-    let data = aoc_input!(2020, 2).unwrap();
+pub fn main(data: &str) {
     let input = data
         .lines()
         .flat_map(|l| l.splitn(3, ' ').collect_tuple())
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 
 fn count_trees<'a>(mut map: impl Iterator<Item = &'a str>, shift: usize) -> usize {
     let n = map.next().unwrap().len();
@@ -8,8 +8,7 @@ fn count_trees<'a>(mut map: impl Iterator<Item = &'a str>, shift: usize) -> usiz
 }
 
 #[aoc(2020, 3)]
-pub fn main() {
-    let data = aoc_input!(2020, 3).unwrap();
+pub fn main(data: &str) {
     let iter = data.lines().filter(|x| !x.is_empty());
 
     // Part I
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::sync::LazyLock;
 
@@ -52,8 +52,7 @@ fn is_valid2(passport: &&FxHashMap<&str, &str>) -> bool {
 }
 
 #[aoc(2020, 4)]
-pub fn main() {
-    let data = aoc_input!(2020, 4).unwrap();
+pub fn main(data: &str) {
     let passports = data
         .split("\n\n")
         .map(|line| line.split([' ', '\n']).collect::<Vec<_>>())
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 
 fn seat_id(code: &str) -> usize {
@@ -10,8 +10,7 @@ fn seat_id(code: &str) -> usize {
 }
 
 #[aoc(2020, 5)]
-pub fn main() {
-    let data = aoc_input!(2020, 5).unwrap();
+pub fn main(data: &str) {
     let ids: Vec<usize> = data.trim_end().lines().map(seat_id).sorted().collect();
 
     // Part I
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use std::borrow::Borrow;
 use std::collections::HashSet;
@@ -24,8 +24,7 @@ where
 }
 
 #[aoc(2020, 6)]
-pub fn main() {
-    let data = aoc_input!(2020, 6).unwrap();
+pub fn main(data: &str) {
     let answers: Vec<Vec<HashSet<char>>> = data
         .trim_end()
         .split("\n\n")
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use regex_lite::Regex;
 use rustc_hash::FxHashMap;
 
@@ -68,9 +68,8 @@ impl<'a> Bag<'a> {
 }
 
 #[aoc(2020, 7)]
-pub fn main() {
-    let data = aoc_input!(2020, 7).unwrap();
-    let mut bag = Bag::from_input(&data);
+pub fn main(data: &str) {
+    let mut bag = Bag::from_input(data);
     bag.set_color("shiny gold");
 
     // Part I
@@ -1,53 +1,103 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
+use aoc::vm::{Cpu, Exit, Instruction, Jump, Machine, parse_program, tokens};
+use std::str::FromStr;
 
-fn exe(prog: &[(&str, i32)]) -> (bool, i32) {
-    let (mut acc, mut i) = (0, 0);
-    let mut executed = vec![false; prog.len()];
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Acc(i64),
+    Jmp(i64),
+    Nop(i64),
+}
+
+impl FromStr for Op {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (op, args) = tokens(s);
+        let val = args
+            .first()
+            .and_then(|x| x.parse().ok())
+            .ok_or(format!("Invalid argument in: {s}"))?;
 
-    loop {
-        match executed.get_mut(i) {
-            Some(&mut true) => return (false, acc),
-            Some(x @ &mut false) => *x = true,
-            _ => return (true, acc),
+        match op {
+            "acc" => Ok(Self::Acc(val)),
+            "jmp" => Ok(Self::Jmp(val)),
+            "nop" => Ok(Self::Nop(val)),
+            _ => Err(format!("Unknown instruction: {s}")),
         }
+    }
+}
+
+impl Instruction for Op {
+    type Word = i64;
+
+    fn execute(&self, cpu: &mut Cpu<i64>) -> Jump {
+        match *self {
+            Self::Acc(val) => cpu.registers[0] += val,
+            Self::Jmp(val) => return Jump::Relative(val),
+            Self::Nop(_) => {}
+        }
+        Jump::Next
+    }
+}
+
+/// Run the boot code from scratch. Returns whether it terminated and the accumulator.
+fn exe(machine: &mut Machine<Op>) -> (bool, i64) {
+    machine.reset(&[0]);
+    let exit = machine.run();
+    (exit == Exit::Halted, machine.register(0))
+}
 
-        match prog[i] {
-            ("acc", val) => (acc, i) = (acc + val, i + 1),
-            ("jmp", val) => i = i.saturating_add_signed(val as isize),
-            _ => i += 1,
+/// Swap a single `jmp` and `nop` so that the boot code terminates.
+fn repair(machine: &mut Machine<Op>) -> Option<i64> {
+    for i in 0..machine.program().len() {
+        let original = machine.program()[i];
+        machine.program_mut()[i] = match original {
+            Op::Jmp(val) => Op::Nop(val),
+            Op::Nop(val) => Op::Jmp(val),
+            Op::Acc(_) => continue,
+        };
+
+        let (terminated, acc) = exe(machine);
+        machine.program_mut()[i] = original;
+        if terminated {
+            return Some(acc);
         }
     }
+    None
 }
 
 #[aoc(2020, 8)]
-pub fn main() {
-    let data = aoc_input!(2020, 8).unwrap();
-    let prog = data
-        .lines()
-        .filter_map(|l| l.trim().split_once(' '))
-        .map(|(cmd, val)| (cmd, val.parse::<i32>().unwrap()))
-        .collect::<Vec<_>>();
+pub fn main(data: &str) {
+    let mut machine = Machine::new(parse_program(data).unwrap(), 1);
+    machine.set_loop_detection(true);
 
     // Part I
-    println!("{:?}", exe(&prog).1);
+    println!("{:?}", exe(&mut machine).1);
 
     // Part II
-    for (i, (cmd, val)) in prog
-        .iter()
-        .enumerate()
-        .filter(|(_, (c, _))| ["nop", "jmp"].contains(c))
-    {
-        let mut new_prog = prog.clone();
-
-        match *cmd {
-            "nop" => new_prog[i] = ("jmp", *val),
-            "jmp" => new_prog[i] = ("nop", *val),
-            _ => {}
-        }
+    println!("{}", repair(&mut machine).unwrap());
+}
 
-        if let (true, acc) = exe(&new_prog) {
-            println!("{}", acc);
-            break;
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = "nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6";
+
+    #[test]
+    fn test_example() {
+        let mut machine = Machine::new(parse_program(EXAMPLE).unwrap(), 1);
+        machine.set_loop_detection(true);
+        assert_eq!(exe(&mut machine), (false, 5));
+        assert_eq!(repair(&mut machine), Some(8));
     }
 }
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::FoldWhile::{Continue, Done};
 use itertools::Itertools;
 use itertools::MinMaxResult::MinMax;
@@ -41,8 +41,7 @@ fn find_subset(nums: &[i64], target: i64) -> Option<&[i64]> {
 }
 
 #[aoc(2020, 9)]
-pub fn main() {
-    let data = aoc_input!(2020, 9).unwrap();
+pub fn main(data: &str) {
     let nums = data
         .lines()
         .flat_map(|x| x.parse::<i64>())
@@ -1,8 +1,7 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 
 #[aoc(2021, 1)]
-pub fn main() {
-    let data = aoc_input!(2021, 1).unwrap();
+pub fn main(data: &str) {
     let measurements = data
         .lines()
         .flat_map(|x| x.parse::<u32>())
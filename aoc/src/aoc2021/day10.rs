@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::sync::LazyLock;
@@ -25,10 +25,10 @@ fn check(line: &[u8], stack: &mut Vec<u8>) -> Option<u8> {
         match OPEN.contains(&ch) {
             true => stack.push(*ch),
             false => {
-                if let Some(x) = stack.pop() {
-                    if PAIRS.get(&x) != Some(ch) {
-                        return Some(*ch);
-                    }
+                if let Some(x) = stack.pop()
+                    && PAIRS.get(&x) != Some(ch)
+                {
+                    return Some(*ch);
                 }
             }
         }
@@ -36,7 +36,7 @@ fn check(line: &[u8], stack: &mut Vec<u8>) -> Option<u8> {
     None
 }
 
-fn get_score(stack: &mut Vec<u8>) -> usize {
+fn get_score(stack: &[u8]) -> usize {
     let score = FxHashMap::from_iter([(b')', 1), (b']', 2), (b'}', 3), (b'>', 4)]);
 
     stack
@@ -47,8 +47,7 @@ fn get_score(stack: &mut Vec<u8>) -> usize {
 }
 
 #[aoc(2021, 10)]
-pub fn main() {
-    let data = aoc_input!(2021, 10).unwrap();
+pub fn main(data: &str) {
     let lines = data.trim().lines().map(|line| line.as_bytes());
 
     // Part I
@@ -66,7 +65,7 @@ pub fn main() {
             let mut stack = vec![];
 
             match check(bytes, &mut stack) {
-                None => Some(get_score(&mut stack)),
+                None => Some(get_score(&stack)),
                 Some(_) => None,
             }
         })
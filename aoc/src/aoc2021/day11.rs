@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::ops::AddAssign;
@@ -107,9 +107,8 @@ impl Cavern {
 }
 
 #[aoc(2021, 11)]
-pub fn main() {
-    let data = aoc_input!(2021, 11).unwrap();
-    let energy = EnergyState::try_from(data.as_str()).unwrap();
+pub fn main(data: &str) {
+    let energy = EnergyState::try_from(data).unwrap();
 
     // Part I
     let mut c = Cavern::new(energy.clone());
@@ -1,13 +1,13 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::{collections::VecDeque, fmt::Debug};
 
 #[derive(PartialEq, Eq, Hash, Debug, Clone)]
 enum Cave {
-    START,
-    END,
-    SMALL(String),
-    BIG(String),
+    Start,
+    End,
+    Small(String),
+    Big(String),
 }
 
 impl From<&str> for Cave {
@@ -16,10 +16,10 @@ impl From<&str> for Cave {
         let upper = value.to_ascii_uppercase();
 
         match value {
-            "start" => Cave::START,
-            "end" => Cave::END,
-            _ if value == lower => Cave::SMALL(lower),
-            _ if value == upper => Cave::BIG(upper),
+            "start" => Cave::Start,
+            "end" => Cave::End,
+            _ if value == lower => Cave::Small(lower),
+            _ if value == upper => Cave::Big(upper),
             _ => unreachable!(),
         }
     }
@@ -33,7 +33,7 @@ impl CaveSystem {
     where
         T: Into<Cave>,
     {
-        self.0.entry(a.into()).or_insert(FxHashSet::default())
+        self.0.entry(a.into()).or_default()
     }
 
     fn add_edge<T>(&mut self, a: T, b: T)
@@ -47,28 +47,26 @@ impl CaveSystem {
     fn count_paths(&self, extra_visit: bool) -> usize {
         let mut paths = 0;
         let mut queue = VecDeque::default();
-        queue.push_back((vec![Cave::START], false));
+        queue.push_back((vec![Cave::Start], false));
 
         while let Some((path, mut has_extra_visit)) = queue.pop_front() {
             if let Some(cave) = path.last() {
                 match cave {
-                    Cave::START => {
-                        if path.len() > 1 {
-                            continue;
-                        }
+                    Cave::Start if path.len() > 1 => {
+                        continue;
                     }
-                    Cave::END => {
+                    Cave::End => {
                         paths += 1;
                         continue;
                     }
-                    Cave::SMALL(_) => {
-                        if let Some((_, prev_path)) = path.split_last() {
-                            if prev_path.contains(cave) {
-                                match (extra_visit, has_extra_visit) {
-                                    (true, true) => continue,
-                                    (true, false) => has_extra_visit = true,
-                                    (false, _) => continue,
-                                }
+                    Cave::Small(_) => {
+                        if let Some((_, prev_path)) = path.split_last()
+                            && prev_path.contains(cave)
+                        {
+                            match (extra_visit, has_extra_visit) {
+                                (true, true) => continue,
+                                (true, false) => has_extra_visit = true,
+                                (false, _) => continue,
                             }
                         }
                     }
@@ -90,8 +88,7 @@ impl CaveSystem {
 }
 
 #[aoc(2021, 12)]
-pub fn main() {
-    let data = aoc_input!(2021, 12).unwrap();
+pub fn main(data: &str) {
     let mut system = CaveSystem::default();
 
     for line in data.trim().lines() {
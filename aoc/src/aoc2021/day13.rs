@@ -1,4 +1,5 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
+use aoc::ocr;
 use itertools::Itertools;
 use num::Integer;
 use std::{fmt::Display, num::ParseIntError};
@@ -57,16 +58,23 @@ impl BoolArray2 {
         }
     }
 
+    fn rows(&self) -> Vec<Vec<bool>> {
+        self.data
+            .chunks(self.shape.1)
+            .map(<[bool]>::to_vec)
+            .collect()
+    }
+
     fn _fold_y(&self, line: usize) -> Self {
         let w = self.shape.1;
         let mut data = self.data[..line * w].to_vec();
         let out = &self.data[(line + 1) * w..];
 
-        let (n, m) = (data.len(), out.len());
+        let n = data.len();
 
-        for i in 0..m {
+        for (i, &x) in out.iter().enumerate() {
             let (j, k) = i.div_rem(&w);
-            data[n - (j + 1) * w + k] |= out[i];
+            data[n - (j + 1) * w + k] |= x;
         }
 
         Self {
@@ -121,7 +129,10 @@ fn apply_folds<'a>(
     Ok(sheet)
 }
 
-fn parse(data: &str) -> Result<(Vec<(i32, i32)>, Vec<(&str, i32)>), ParseIntError> {
+type Dot = (i32, i32);
+type Fold<'a> = (&'a str, i32);
+
+fn parse(data: &str) -> Result<(Vec<Dot>, Vec<Fold<'_>>), ParseIntError> {
     let (mut dots, mut folds) = (vec![], vec![]);
     let mut is_fold = false;
 
@@ -140,8 +151,7 @@ fn parse(data: &str) -> Result<(Vec<(i32, i32)>, Vec<(&str, i32)>), ParseIntErro
             if let Some((x, y)) = line
                 .split_whitespace()
                 .last()
-                .map(|l| l.split_once('='))
-                .flatten()
+                .and_then(|l| l.split_once('='))
             {
                 folds.push((x, y.parse::<i32>()?));
             }
@@ -152,9 +162,8 @@ fn parse(data: &str) -> Result<(Vec<(i32, i32)>, Vec<(&str, i32)>), ParseIntErro
 }
 
 #[aoc(2021, 13)]
-pub fn main() {
-    let data = aoc_input!(2021, 13).unwrap();
-    let (dots, folds) = parse(&data).unwrap();
+pub fn main(data: &str) {
+    let (dots, folds) = parse(data).unwrap();
     let sheet = get_sheet(&dots).unwrap();
 
     // Part I
@@ -163,5 +172,8 @@ pub fn main() {
 
     // Part II
     let res = apply_folds(&sheet, folds).unwrap();
-    println!("{res}");
+    match ocr::recognize(&res.rows()) {
+        Ok(code) => println!("{code}"),
+        Err(_) => println!("{res}"),
+    }
 }
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input, counter::Counter};
+use aoc::{aoc, counter::Counter};
 use itertools::Itertools;
 use rustc_hash::FxHashMap;
 
@@ -28,17 +28,15 @@ fn insert_pairs(pairs: &Counter<(u8, u8)>, rules: &FxHashMap<(u8, u8), u8>) -> C
 }
 
 fn score(pairs: &Counter<(u8, u8)>) -> usize {
-    let mut counter: Counter<u8> = Counter::default();
-
-    for (pair, count) in pairs {
-        counter.increment_by(pair.0, *count);
-        counter.increment_by(pair.1, *count);
-    }
+    // Every element is counted twice, except for the two ends of the polymer
+    let counter = pairs
+        .iter()
+        .flat_map(|(pair, &count)| [(pair.0, count), (pair.1, count)])
+        .collect::<Counter<u8>>();
 
-    match counter.values().minmax() {
-        itertools::MinMaxResult::MinMax(a, b) => (b + 1) / 2 - (a + 1) / 2,
-        _ => unreachable!(),
-    }
+    let (_, most) = counter.most_common(1)[0];
+    let (_, least) = counter.least_common(1)[0];
+    most.div_ceil(2) - least.div_ceil(2)
 }
 
 fn parse(data: &str) -> (&str, FxHashMap<(u8, u8), u8>) {
@@ -56,9 +54,8 @@ fn parse(data: &str) -> (&str, FxHashMap<(u8, u8), u8>) {
 }
 
 #[aoc(2021, 14)]
-pub fn main() {
-    let data = aoc_input!(2021, 14).unwrap();
-    let (template, rules) = parse(&data);
+pub fn main(data: &str) {
+    let (template, rules) = parse(data);
 
     // Part I
     println!("{}", score(&grow(template, &rules, 10)));
@@ -66,3 +63,34 @@ pub fn main() {
     // Part I
     println!("{}", score(&grow(template, &rules, 40)));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = "NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C";
+
+    #[test]
+    fn test_score() {
+        let (template, rules) = parse(EXAMPLE);
+        assert_eq!(score(&grow(template, &rules, 10)), 1588);
+        assert_eq!(score(&grow(template, &rules, 40)), 2188189693529);
+    }
+}
@@ -1,6 +1,6 @@
+use aoc::aoc;
 use aoc::graph::parse_graph;
-use aoc::{aoc, aoc_input};
-use petgraph::algo::{astar, Measure};
+use petgraph::algo::{Measure, astar};
 use petgraph::graph::DiGraph;
 use petgraph::visit::EdgeRef;
 use std::cmp::max;
@@ -11,7 +11,7 @@ where
     T: Copy + Debug + Default + Measure,
 {
     let start = graph.node_indices().next().unwrap();
-    let goal = graph.node_indices().last().unwrap();
+    let goal = graph.node_indices().next_back().unwrap();
     let path = astar(
         &graph,
         start,
@@ -60,17 +60,15 @@ fn extend(data: &str) -> String {
     lines.join("\n")
 }
 
-#[aoc(2021, 15)]
-pub fn main() {
-    let data = aoc_input!(2021, 15).unwrap();
-
+#[aoc(2021, 15, title = "Chiton", tags = ["astar", "grid"])]
+pub fn main(data: &str) {
     // Part I
-    let graph: DiGraph<u32, ()> = parse_graph(&data, |c| c.parse::<u32>().unwrap());
+    let graph: DiGraph<u32, ()> = parse_graph(data, |c| c.parse::<u32>().unwrap());
     let dist = shortest_path(&graph).unwrap();
     println!("{}", dist);
 
     // Part II
-    let data2 = extend(&data);
+    let data2 = extend(data);
     let graph = parse_graph(&data2, |c| c.parse::<u32>().unwrap());
     let dist = shortest_path(&graph).unwrap();
     println!("{}", dist);
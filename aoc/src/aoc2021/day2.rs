@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 
 fn parse(data: &str) -> Vec<(&str, i32)> {
     data.lines()
@@ -41,9 +41,8 @@ fn find_aim(commands: &[(&str, i32)]) -> i32 {
 }
 
 #[aoc(2021, 2)]
-pub fn main() {
-    let data = aoc_input!(2021, 2).unwrap();
-    let commands = parse(&data);
+pub fn main(data: &str) {
+    let commands = parse(data);
 
     // Part I
     println!("{}", find_pos(&commands));
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use std::{num::ParseIntError, str::FromStr};
 
 #[derive(Clone)]
@@ -37,7 +37,7 @@ impl Report {
     }
 
     fn size(&self) -> Option<u8> {
-        self.0.get(0).map(|x| x.size)
+        self.0.first().map(|x| x.size)
     }
 }
 
@@ -116,14 +116,11 @@ fn rating(report: &Report, cond: impl Fn(&Report, u8) -> bool) -> Option<usize>
         }
     }
 
-    match report.0.get(0) {
-        Some(num) => Some(num.data.into()),
-        _ => None,
-    }
+    report.0.first().map(|num| num.data.into())
 }
 
 fn oxygen_generator_rating(report: &Report) -> Option<usize> {
-    rating(report, |report, i| most_common_bit(report, i))
+    rating(report, most_common_bit)
 }
 
 fn co2_scrubber_rating(report: &Report) -> Option<usize> {
@@ -131,9 +128,8 @@ fn co2_scrubber_rating(report: &Report) -> Option<usize> {
 }
 
 #[aoc(2021, 3)]
-pub fn main() {
-    let data = aoc_input!(2021, 3).unwrap();
-    let report = Report::from_str(&data).unwrap();
+pub fn main(data: &str) {
+    let report = Report::from_str(data).unwrap();
 
     // Part I
     println!("{}", power_consumption(&report));
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use std::num::ParseIntError;
 use tinyvec::ArrayVec;
 
@@ -58,8 +58,7 @@ impl Board {
 }
 
 #[aoc(2021, 4)]
-pub fn main() {
-    let data = aoc_input!(2021, 4).unwrap();
+pub fn main(data: &str) {
     let mut segments = data.split("\n\n");
     let nums = segments
         .next()
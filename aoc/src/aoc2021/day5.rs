@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use rustc_hash::FxHashMap;
 use std::num::ParseIntError;
 
@@ -59,8 +59,7 @@ fn count_overlap<'a>(lines: impl IntoIterator<Item = &'a Line>) -> usize {
 }
 
 #[aoc(2021, 5)]
-pub fn main() {
-    let data = aoc_input!(2021, 5).unwrap();
+pub fn main(data: &str) {
     let lines = data
         .trim()
         .lines()
@@ -1,6 +1,6 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 
-fn advance(state: &Vec<usize>) -> Vec<usize> {
+fn advance(state: &[usize]) -> Vec<usize> {
     let mut new_state = vec![0; 9];
 
     for (i, &cnt) in state.iter().enumerate() {
@@ -15,8 +15,8 @@ fn advance(state: &Vec<usize>) -> Vec<usize> {
     new_state
 }
 
-fn simulate(state: &Vec<usize>, n: usize) -> usize {
-    let mut state = state.clone();
+fn simulate(state: &[usize], n: usize) -> usize {
+    let mut state = state.to_vec();
 
     for _ in 0..n {
         state = advance(&state);
@@ -36,9 +36,8 @@ fn parse(line: &str) -> Vec<usize> {
 }
 
 #[aoc(2021, 6)]
-pub fn main() {
-    let data = aoc_input!(2021, 6).unwrap();
-    let state = parse(&data);
+pub fn main(data: &str) {
+    let state = parse(data);
 
     // Part I
     println!("{}", simulate(&state, 80));
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 
 fn optimize<F>(positions: &[i32], cost: F) -> Option<i32>
@@ -20,8 +20,7 @@ where
 }
 
 #[aoc(2021, 7)]
-pub fn main() {
-    let data = aoc_input!(2021, 7).unwrap();
+pub fn main(data: &str) {
     let positions = data
         .trim()
         .split(',')
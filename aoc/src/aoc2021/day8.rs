@@ -1,9 +1,9 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::{num::ParseIntError, str::Utf8Error, sync::LazyLock};
 
-static CHARS: &'static str = "abcdefg";
+static CHARS: &str = "abcdefg";
 static ENCODING: LazyLock<FxHashMap<usize, &'static str>> = LazyLock::new(|| {
     FxHashMap::from_iter([
         (0, "abcefg"),
@@ -78,8 +78,7 @@ fn parse(line: &str) -> (&str, &str) {
 }
 
 #[aoc(2021, 8)]
-pub fn main() {
-    let data = aoc_input!(2021, 8).unwrap();
+pub fn main(data: &str) {
     let entries = data.trim().lines().map(parse).collect::<Vec<_>>();
     let (mut count, mut outval) = (0, 0);
 
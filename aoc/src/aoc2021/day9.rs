@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::collections::VecDeque;
@@ -74,9 +74,8 @@ impl TryFrom<&str> for HeightMap {
 }
 
 #[aoc(2021, 9)]
-pub fn main() {
-    let data = aoc_input!(2021, 9).unwrap();
-    let height_map = HeightMap::try_from(data.as_str()).unwrap();
+pub fn main(data: &str) {
+    let height_map = HeightMap::try_from(data).unwrap();
 
     // Part I
     println!("{}", height_map.total_risk_level());
@@ -1,9 +1,8 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 
 #[aoc(2022, 1)]
-pub fn main() {
-    let data = aoc_input!(2022, 1).unwrap();
+pub fn main(data: &str) {
     let mut calories = data
         .split("\n\n")
         .map(|group| {
@@ -1,81 +1,72 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
+use aoc::ocr;
+use aoc::vm::{Cpu, Instruction, Jump, Machine, parse_program, tokens};
 use itertools::Itertools;
+use std::str::FromStr;
 
-enum Instruction {
+enum Op {
     NoOp,
     Add(i32),
 }
 
-impl From<&str> for Instruction {
-    fn from(value: &str) -> Self {
-        return if value.starts_with("noop") {
-            Self::NoOp
-        } else {
-            let x = value.split_once(' ').unwrap().1.parse::<i32>().unwrap();
-            Self::Add(x)
-        };
+impl FromStr for Op {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match tokens(s) {
+            ("noop", _) => Ok(Self::NoOp),
+            ("addx", args) if args.len() == 1 => args[0]
+                .parse()
+                .map(Self::Add)
+                .map_err(|_| format!("Invalid argument in: {s}")),
+            _ => Err(format!("Unknown instruction: {s}")),
+        }
     }
 }
 
-#[allow(non_snake_case)]
-struct CPU<'ops> {
-    X: i32,
-    cycle: i32,
-    instructions: &'ops [Instruction],
-    states: Vec<(i32, i32)>,
-}
+impl Instruction for Op {
+    type Word = i32;
 
-impl<'ops> CPU<'ops> {
-    fn new(instructions: &'ops [Instruction]) -> Self {
-        Self {
-            X: 1,
-            cycle: 0,
-            instructions,
-            states: Vec::with_capacity(300),
+    fn execute(&self, cpu: &mut Cpu<i32>) -> Jump {
+        if let Self::Add(x) = self {
+            cpu.registers[0] += x;
         }
+        Jump::Next
     }
 
-    #[inline]
-    fn step(&mut self) {
-        self.cycle += 1;
-        self.states.push((self.cycle, self.X));
+    fn cycles(&self) -> usize {
+        match self {
+            Self::NoOp => 1,
+            Self::Add(_) => 2,
+        }
     }
+}
 
-    fn addx(&mut self, x: i32) {
-        self.step();
-        self.step();
-        self.X += x;
-    }
+/// Value of the `X` register during every cycle, starting with cycle 1.
+fn register_during_cycles(program: Vec<Op>) -> Vec<i32> {
+    let mut cpu = Machine::new(program, 1);
+    cpu.set_register(0, 1);
+    cpu.set_tracing(true);
+    cpu.run();
 
-    fn eval(&mut self) {
-        for instruction in self.instructions {
-            match instruction {
-                Instruction::NoOp => self.step(),
-                Instruction::Add(x) => self.addx(*x),
-            }
-        }
-    }
+    // A register only changes once an instruction has taken all its cycles
+    cpu.trace()
+        .iter()
+        .flat_map(|s| std::iter::repeat_n(s.registers[0], cpu.program()[s.pc].cycles()))
+        .collect()
 }
 
 #[aoc(2022, 10)]
-pub fn main() {
-    let data = aoc_input!(2022, 10).unwrap();
-    let instructions = data
-        .trim_end()
-        .lines()
-        .map(Instruction::from)
-        .collect::<Vec<_>>();
-    let mut cpu = CPU::new(&instructions);
-    cpu.eval();
+pub fn main(data: &str) {
+    let states = register_during_cycles(parse_program(data).unwrap());
 
     // Part I
     let cycles = [20, 60, 100, 140, 180, 220];
     println!(
         "{}",
-        cpu.states
+        cycles
             .iter()
-            .filter(|(i, _)| cycles.contains(i))
-            .map(|(i, v)| i * v)
+            .map(|&i| i as i32 * states[i - 1])
             .sum::<i32>()
     );
 
@@ -83,7 +74,7 @@ pub fn main() {
     let mut idx_crt = 0;
     let mut display: Vec<char> = Vec::with_capacity(320);
 
-    for (_, value) in cpu.states {
+    for value in states {
         display.push(if (idx_crt - value).abs() <= 1 {
             '█'
         } else {
@@ -92,11 +83,23 @@ pub fn main() {
         idx_crt = (idx_crt + 1) % 40;
     }
 
-    println!(
-        "{}",
-        display
-            .chunks(40)
-            .map(|line| line.iter().collect::<String>())
-            .join("\n")
-    );
+    let display = display
+        .chunks(40)
+        .map(|line| line.iter().collect::<String>())
+        .join("\n");
+    match ocr::recognize_text(&display) {
+        Ok(code) => println!("{code}"),
+        Err(_) => println!("{display}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycles() {
+        let program = parse_program("noop\naddx 3\naddx -5").unwrap();
+        assert_eq!(register_during_cycles(program), [1, 1, 1, 4, 4]);
+    }
 }
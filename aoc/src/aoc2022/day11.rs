@@ -1,6 +1,6 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
+use aoc::math::lcm;
 use itertools::Itertools;
-use num::Integer;
 use std::collections::VecDeque;
 
 #[derive(Debug)]
@@ -110,11 +110,7 @@ impl<'m> Gang<'m> {
     }
 
     fn lcm(&self) -> usize {
-        self.0
-            .iter()
-            .map(|m| m.divisor)
-            .reduce(|acc, x| acc.lcm(&x))
-            .unwrap()
+        self.0.iter().map(|m| m.divisor).reduce(lcm).unwrap()
     }
 
     fn round(&mut self) {
@@ -143,9 +139,7 @@ impl<'m> Gang<'m> {
 }
 
 #[aoc(2022, 11)]
-pub fn main() {
-    let data = aoc_input!(2022, 11).unwrap();
-
+pub fn main(data: &str) {
     // Part I
     let mut monkeys = data.split("\n\n").map(Monkey::from).collect::<Vec<_>>();
     let mut gang = Gang::new(&mut monkeys);
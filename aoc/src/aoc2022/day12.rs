@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input, heap::MinHeap};
+use aoc::{aoc, heap::MinHeap};
 use rustc_hash::{FxHashMap, FxHashSet};
 
 type Graph = FxHashMap<(i16, i16), char>;
@@ -9,7 +9,7 @@ fn build_graph(data: &str) -> Graph {
         .enumerate()
         .flat_map(|(i, line)| {
             line.as_bytes()
-                .into_iter()
+                .iter()
                 .enumerate()
                 .map(move |(j, ch)| ((i as i16, j as i16), *ch as char))
         })
@@ -30,7 +30,7 @@ fn is_transition(start: char, end: char) -> bool {
     match (start, end) {
         ('E', 'y' | 'z') | ('a', 'S') => true,
         ('E', _) | (_, 'S') => false,
-        (src @ _, dest @ _) => (dest as i8) + 1 >= (src as i8),
+        (src, dest) => (dest as i8) + 1 >= (src as i8),
     }
 }
 
@@ -50,10 +50,10 @@ fn shortest_path_len(graph: &Graph, start: (i16, i16), target: char) -> Option<u
             }
             // visit neighbors
             for n in neighbors(&pos) {
-                if let Some(&next) = graph.get(&n) {
-                    if is_transition(ch, next) {
-                        queue.push(dist + 1, n);
-                    }
+                if let Some(&next) = graph.get(&n)
+                    && is_transition(ch, next)
+                {
+                    queue.push(dist + 1, n);
                 }
             }
         }
@@ -63,10 +63,9 @@ fn shortest_path_len(graph: &Graph, start: (i16, i16), target: char) -> Option<u
 }
 
 #[allow(non_snake_case)]
-#[aoc(2022, 12)]
-pub fn main() {
-    let data = aoc_input!(2022, 12).unwrap();
-    let graph = build_graph(&data);
+#[aoc(2022, 12, title = "Hill Climbing Algorithm", tags = ["dijkstra", "grid"])]
+pub fn main(data: &str) {
+    let graph = build_graph(data);
     let E = graph
         .iter()
         .find(|(_, v)| **v == 'E')
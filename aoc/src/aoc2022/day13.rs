@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use std::str;
 use std::str::FromStr;
 
@@ -9,7 +9,7 @@ use std::str::FromStr;
 
 type List = Vec<Node>;
 
-#[derive(Debug, Eq, Ord)]
+#[derive(Debug, Eq)]
 enum Node {
     Number(u8),
     List(List),
@@ -19,7 +19,7 @@ impl PartialEq for Node {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (&Node::Number(x), &Node::Number(y)) => x == y,
-            (&Node::List(ref x), &Node::List(ref y)) => x == y,
+            (Node::List(x), Node::List(y)) => x == y,
             _ => false,
         }
     }
@@ -27,13 +27,17 @@ impl PartialEq for Node {
 
 impl PartialOrd for Node {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         match (self, other) {
-            (&Node::Number(ref x), &Node::Number(ref y)) => x.partial_cmp(y),
-            (&Node::List(ref x), &Node::List(ref y)) => x.partial_cmp(y),
-            (&Node::Number(ref x), &Node::List(_)) => {
-                Node::List(vec![Node::Number(*x)]).partial_cmp(other)
-            }
-            (&Node::List(ref x), &Node::Number(ref y)) => x.partial_cmp(&vec![Node::Number(*y)]),
+            (Node::Number(x), Node::Number(y)) => x.cmp(y),
+            (Node::List(x), Node::List(y)) => x.cmp(y),
+            (Node::Number(x), &Node::List(_)) => Node::List(vec![Node::Number(*x)]).cmp(other),
+            (Node::List(x), Node::Number(y)) => x.cmp(&vec![Node::Number(*y)]),
         }
     }
 }
@@ -86,8 +90,7 @@ fn parse_number(input: &str) -> Result<(Node, &str), ()> {
 }
 
 #[aoc(2022, 13)]
-pub fn main() {
-    let data = aoc_input!(2022, 13).unwrap();
+pub fn main(data: &str) {
     let node_pairs: Vec<_> = data
         .trim()
         .split("\n\n")
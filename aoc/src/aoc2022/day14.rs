@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use glam::i32::IVec2 as Point;
 use itertools::Itertools;
 use rustc_hash::FxHashSet;
@@ -144,9 +144,7 @@ impl Cave {
 }
 
 #[aoc(2022, 14)]
-pub fn main() {
-    let data = aoc_input!(2022, 14).unwrap();
-
+pub fn main(data: &str) {
     let sand = Point::new(500, 0);
     let paths = data.trim().lines().map(|line| {
         Path::create(line.split(" -> ").map(|p| {
@@ -1,4 +1,5 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
+use aoc::interval::{Interval, IntervalSet};
 use glam::I64Vec2 as Point;
 use regex_lite::Regex;
 use rustc_hash::FxHashSet;
@@ -7,23 +8,41 @@ fn dist(s: &Point, b: &Point) -> i64 {
     (s.x - b.x).abs() + (s.y - b.y).abs()
 }
 
-fn find(s: &Point, b: &Point, y: i64) -> FxHashSet<i64> {
-    let d = dist(s, b);
-    let d_prime = (s.y - y).abs();
-    let mut res = FxHashSet::default();
+/// All `x` positions on row `y` within range of any sensor.
+fn coverage(points: &[(Point, Point)], y: i64) -> IntervalSet<i64> {
+    points
+        .iter()
+        .map(|(s, b)| {
+            let reach = dist(s, b) - (s.y - y).abs();
+            Interval::new(s.x - reach, s.x + reach + 1)
+        })
+        .collect()
+}
 
-    if d > d_prime {
-        for i in (s.x)..=(s.x + d - d_prime) {
-            res.insert(i);
-        }
-    }
-    if d >= d_prime {
-        for i in (s.x - d + d_prime)..=(s.x) {
-            res.insert(i);
-        }
-    }
+/// Positions on row `y` where the distress beacon cannot be.
+fn count_excluded(points: &[(Point, Point)], y: i64) -> i64 {
+    let covered = coverage(points, y);
+    let beacons = points
+        .iter()
+        .filter(|(_, b)| b.y == y && covered.contains(b.x))
+        .map(|(_, b)| b.x)
+        .collect::<FxHashSet<_>>();
 
-    res
+    covered.len() - beacons.len() as i64
+}
+
+fn parse(data: &str) -> Vec<(Point, Point)> {
+    let re = Regex::new(r"-?\d+").unwrap();
+
+    data.trim()
+        .lines()
+        .map(|line| {
+            re.find_iter(line)
+                .filter_map(|num| num.as_str().parse::<i64>().ok())
+                .collect::<Vec<_>>()
+        })
+        .map(|v| (Point::new(v[0], v[1]), Point::new(v[2], v[3])))
+        .collect()
 }
 
 fn iter_circle(x0: &Point, d: i64) -> FxHashSet<Point> {
@@ -56,35 +75,11 @@ fn is_beacon(x: &Point, data: &[(Point, Point, i64)]) -> bool {
 }
 
 #[aoc(2022, 15)]
-pub fn main() {
-    let data = aoc_input!(2022, 15).unwrap();
-    let re = Regex::new(r"-?\d+").unwrap();
-
-    let points: Vec<_> = data
-        .trim()
-        .lines()
-        .map(|line| {
-            re.find_iter(line)
-                .filter_map(|num| num.as_str().parse::<i64>().ok())
-                .collect::<Vec<_>>()
-        })
-        .map(|v| (Point::new(v[0], v[1]), Point::new(v[2], v[3])))
-        .collect();
+pub fn main(data: &str) {
+    let points = parse(data);
 
     // Part I
-    let mut res = FxHashSet::default();
-    let target_y = 2_000_000;
-    let beacons_on_target = points
-        .iter()
-        .filter(|(_, b)| b.y == target_y)
-        .map(|(_, b)| b.x)
-        .collect::<FxHashSet<_>>();
-
-    for (s, b) in &points {
-        res.extend(find(s, b, target_y));
-    }
-
-    println!("{}", res.difference(&beacons_on_target).count());
+    println!("{}", count_excluded(&points, 2_000_000));
 
     // Part II
     let data_w_dist = points
@@ -101,3 +96,28 @@ pub fn main() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(count_excluded(&parse(EXAMPLE), 10), 26);
+    }
+}
@@ -1,48 +1,11 @@
-use aoc::{aoc, aoc_input};
-use itertools::{iproduct, Itertools};
+use aoc::aoc;
+use aoc::graph::{all_pairs_distances, from_adjacency};
+use itertools::iproduct;
+use petgraph::graphmap::UnGraphMap;
+use petgraph::visit::NodeIndexable;
 use regex_lite::Regex;
-use rustc_hash::{FxHashMap, FxHashSet};
-use std::cmp::{max, min};
-
-#[derive(Clone, PartialEq, Eq, Hash)]
-struct State<'a> {
-    node: &'a str,
-    flow: i32,
-    opened: usize,
-    t: i32,
-}
-
-#[derive(Default)]
-struct Heap<'a> {
-    i: usize,
-    heap: Vec<State<'a>>,
-}
-
-impl<'a> Heap<'a> {
-    fn next(&mut self) -> Option<&State<'a>> {
-        self.heap.get(self.i).map(|x| {
-            self.i += 1;
-            x
-        })
-    }
-
-    fn push(&mut self, item: State<'a>) {
-        self.heap.push(item);
-    }
-
-    fn shrink(&mut self, max_size: usize) -> Self {
-        let n = min(max_size, self.heap.len());
-        let sorted = self
-            .heap
-            .iter()
-            .sorted_by_key(|s| -s.flow)
-            .take(n)
-            .cloned()
-            .collect::<Vec<_>>();
-
-        Self { i: 0, heap: sorted }
-    }
-}
+use rustc_hash::FxHashMap;
+use std::cmp::max;
 
 fn parse(data: &str) -> (FxHashMap<&str, i32>, FxHashMap<&str, Vec<&str>>) {
     let re = Regex::new(r"(.*?)([A-Z]{2})(.*?)rate=(\d+)(.*?)valve(s)* (.*?)\n").unwrap();
@@ -66,87 +29,58 @@ fn parse(data: &str) -> (FxHashMap<&str, i32>, FxHashMap<&str, Vec<&str>>) {
     (flow_rates, graph)
 }
 
-fn find_max_flow<'a>(
-    flow_rates: &FxHashMap<&'a str, i32>,
-    graph: &FxHashMap<&'a str, Vec<&'a str>>,
-    start: &'a str,
-    time: usize,
-) -> FxHashMap<usize, i32> {
-    let valve_index = flow_rates
-        .keys()
-        .enumerate()
-        .map(|(i, valve)| (*valve, 1 << i))
-        .collect::<FxHashMap<_, _>>();
-    let init = State {
-        node: start,
-        flow: 0,
-        opened: 0,
-        t: time as i32,
-    };
-    let mut states = Heap {
-        i: 0,
-        heap: vec![init],
-    };
-    let mut visited = FxHashSet::default();
-    let mut max_flow = FxHashMap::default();
-
-    for _ in 0..time {
-        let mut queue = Heap::default();
-
-        while let Some(state) = states.next() {
-            if !visited.insert(state.clone()) {
-                continue;
-            }
-
-            if state.t < 0 {
-                continue;
-            }
-
-            // move
-            for adj in &graph[state.node] {
-                queue.push(State {
-                    node: *adj,
-                    flow: state.flow,
-                    opened: state.opened.clone(),
-                    t: state.t - 1,
-                });
-            }
-
-            // open valve
-            let node_flow_rate = flow_rates[state.node];
-
-            if node_flow_rate > 0 && (state.opened & valve_index[state.node]) == 0 {
-                let opened = state.opened | valve_index[state.node];
-                let new_flow = state.flow + node_flow_rate * (state.t - 1);
-                max_flow
-                    .entry(opened)
-                    .and_modify(|x| *x = max(*x, new_flow))
-                    .or_insert(new_flow);
-                queue.push(State {
-                    node: state.node,
-                    flow: new_flow,
-                    opened,
-                    t: state.t - 1,
-                });
-            }
-
-            // do nothing
-            queue.push(State {
-                t: state.t - 1,
-                ..*state
-            });
+/// Record the best flow for every set of opened valves reachable from `valve` with
+/// `time` minutes left. `valves` are the valves worth opening, as indices into `dist`,
+/// with their flow rates.
+fn open_valves(
+    valves: &[(usize, i32)],
+    dist: &[Vec<Option<i32>>],
+    (valve, time): (usize, i32),
+    (opened, flow): (usize, i32),
+    max_flow: &mut FxHashMap<usize, i32>,
+) {
+    max_flow
+        .entry(opened)
+        .and_modify(|x| *x = max(*x, flow))
+        .or_insert(flow);
+
+    for (k, &(next, rate)) in valves.iter().enumerate() {
+        // Walk there and spend a minute opening it
+        let Some(t) = dist[valve][next].map(|d| time - d - 1) else {
+            continue;
+        };
+        if opened & (1 << k) == 0 && t > 0 {
+            let state = (opened | (1 << k), flow + rate * t);
+            open_valves(valves, dist, (next, t), state, max_flow);
         }
-
-        states = queue.shrink(6000);
     }
+}
+
+/// Best total flow for every set of opened valves, opening valves for `time` minutes
+/// starting from `start`.
+fn find_max_flow(
+    flow_rates: &FxHashMap<&str, i32>,
+    graph: &FxHashMap<&str, Vec<&str>>,
+    start: &str,
+    time: usize,
+) -> FxHashMap<usize, i32> {
+    let tunnels: UnGraphMap<_, _> = from_adjacency(graph);
+    let dist = all_pairs_distances(&tunnels, |_| 1);
+    let valves = flow_rates
+        .iter()
+        .filter(|&(_, &rate)| rate > 0)
+        .map(|(&valve, &rate)| (tunnels.to_index(valve), rate))
+        .collect::<Vec<_>>();
 
+    let mut max_flow = FxHashMap::default();
+    let start = (tunnels.to_index(start), time as i32);
+    open_valves(&valves, &dist, start, (0, 0), &mut max_flow);
     max_flow
 }
 
 #[aoc(2022, 16)]
-pub fn main() {
-    let data = aoc_input!(2022, 16).unwrap();
-    let (flow_rates, graph) = parse(&data);
+pub fn main(data: &str) {
+    let (flow_rates, graph) = parse(data);
 
     // Part I
     let max_flow = find_max_flow(&flow_rates, &graph, "AA", 30)
@@ -165,3 +99,34 @@ pub fn main() {
         .unwrap();
     println!("{max_flow}");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II
+";
+
+    #[test]
+    fn test_max_flow() {
+        let (flow_rates, graph) = parse(EXAMPLE);
+        let flows = find_max_flow(&flow_rates, &graph, "AA", 30);
+        assert_eq!(flows.values().max(), Some(&1651));
+
+        let flows = find_max_flow(&flow_rates, &graph, "AA", 26);
+        let max_flow = iproduct!(&flows, &flows)
+            .filter(|(f, g)| f.0 & g.0 == 0)
+            .map(|(f, g)| f.1 + g.1)
+            .max();
+        assert_eq!(max_flow, Some(1707));
+    }
+}
@@ -1,11 +1,10 @@
-use aoc::{aoc, aoc_input};
-use num::Integer;
+use aoc::aoc;
+use aoc::cycle::find_cycle_by_key;
 use rustc_hash::FxHashMap;
-use std::iter::Cycle;
-use std::slice::Iter;
-use std::str::Chars;
 
 static BOUNDARIES: u16 = 0b100000001;
+// Number of rows from the top of the tower used to recognise a repeated state
+static FINGERPRINT_ROWS: usize = 32;
 static PIECES: [&[u16]; 5] = [
     // flipped upside-down
     &[0b000111100], // ####
@@ -31,33 +30,48 @@ static PIECES: [&[u16]; 5] = [
     ],
 ];
 
-fn push(block: &[u16], jet: char) -> Vec<u16> {
+fn push(block: &[u16], jet: u8) -> Vec<u16> {
     match jet {
-        '<' => block.iter().map(|x| *x << 1).collect(),
+        b'<' => block.iter().map(|x| *x << 1).collect(),
         _ => block.iter().map(|x| *x >> 1).collect(),
     }
 }
 
 struct Tetris<'a> {
     h: usize,
-    jiter: Cycle<Chars<'a>>,
-    piter: Cycle<Iter<'static, &'static [u16]>>,
+    jets: &'a [u8],
+    jet: usize,
+    piece: usize,
     board: FxHashMap<u16, u16>,
 }
 
 impl<'a> Tetris<'a> {
     fn new(jet_pattern: &'a str) -> Self {
-        let jiter = jet_pattern.chars().cycle();
-        let piter = PIECES.iter().cycle();
-
         Self {
             h: 3,
-            jiter,
-            piter,
+            jets: jet_pattern.as_bytes(),
+            jet: 0,
+            piece: 0,
             board: FxHashMap::default(),
         }
     }
 
+    fn height(&self) -> usize {
+        self.h - 3
+    }
+
+    /// Next piece, next jet and the top of the tower, which together determine
+    /// how the tower grows from here.
+    fn fingerprint(&self) -> (usize, usize, Vec<u16>) {
+        let top = (0..FINGERPRINT_ROWS)
+            .map(|i| match self.height().checked_sub(i + 1) {
+                Some(row) => self.board[&(row as u16)],
+                None => BOUNDARIES,
+            })
+            .collect();
+        (self.piece, self.jet, top)
+    }
+
     fn is_collision(&self, h: usize, piece: &[u16]) -> bool {
         let board_slice =
             (h..(h + piece.len())).map(|i| self.board.get(&(i as u16)).unwrap_or(&BOUNDARIES));
@@ -71,10 +85,12 @@ impl<'a> Tetris<'a> {
     }
 
     fn process(&mut self) {
-        let mut piece: Vec<u16> = self.piter.next().unwrap().to_vec();
+        let mut piece: Vec<u16> = PIECES[self.piece].to_vec();
+        self.piece = (self.piece + 1) % PIECES.len();
 
         loop {
-            let jet = self.jiter.next().unwrap();
+            let jet = self.jets[self.jet];
+            self.jet = (self.jet + 1) % self.jets.len();
             let shifted_piece = push(&piece, jet);
 
             if !self.is_collision(self.h, &shifted_piece) {
@@ -95,87 +111,48 @@ impl<'a> Tetris<'a> {
     }
 }
 
-fn find_min(pattern: &[u16]) -> (usize, i64) {
-    let n = pattern.len();
-    let mut min_cost: i64 = 1_000_000_000_000;
-    let mut i_min = 0;
-
-    for i in 1_000..20_000 {
-        let cost = pattern[i..]
-            .iter()
-            .zip(pattern[..(n - i)].iter())
-            .map(|(x, y)| x.abs_diff(*y) as i64)
-            .sum::<i64>();
-
-        if cost < min_cost {
-            min_cost = cost;
-            i_min = i;
-        }
-    }
-
-    (i_min, min_cost)
-}
-
-fn subfinder(mylist: &[u16], pattern: &[u16]) -> Vec<usize> {
-    let n = pattern.len();
-    let mut matches = vec![];
-
-    for (i, x) in mylist.iter().enumerate() {
-        if *x == pattern[0] && &mylist[i..i + n] == pattern {
-            matches.push(i)
-        }
-    }
+fn tower_height(jet_pattern: &str, rocks: usize) -> usize {
+    let mut tetris = Tetris::new(jet_pattern);
+    let init = (tetris.height(), tetris.fingerprint());
+    let drop_rock = |_: &(usize, _)| {
+        tetris.process();
+        (tetris.height(), tetris.fingerprint())
+    };
 
-    matches
+    let (cycle, history) = find_cycle_by_key(init, drop_rock, |(_, key)| key.clone());
+    let heights = history.into_iter().map(|(h, _)| h).collect::<Vec<_>>();
+    cycle.extrapolate(rocks, &heights)
 }
 
 #[aoc(2022, 17)]
-pub fn main() {
-    let data = aoc_input!(2022, 17).unwrap();
+pub fn main(data: &str) {
     let jet_pattern = data.trim();
 
     // Part I
     let mut tetris = Tetris::new(jet_pattern);
     tetris.simulate(2022);
-    println!("{}", tetris.h - 3);
+    println!("{}", tetris.height());
 
     // Part II
-    // First, simulate 50k rocks to record a pattern in the board
-    let mut tetris = Tetris::new(jet_pattern);
-    tetris.simulate(50_000);
-    // h - 30 is arbitrary
-    let pattern = (0..(tetris.h - 30))
-        .map(|i| tetris.board[&(i as u16)])
-        .collect::<Vec<_>>();
-
-    // Then, start shifting a pattern and calculate the "cost".
-    // When cost is minimized, the cycle in the pattern and shifted
-    // one overlaps, which results in the minimal cost.
-    let (i_min, _) = find_min(&pattern);
-    let sub_pattern = &pattern[i_min..i_min + 40];
-
-    // Find indices of the sub_pattern in the pattern
-    let idx = subfinder(&pattern, &sub_pattern);
-
-    // Run a simulation again to get a number of rocks that have to fall
-    // in order to fill in the board up to a height of idx[0]
-    let mut tetris = Tetris::new(jet_pattern);
-    let mut i_max = 0;
-
-    for i in 0..10_000 {
-        tetris.process();
+    println!("{}", tower_height(jet_pattern, 1_000_000_000_000));
+}
 
-        if (tetris.h - 3) > idx[0] {
-            i_max = i;
-            break;
-        }
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let (a, b) = 1000000000000_i64.div_rem(&i_max);
+    static EXAMPLE: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
 
-    // Again, run a simulation b times to record the height
-    let mut tetris = Tetris::new(jet_pattern);
-    tetris.simulate(b as usize);
+    #[test]
+    fn test_part1() {
+        let mut tetris = Tetris::new(EXAMPLE);
+        tetris.simulate(2022);
+        assert_eq!(tetris.height(), 3068);
+        assert_eq!(tower_height(EXAMPLE, 2022), 3068);
+    }
 
-    println!("{}", a as usize * idx[0] + (tetris.h - 3));
+    #[test]
+    fn test_part2() {
+        assert_eq!(tower_height(EXAMPLE, 1_000_000_000_000), 1514285714288);
+    }
 }
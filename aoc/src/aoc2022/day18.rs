@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use rustc_hash::FxHashSet;
 use std::collections::VecDeque;
 
@@ -45,7 +45,7 @@ fn lava_fill(data: &[[i32; 3]]) -> usize {
 
     while let Some(node) = queue.pop_front() {
         for w in neighbors3d(&node) {
-            if w.iter().any(|&x| -1 > x || x > 20) {
+            if w.iter().any(|&x| !(-1..=20).contains(&x)) {
                 continue;
             }
             if data.contains(&w) {
@@ -62,9 +62,8 @@ fn lava_fill(data: &[[i32; 3]]) -> usize {
 }
 
 #[aoc(2022, 18)]
-pub fn main() {
-    let data = aoc_input!(2022, 18).unwrap();
-    let data = parse(&data);
+pub fn main(data: &str) {
+    let data = parse(data);
 
     // Part I
     println!("{}", count_faces(&data));
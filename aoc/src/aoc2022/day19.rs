@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use glam::IVec4;
 use itertools::Itertools;
 use regex_lite::Regex;
@@ -143,15 +143,14 @@ where
 }
 
 #[aoc(2022, 19)]
-pub fn main() {
-    let data = aoc_input!(2022, 19).unwrap();
-    let blueprints = parse(&data);
+pub fn main(data: &str) {
+    let blueprints = parse(data);
 
     // Part I
     let state0 = [0, 0, 0, 0, 1, 0, 0, 0, 0];
     let result: i32 = blueprints
         .iter()
-        .map(|blueprint| bfs(&state0, &blueprint, 24) * blueprint[0])
+        .map(|blueprint| bfs(&state0, blueprint, 24) * blueprint[0])
         .sum();
     println!("{result}");
 
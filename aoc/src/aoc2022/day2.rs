@@ -1,8 +1,7 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 
 #[aoc(2022, 2)]
-pub fn main() {
-    let data = aoc_input!(2022, 2).unwrap();
+pub fn main(data: &str) {
     let (mut score1, mut score2) = (0, 0);
 
     for line in data.trim().lines() {
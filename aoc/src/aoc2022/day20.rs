@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 
 fn modif(line: &[i64], t: usize) -> Vec<i64> {
     let mut data: Vec<_> = line.iter().enumerate().collect();
@@ -33,8 +33,7 @@ fn score(data: &[i64]) -> i64 {
 }
 
 #[aoc(2022, 20)]
-pub fn main() {
-    let data = aoc_input!(2022, 20).unwrap();
+pub fn main(data: &str) {
     let line: Vec<i64> = data.trim().lines().flat_map(|x| x.parse()).collect();
 
     // Part I
@@ -1,105 +1,91 @@
-use aoc::{aoc, aoc_input};
-use itertools::Itertools;
+use aoc::aoc;
+use aoc::expr::{Expr, Grammar};
 use rustc_hash::FxHashMap;
 
-#[derive(Debug, Clone, Copy)]
-enum Job<'a> {
-    Value(Option<i64>),
-    MathOp(&'a str, &'a str, &'a str),
+type Monkeys<'a> = FxHashMap<&'a str, Expr<i64>>;
+
+fn arithmetic() -> Grammar<i64> {
+    Grammar::new()
+        .infix("+", 1, |a, b| a + b)
+        .infix("-", 1, |a, b| a - b)
+        .infix("*", 2, |a, b| a * b)
+        .infix("/", 2, |a, b| a / b)
 }
 
-fn parse(data: &str) -> FxHashMap<&str, Job<'_>> {
+fn parse<'a>(data: &'a str, grammar: &Grammar<i64>) -> Monkeys<'a> {
     data.trim()
         .lines()
         .map(|line| {
-            let (monkey, op) = line.trim().split_once(": ").unwrap();
-            let rhs = if let Result::Ok(val) = op.parse::<i64>() {
-                Job::Value(Some(val))
-            } else {
-                let val = op
-                    .split_whitespace()
-                    .collect_tuple::<(&str, &str, &str)>()
-                    .unwrap();
-                Job::MathOp(val.0, val.1, val.2)
-            };
-            (monkey, rhs)
+            let (monkey, job) = line.trim().split_once(": ").unwrap();
+            (monkey, grammar.parse(job).unwrap())
         })
         .collect()
 }
 
-fn eval_node(monkeys: &FxHashMap<&str, Job>, node: &str) -> Option<i64> {
-    match monkeys.get(node) {
-        Some(Job::Value(val)) => *val,
-        Some(Job::MathOp(m1, op, m2)) => {
-            let lhs = eval_node(monkeys, m1);
-            let rhs = eval_node(monkeys, m2);
-
-            if lhs.is_none() || rhs.is_none() {
-                return None;
-            }
-
-            let (lhs, rhs) = (lhs.unwrap(), rhs.unwrap());
-
-            match *op {
-                "+" => Some(lhs + rhs),
-                "-" => Some(lhs - rhs),
-                "*" => Some(lhs * rhs),
-                "/" => Some(lhs / rhs),
-                _ => unreachable!(),
-            }
-        }
-        _ => unreachable!(),
-    }
+/// The job of `monkey` with the jobs of the monkeys it listens to inlined, except for
+/// those listed in `unknowns`.
+fn expand(monkeys: &Monkeys, monkey: &str, unknowns: &[&str]) -> Expr<i64> {
+    monkeys[monkey]
+        .substitute(&mut |name| (!unknowns.contains(&name)).then(|| monkeys[name].clone()))
 }
 
-fn propagate(monkeys: &FxHashMap<&str, Job>, node: &str, value: i64) -> i64 {
-    if node == "humn" {
-        return value;
-    }
-
-    match monkeys.get(node) {
-        Some(Job::MathOp(m1, op, m2)) => match (eval_node(&monkeys, m1), eval_node(&monkeys, m2)) {
-            (Some(val), None) => match *op {
-                "+" => propagate(monkeys, m2, value - val),
-                "-" => propagate(monkeys, m2, val - value),
-                "/" => propagate(monkeys, m2, val / value),
-                "*" => propagate(monkeys, m2, value / val),
-                _ => unreachable!(),
-            },
-            (None, Some(val)) => match *op {
-                "+" => propagate(monkeys, m1, value - val),
-                "-" => propagate(monkeys, m1, value + val),
-                "/" => propagate(monkeys, m1, val * value),
-                "*" => propagate(monkeys, m1, value / val),
-                _ => unreachable!(),
-            },
-            _ => unreachable!(),
-        },
-        _ => unreachable!(),
-    }
+/// Solution - Part I
+fn root_number(monkeys: &Monkeys, grammar: &Grammar<i64>) -> Option<i64> {
+    expand(monkeys, "root", &[]).eval(grammar, &mut |_| None)
 }
 
-fn find(monkeys: &FxHashMap<&str, Job>) -> i64 {
-    let mut monkeys = monkeys.clone();
-    let (m1, m2) = match monkeys.get(&"root") {
-        Some(Job::MathOp(m1, _, m2)) => (*m1, *m2),
-        _ => unreachable!(),
+/// Solution - Part II
+fn find_humn(monkeys: &Monkeys, grammar: &Grammar<i64>) -> Option<i64> {
+    let Expr::Binary(_, lhs, rhs) = expand(monkeys, "root", &["humn"]) else {
+        return None;
     };
 
-    monkeys.insert("humn", Job::Value(None));
-    let (a, b) = (eval_node(&monkeys, m1), eval_node(&monkeys, m2));
-
-    propagate(&monkeys, "root", 2 * (a.unwrap_or(0) + b.unwrap_or(0)))
+    let lhs = lhs.partial_eval(grammar, &mut |_| None);
+    let rhs = rhs.partial_eval(grammar, &mut |_| None);
+    match (lhs.as_num(), rhs.as_num()) {
+        (None, Some(target)) => lhs.solve("humn", target),
+        (Some(target), None) => rhs.solve("humn", target),
+        _ => None,
+    }
 }
 
 #[aoc(2022, 21)]
-pub fn main() {
-    let data = aoc_input!(2022, 21).unwrap();
-    let monkeys = parse(&data);
+pub fn main(data: &str) {
+    let grammar = arithmetic();
+    let monkeys = parse(data, &grammar);
 
     // Part I
-    println!("{}", eval_node(&monkeys, "root").unwrap());
+    println!("{}", root_number(&monkeys, &grammar).unwrap());
 
     // Part II
-    println!("{}", find(&monkeys));
+    println!("{}", find_humn(&monkeys, &grammar).unwrap());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = "root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32";
+
+    #[test]
+    fn test_example() {
+        let grammar = arithmetic();
+        let monkeys = parse(EXAMPLE, &grammar);
+        assert_eq!(root_number(&monkeys, &grammar), Some(152));
+        assert_eq!(find_humn(&monkeys, &grammar), Some(301));
+    }
 }
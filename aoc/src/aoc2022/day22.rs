@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use glam::IVec2;
 use regex_lite::Regex;
 use rustc_hash::FxHashMap;
@@ -87,9 +87,8 @@ macro_rules! wrap {
 }
 
 #[aoc(2022, 22)]
-pub fn main() {
-    let data = aoc_input!(2022, 22).unwrap();
-    let (cube, moves) = parse(&data);
+pub fn main(data: &str) {
+    let (cube, moves) = parse(data);
 
     // Part I
     let mut wrappings = FxHashMap::default();
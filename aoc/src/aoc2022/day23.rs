@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use glam::IVec2 as Point;
 use itertools::Itertools;
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -136,7 +136,7 @@ fn eval_round(elves: &FxHashSet<Point>, n: usize) -> ElvesResult {
     let values = proposed_moves.values().copied().collect::<Vec<_>>();
     proposed_moves.retain(|_, new_elf| values.iter().filter(|v| **v == *new_elf).count() == 1);
 
-    if proposed_moves.len() == 0 {
+    if proposed_moves.is_empty() {
         ElvesResult::NoMoves
     } else {
         let mut output = elves.clone();
@@ -154,7 +154,7 @@ fn empty_ground(elves: &FxHashSet<Point>) -> usize {
             let w = x_max - x_min + 1;
             let h = y_max - y_min + 1;
 
-            return (w as usize) * (h as usize) - elves.len();
+            (w as usize) * (h as usize) - elves.len()
         } else {
             unreachable!();
         }
@@ -164,11 +164,9 @@ fn empty_ground(elves: &FxHashSet<Point>) -> usize {
 }
 
 #[aoc(2022, 23)]
-pub fn main() {
-    let data = aoc_input!(2022, 23).unwrap();
-
+pub fn main(data: &str) {
     // Part I
-    let mut scan = parse(&data);
+    let mut scan = parse(data);
 
     for i in 0..10 {
         if let ElvesResult::Moves(new_scan) = eval_round(&scan, i) {
@@ -179,7 +177,7 @@ pub fn main() {
     println!("{}", empty_ground(&scan));
 
     // Part II
-    let mut scan = parse(&data);
+    let mut scan = parse(data);
     let mut i = 1;
 
     loop {
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use glam::IVec2;
 use rustc_hash::FxHashSet;
 
@@ -32,7 +32,7 @@ impl<T> Where<bool> for Array<T> {
     }
 }
 
-fn roll<T>(vec: &Vec<T>, shift: isize) -> Vec<T>
+fn roll<T>(vec: &[T], shift: isize) -> Vec<T>
 where
     T: Clone,
 {
@@ -81,7 +81,7 @@ struct Valley {
 impl Valley {
     fn new(basin: &Array<char>) -> Self {
         let h = basin.len();
-        let w: usize = basin.get(0).and_then(|x| Some(x.len())).unwrap_or_default();
+        let w: usize = basin.first().map(|x| x.len()).unwrap_or_default();
         let blizzards = [
             basin.r#where(|ch| *ch == '>', true, false),
             basin.r#where(|ch| *ch == '<', true, false),
@@ -146,14 +146,14 @@ fn bfs(valley: &mut Valley, start: IVec2, end: IVec2) -> Option<usize> {
                     return Some(valley.t);
                 }
 
-                if n != start || n != end {
-                    if next_blizz.iter().any(|arr| {
+                if (n != start || n != end)
+                    && next_blizz.iter().any(|arr| {
                         arr.get(n.y as usize)
-                            .and_then(|row| Some(*row.get(n.x as usize).unwrap_or(&false)))
+                            .map(|row| *row.get(n.x as usize).unwrap_or(&false))
                             .unwrap_or(false)
-                    }) {
-                        continue;
-                    }
+                    })
+                {
+                    continue;
                 }
 
                 _queue.insert(n);
@@ -177,9 +177,8 @@ fn parse(data: &str) -> Array<char> {
 }
 
 #[aoc(2022, 24)]
-pub fn main() {
-    let data = aoc_input!(2022, 24).unwrap();
-    let basin = parse(&data);
+pub fn main(data: &str) {
+    let basin = parse(data);
     let mut valley = Valley::new(&basin);
     let start = valley.start;
     let end = valley.end;
@@ -241,7 +240,7 @@ mod tests {
 #>v.><>#
 #<^v^^>#
 ######.#";
-        let basin = parse(&data);
+        let basin = parse(data);
         let mut valley = Valley::new(&basin);
         let start = valley.start;
         let end = valley.end;
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use num::Integer;
 use rustc_hash::FxHashMap;
@@ -37,9 +37,7 @@ fn num2snafu(n: i64) -> String {
 }
 
 #[aoc(2022, 25)]
-pub fn main() {
-    let data = aoc_input!(2022, 25).unwrap();
-
+pub fn main(data: &str) {
     // Part I
     let n = data.lines().map(snafu2num).sum::<i64>();
     println!("{}", num2snafu(n));
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use std::borrow::Borrow;
 
@@ -13,9 +13,7 @@ fn priority<C: Borrow<char>>(ch: C) -> u16 {
 }
 
 #[aoc(2022, 3)]
-pub fn main() {
-    let data = aoc_input!(2022, 3).unwrap();
-
+pub fn main(data: &str) {
     // Part I
     let sum_priorities: u16 = data
         .trim()
@@ -41,7 +39,7 @@ pub fn main() {
                 first.retain(|ch| line.contains(*ch));
             }
 
-            if let Some(ch) = first.get(0) {
+            if let Some(ch) = first.first() {
                 priority(ch)
             } else {
                 0
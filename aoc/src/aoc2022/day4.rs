@@ -1,21 +1,22 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 
 type Interval = aoc::interval::Interval<u8>;
 
+/// Parse an inclusive section range such as `2-4`.
+#[inline(always)]
+fn parse_sections(s: &str) -> Interval {
+    let (start, end) = s.split_once('-').unwrap();
+    Interval::new(start.parse().unwrap(), end.parse::<u8>().unwrap() + 1)
+}
+
 #[inline(always)]
 fn parse_line(line: &str) -> (Interval, Interval) {
     let (sec1, sec2) = line.split_once(',').unwrap();
-    let int1 = sec1.split_once('-').unwrap();
-    let int2 = sec2.split_once('-').unwrap();
-    (
-        Interval::new(int1.0.parse::<u8>().unwrap(), int1.1.parse::<u8>().unwrap()),
-        Interval::new(int2.0.parse::<u8>().unwrap(), int2.1.parse::<u8>().unwrap()),
-    )
+    (parse_sections(sec1), parse_sections(sec2))
 }
 
 #[aoc(2022, 4)]
-pub fn main() {
-    let data = aoc_input!(2022, 4).unwrap();
+pub fn main(data: &str) {
     let (mut n1, mut n2) = (0, 0);
 
     for line in data.trim().lines() {
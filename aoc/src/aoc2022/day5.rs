@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 
 type Stack = Vec<char>;
@@ -49,9 +49,8 @@ fn rearrange_multiple(crates: &mut [Stack], instruction: &Step) {
 }
 
 #[aoc(2022, 5)]
-pub fn main() {
-    let data = aoc_input!(2022, 5).unwrap();
-    let (mut crates, instructions) = parse(&data);
+pub fn main(data: &str) {
+    let (mut crates, instructions) = parse(data);
 
     // Part I
     let mut crates1 = crates.clone();
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 
 #[inline]
 fn is_unique(vec: &[u8]) -> bool {
@@ -18,8 +18,7 @@ fn find<const N: usize>(txt: &[u8]) -> Option<usize> {
 }
 
 #[aoc(2022, 6)]
-pub fn main() {
-    let data = aoc_input!(2022, 6).unwrap();
+pub fn main(data: &str) {
     let bytes = data.as_bytes();
 
     // Part I
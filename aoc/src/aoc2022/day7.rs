@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 
 #[derive(Default)]
 struct Dir<'a> {
@@ -73,9 +73,8 @@ fn parse(data: &str) -> Disk<'_> {
 }
 
 #[aoc(2022, 7)]
-pub fn main() {
-    let data = aoc_input!(2022, 7).unwrap();
-    let disk = parse(&data);
+pub fn main(data: &str) {
+    let disk = parse(data);
 
     // Part I
     let mut cache = [None; 256];
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 
 struct Grid<'a> {
@@ -84,9 +84,8 @@ fn scenic_score(grid: &Grid, x: usize, y: usize) -> usize {
 }
 
 #[aoc(2022, 8)]
-pub fn main() {
-    let data = aoc_input!(2022, 8).unwrap();
-    let grid = Grid::from(data.as_str());
+pub fn main(data: &str) {
+    let grid = Grid::from(data);
     let mut seen = vec![false; grid.size()];
     let (width, height) = (grid.width as isize, grid.height as isize);
 
@@ -1,79 +1,36 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
+use aoc::{direction::Direction, point::Point2};
 use atoi::atoi;
-use glam::IVec2;
 use rustc_hash::FxHashSet;
 
-#[inline]
-fn sign(x: i32) -> i32 {
-    match x.cmp(&0) {
-        std::cmp::Ordering::Less => -1,
-        std::cmp::Ordering::Equal => 0,
-        std::cmp::Ordering::Greater => 1,
-    }
-}
-
-trait Sign {
-    fn sign(&self) -> Self;
-}
-
-impl Sign for IVec2 {
-    fn sign(&self) -> Self {
-        IVec2::new(sign(self.x), sign(self.y))
-    }
-}
-
-enum Direction {
-    Down,
-    Up,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn to_ivec2(&self) -> IVec2 {
-        match self {
-            Self::Down => IVec2::new(0, -1),
-            Self::Up => IVec2::new(0, 1),
-            Self::Left => IVec2::new(-1, 0),
-            Self::Right => IVec2::new(1, 0),
-        }
-    }
-}
-
 struct Motion(Direction, i32);
 
 impl From<&str> for Motion {
     fn from(value: &str) -> Self {
         let (dir, val) = value.split_once(' ').unwrap();
         let val = atoi::<i32>(val.as_bytes()).unwrap();
-        let dir = match dir {
-            "D" => Direction::Down,
-            "U" => Direction::Up,
-            "L" => Direction::Left,
-            "R" => Direction::Right,
-            _ => unimplemented!(),
-        };
+        let dir = dir.parse().unwrap();
         Self(dir, val)
     }
 }
 
 #[allow(non_snake_case)]
 struct Rope {
-    t_visited: FxHashSet<IVec2>,
-    H: IVec2,
-    T: IVec2,
+    t_visited: FxHashSet<Point2>,
+    H: Point2,
+    T: Point2,
 }
 
 impl Rope {
     fn new() -> Self {
         let mut t_visited = FxHashSet::default();
         t_visited.reserve(10_000);
-        t_visited.insert(IVec2::default());
+        t_visited.insert(Point2::origin());
 
         Self {
             t_visited,
-            H: IVec2::default(),
-            T: IVec2::default(),
+            H: Point2::origin(),
+            T: Point2::origin(),
         }
     }
 
@@ -85,15 +42,13 @@ impl Rope {
 
     #[inline]
     fn move_head(&mut self, direction: &Direction) {
-        self.H += direction.to_ivec2();
+        self.H += direction.unit();
     }
 
     #[inline]
     fn move_tail(&mut self) {
-        let delta = self.H - self.T;
-
-        if delta.as_vec2().length() > 1.5 {
-            self.T += delta.sign();
+        if self.H.chebyshev(&self.T) > 1 {
+            self.T += (self.H - self.T).signum();
             self.t_visited.insert(self.T);
         }
     }
@@ -117,8 +72,7 @@ fn sim_rope(motions: &[Motion], n_knots: usize) -> usize {
 }
 
 #[aoc(2022, 9)]
-pub fn main() {
-    let data = aoc_input!(2022, 9).unwrap();
+pub fn main(data: &str) {
     let motions = data
         .trim_end()
         .lines()
@@ -131,3 +85,41 @@ pub fn main() {
     // Part II
     println!("{}", sim_rope(&motions, 10));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = "R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2";
+
+    static EXAMPLE2: &str = "R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20";
+
+    fn parse(data: &str) -> Vec<Motion> {
+        data.lines().map(Motion::from).collect()
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(sim_rope(&parse(EXAMPLE), 2), 13);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(sim_rope(&parse(EXAMPLE), 10), 1);
+        assert_eq!(sim_rope(&parse(EXAMPLE2), 10), 36);
+    }
+}
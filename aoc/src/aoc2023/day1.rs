@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 
 static NUMS: [&str; 10] = [
     ".", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
@@ -33,8 +33,7 @@ fn enumerate_words(line: impl Into<String> + Clone) -> Vec<(usize, u32)> {
 }
 
 #[aoc(2023, 1)]
-pub fn main() {
-    let data = aoc_input!(2023, 1).unwrap();
+pub fn main(data: &str) {
     let lines = data.lines().filter(|x| !x.is_empty()).collect::<Vec<_>>();
 
     // Part I
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::collections::VecDeque;
 
@@ -111,9 +111,8 @@ fn parse(data: &str) -> Diagram {
 }
 
 #[aoc(2023, 10)]
-pub fn main() {
-    let data = aoc_input!(2023, 10).unwrap();
-    let diagram = parse(&data);
+pub fn main(data: &str) {
+    let diagram = parse(data);
     let r#loop = find_loop(&diagram);
 
     // Part I
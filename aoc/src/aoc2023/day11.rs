@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use rustc_hash::FxHashSet;
 
@@ -45,9 +45,8 @@ fn shortest_paths(sky: &FxHashSet<(i64, i64)>) -> u64 {
 }
 
 #[aoc(2023, 11)]
-pub fn main() {
-    let data = aoc_input!(2023, 11).unwrap();
-    let sky = parse(&data);
+pub fn main(data: &str) {
+    let sky = parse(data);
 
     // Part I
     let sky1 = expand(&sky, 1);
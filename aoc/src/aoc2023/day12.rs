@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 
 fn parse(data: &str) -> Vec<(String, Vec<usize>)> {
@@ -67,9 +67,8 @@ fn calc(record: String, groups: Vec<usize>) -> usize {
 
 #[allow(clippy::useless_vec)]
 #[aoc(2023, 12)]
-pub fn main() {
-    let data = aoc_input!(2023, 12).unwrap();
-    let input = parse(&data);
+pub fn main(data: &str) {
+    let input = parse(data);
 
     // Part I
     let total = input
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input, utils::transpose};
+use aoc::{aoc, utils::transpose};
 
 struct ReflectionPattern<'a> {
     pattern: &'a str,
@@ -62,9 +62,8 @@ fn parse(data: &str) -> Vec<ReflectionPattern<'_>> {
 }
 
 #[aoc(2023, 13)]
-pub fn main() {
-    let data = aoc_input!(2023, 13).unwrap();
-    let patterns = parse(&data);
+pub fn main(data: &str) {
+    let patterns = parse(data);
 
     // Part I
     let summary = patterns.iter().map(|p| solve(p, 0)).sum::<usize>();
@@ -1,4 +1,5 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
+use aoc::cycle::find_cycle;
 use rustc_hash::FxHashMap;
 use std::{fmt::Display, hash::Hash, str::FromStr};
 
@@ -94,26 +95,18 @@ impl Hash for Platform {
     }
 }
 
-fn solve_cycle(platform: &mut Platform, n: u32) -> Option<u32> {
-    let mut cache: FxHashMap<Platform, u32> = FxHashMap::default();
-
-    for i in 1..n {
+fn solve_cycle(platform: &Platform, n: usize) -> u32 {
+    let spin = |platform: &Platform| {
+        let mut platform = platform.clone();
         platform.cycle();
-
-        if let Some(v) = cache.insert(platform.clone(), i) {
-            let y = (n - v) % (i - v) + v;
-            return cache
-                .iter()
-                .find(|&(_, &x)| x == y)
-                .map(|(p, _)| p.total_load());
-        }
-    }
-    None
+        platform
+    };
+    let (cycle, history) = find_cycle(platform.clone(), spin);
+    history[cycle.index(n)].total_load()
 }
 
 #[aoc(2023, 14)]
-pub fn main() {
-    let data = aoc_input!(2023, 14).unwrap();
+pub fn main(data: &str) {
     let parsed: Platform = data.parse().unwrap();
 
     // Part I
@@ -122,7 +115,7 @@ pub fn main() {
     println!("{}", platform.total_load());
 
     // Part II
-    let n = solve_cycle(&mut platform, 1_000_000_000).unwrap();
+    let n = solve_cycle(&platform, 1_000_000_000);
     println!("{n}");
 }
 
@@ -150,7 +143,7 @@ O.#..O.#.#
 
     #[test]
     fn test_part2() {
-        let mut platform = Platform::from_str(EXAMPLE).unwrap();
-        assert_eq!(solve_cycle(&mut platform, 1_000_000_000), Some(64));
+        let platform = Platform::from_str(EXAMPLE).unwrap();
+        assert_eq!(solve_cycle(&platform, 1_000_000_000), 64);
     }
 }
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use std::hash::{Hash, Hasher};
 
 #[derive(Default)]
@@ -92,9 +92,8 @@ fn power(boxes: &[Vec<Lens>]) -> usize {
 }
 
 #[aoc(2023, 15)]
-pub fn main() {
-    let data = aoc_input!(2023, 15).unwrap();
-    let input = parse(&data);
+pub fn main(data: &str) {
+    let input = parse(data);
 
     // Part I
     let res: u64 = input
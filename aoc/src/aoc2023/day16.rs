@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::collections::VecDeque;
@@ -76,9 +76,8 @@ fn maximize(contraption: &FxHashMap<(i32, i32), char>, width: i32, height: i32)
 }
 
 #[aoc(2023, 16)]
-pub fn main() {
-    let data = aoc_input!(2023, 16).unwrap();
-    let contraption = parse(&data);
+pub fn main(data: &str) {
+    let contraption = parse(data);
 
     // Part I
     let init = ((-1, 0), 1, 0);
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input, heap::MinHeap};
+use aoc::{aoc, heap::MinHeap};
 use rustc_hash::{FxHashMap, FxHashSet};
 
 type Map = FxHashMap<(i32, i32), usize>;
@@ -157,10 +157,9 @@ fn minimize2(map: &Map, end: &(i32, i32)) -> Option<usize> {
     None
 }
 
-#[aoc(2023, 17)]
-pub fn main() {
-    let data = aoc_input!(2023, 17).unwrap();
-    let map = parse(&data);
+#[aoc(2023, 17, title = "Clumsy Crucible", tags = ["dijkstra", "grid"])]
+pub fn main(data: &str) {
+    let map = parse(data);
     let end = map.keys().max().unwrap();
 
     // Part I
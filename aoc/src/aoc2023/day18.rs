@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 
 fn parse(data: &str) -> Vec<(char, i64)> {
@@ -69,16 +69,14 @@ fn shoelace(edges: &[(i64, i64)]) -> u64 {
 }
 
 #[aoc(2023, 18)]
-pub fn main() {
-    let data = aoc_input!(2023, 18).unwrap();
-
+pub fn main(data: &str) {
     // Part I
-    let plan = parse(&data);
+    let plan = parse(data);
     let edges = dig(&plan);
     println!("{}", shoelace(&edges));
 
     // Part II
-    let plan = parse_hex(&data);
+    let plan = parse_hex(data);
     let edges = dig(&plan);
     println!("{}", shoelace(&edges));
 }
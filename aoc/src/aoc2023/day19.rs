@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input, interval::Interval};
+use aoc::{aoc, interval::Interval};
 use rustc_hash::FxHashMap;
 use std::collections::VecDeque;
 
@@ -132,7 +132,7 @@ fn evaluate_range(
 
                     let range = *rating.get(&var).unwrap();
                     let left_range: Result<Interval, ()> =
-                        (range.start, std::cmp::min(range.end, val - 1)).try_into();
+                        (range.start, std::cmp::min(range.end, val)).try_into();
                     let right_range: Result<Interval, ()> =
                         (std::cmp::max(range.start, val), range.end).try_into();
 
@@ -155,7 +155,7 @@ fn evaluate_range(
 
                     let range = *rating.get(&var).unwrap();
                     let left_range: Result<Interval, ()> =
-                        (range.start, std::cmp::min(range.end, val)).try_into();
+                        (range.start, std::cmp::min(range.end, val + 1)).try_into();
                     let right_range: Result<Interval, ()> =
                         (std::cmp::max(range.start, val + 1), range.end).try_into();
 
@@ -191,9 +191,8 @@ fn evaluate_range(
 }
 
 #[aoc(2023, 19)]
-pub fn main() {
-    let data = aoc_input!(2023, 19).unwrap();
-    let (mut workflows, ratings) = parse(&data);
+pub fn main(data: &str) {
+    let (mut workflows, ratings) = parse(data);
 
     // Part I
     let res: i32 = ratings
@@ -205,10 +204,10 @@ pub fn main() {
 
     // Part II
     let ratings = FxHashMap::from_iter([
-        ('x', Interval::new(1, 4000)),
-        ('m', Interval::new(1, 4000)),
-        ('a', Interval::new(1, 4000)),
-        ('s', Interval::new(1, 4000)),
+        ('x', Interval::new(1, 4001)),
+        ('m', Interval::new(1, 4001)),
+        ('a', Interval::new(1, 4001)),
+        ('s', Interval::new(1, 4001)),
     ]);
 
     workflows.insert("A", vec!["A"]);
@@ -217,7 +216,7 @@ pub fn main() {
     let accepted = evaluate_range(&ratings, &workflows);
     let n_comb: usize = accepted
         .iter()
-        .map(|d| d.values().map(|v| v.len() as usize + 1).product::<usize>())
+        .map(|d| d.values().map(|v| v.len() as usize).product::<usize>())
         .sum();
 
     println!("{n_comb}");
@@ -266,10 +265,10 @@ hdj{m>838:A,pv}
     fn test_part2() {
         let (mut workflows, _) = parse(EXAMPLE);
         let ratings = FxHashMap::from_iter([
-            ('x', Interval::new(1, 4000)),
-            ('m', Interval::new(1, 4000)),
-            ('a', Interval::new(1, 4000)),
-            ('s', Interval::new(1, 4000)),
+            ('x', Interval::new(1, 4001)),
+            ('m', Interval::new(1, 4001)),
+            ('a', Interval::new(1, 4001)),
+            ('s', Interval::new(1, 4001)),
         ]);
 
         workflows.insert("A", vec!["A"]);
@@ -278,7 +277,7 @@ hdj{m>838:A,pv}
         let accepted = evaluate_range(&ratings, &workflows);
         let n_comb: usize = accepted
             .iter()
-            .map(|d| d.values().map(|v| v.len() as usize + 1).product::<usize>())
+            .map(|d| d.values().map(|v| v.len() as usize).product::<usize>())
             .sum();
 
         assert_eq!(n_comb, 167409079868000);
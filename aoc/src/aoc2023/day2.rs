@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use std::str::FromStr;
 
 #[derive(Debug, Default)]
@@ -89,9 +89,8 @@ fn parse(data: &str) -> Vec<&str> {
 }
 
 #[aoc(2023, 2)]
-pub fn main() {
-    let data = aoc_input!(2023, 2).unwrap();
-    let lines = parse(&data);
+pub fn main(data: &str) {
+    let lines = parse(data);
 
     let bag = Set {
         red: 12,
@@ -1,6 +1,6 @@
 use std::collections::VecDeque;
 
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use rustc_hash::{FxHashMap, FxHashSet};
 
 type Garden = FxHashMap<(i32, i32), char>;
@@ -45,10 +45,10 @@ fn search(garden: &Garden, n_steps: usize) -> usize {
             (pos.0 + 1, pos.1),
         ] {
             let adj = ((next.0).rem_euclid(n), (next.1).rem_euclid(n));
-            if let Some(ch) = garden.get(&adj) {
-                if *ch != '#' {
-                    queue.push_back((i + 1, next));
-                }
+            if let Some(ch) = garden.get(&adj)
+                && *ch != '#'
+            {
+                queue.push_back((i + 1, next));
             }
         }
     }
@@ -57,9 +57,8 @@ fn search(garden: &Garden, n_steps: usize) -> usize {
 }
 
 #[aoc(2023, 21)]
-pub fn main() {
-    let data = aoc_input!(2023, 21).unwrap();
-    let garden = parse(&data);
+pub fn main(data: &str) {
+    let garden = parse(data);
     let n: usize = 131;
 
     // Part I
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::collections::VecDeque;
@@ -149,9 +149,7 @@ impl<'a> Iterator for BrickIterator<'a> {
     fn next(&mut self) -> Option<Self::Item> {
         let end = self.brick.ends[1];
 
-        let Some(ret) = self.current else {
-            return None;
-        };
+        let ret = self.current?;
         if ret.x < end.x {
             self.current = Some(Point3 {
                 x: ret.x + 1,
@@ -324,9 +322,8 @@ fn parse(data: &str) -> Tower {
 }
 
 #[aoc(2023, 22)]
-pub fn main() {
-    let data = aoc_input!(2023, 22).unwrap();
-    let tower = parse(&data);
+pub fn main(data: &str) {
+    let tower = parse(data);
     let n = tower.bricks.len();
 
     // Part I
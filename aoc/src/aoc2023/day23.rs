@@ -1,154 +1,52 @@
-use aoc::{aoc, aoc_input};
-use petgraph::{algo::all_simple_paths, prelude::*};
-use rustc_hash::FxHashMap;
-use std::collections::VecDeque;
-
-type Map = FxHashMap<(i32, i32), char>;
-
-fn parse(data: &str) -> Map {
-    data.trim()
-        .lines()
-        .filter(|x| !x.is_empty())
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .map(move |(x, ch)| ((x as i32, y as i32), ch))
-        })
-        .collect::<Map>()
-}
-
-fn longest_path(map: &Map, end: &(i32, i32)) -> Option<usize> {
-    let mut queue = VecDeque::new();
-    let mut paths = vec![];
-
-    queue.push_back(vec![(1, 0)]);
-
-    while let Some(path) = queue.pop_front() {
-        let (x, y) = *path.last().unwrap();
-        if (x, y) == *end {
-            paths.push(path);
-            continue;
-        }
-
-        let ch = match map.get(&(x, y)) {
-            Some(val) => *val,
-            None => {
-                continue;
-            }
-        };
-
-        if ch == '#' {
-            continue;
-        }
-        if (ch == '.' || ch == '>') && !path.contains(&(x + 1, y)) {
-            let mut new_path = path.clone();
-            new_path.push((x + 1, y));
-            queue.push_back(new_path);
-        }
-        if (ch == '.' || ch == '<') && !path.contains(&(x - 1, y)) {
-            let mut new_path = path.clone();
-            new_path.push((x - 1, y));
-            queue.push_back(new_path);
-        }
-        if (ch == '.' || ch == 'v') && !path.contains(&(x, y + 1)) {
-            let mut new_path = path.clone();
-            new_path.push((x, y + 1));
-            queue.push_back(new_path);
-        }
-        if (ch == '.' || ch == '^') && !path.contains(&(x, y - 1)) {
-            let mut new_path = path.clone();
-            new_path.push((x, y - 1));
-            queue.push_back(new_path);
-        }
-    }
-
-    paths.iter().map(|x| x.len() - 1).max()
-}
-
-fn longest_hike(data: &str) -> Option<i32> {
-    //}-> DiGraph<char, i32> {
-    let map = parse(data);
-    let mut idx = FxHashMap::default();
-
-    let mut graph = Graph::new();
-
-    // create nodes
-    for (node, ch) in map {
-        if ch == '#' {
-            continue;
-        }
-        let i = graph.add_node(node);
-        idx.insert(node, i);
-    }
-    // add edges
-    for (node, node_idx) in &idx {
-        for adj in [
-            (node.0 + 1, node.1),
-            (node.0 - 1, node.1),
-            (node.0, node.1 + 1),
-            (node.0, node.1 - 1),
-        ] {
-            if let Some(neighbor) = idx.get(&adj) {
-                graph.add_edge(*node_idx, *neighbor, 1);
-            }
-        }
-    }
-
-    // simplify edges
-    for _ in 0..100 {
-        for node in graph.node_indices() {
-            let adj_edges = graph.edges(node).collect::<Vec<_>>();
-            let adj_nodes = graph.neighbors(node).collect::<Vec<_>>();
-
-            if adj_edges.len() == 2 {
-                let w = adj_edges[0].weight() + adj_edges[1].weight();
-                let (n1, n2) = (adj_nodes[0], adj_nodes[1]);
-
-                graph.add_edge(n1, n2, w);
-                graph.add_edge(n2, n1, w);
-                graph.remove_node(node);
-            }
-        }
-    }
-
-    // find start and end
-    let mut start = NodeIndex::default();
-    let mut end = NodeIndex::default();
-
-    for node in graph.node_indices() {
-        if graph[node] == (1, 0) {
-            start = node;
-        }
-        if graph[node] == (139, 140) {
-            end = node;
+use aoc::aoc;
+use aoc::direction::Direction;
+use aoc::graph::{GridGraph, Neighbors, contract_corridors, exhaustive_longest_path};
+use aoc::grid::Grid;
+use petgraph::prelude::*;
+
+/// Length of the longest hike from the opening in the top row to the one in the bottom
+/// row. Unless `climb`, slopes can only be walked down.
+fn longest_hike(data: &str, climb: bool) -> Option<u32> {
+    let grid = Grid::parse_with(data.trim(), |c| c).unwrap();
+    let mut trails: GridGraph<_, _, Directed> =
+        GridGraph::new(&grid, Neighbors::Four, |&c| c != '#', |_, _| 1);
+    if !climb {
+        let graph = trails.graph();
+        let uphill = graph
+            .edge_indices()
+            .filter(|&edge| {
+                let (from, to) = graph.edge_endpoints(edge).unwrap();
+                Direction::try_from(graph[from]).is_ok_and(|slope| {
+                    grid.step(trails.position(from), slope.offset()) != Some(trails.position(to))
+                })
+            })
+            .collect::<Vec<_>>();
+        // Removing an edge moves the last one into its place
+        for edge in uphill.into_iter().rev() {
+            trails.graph_mut().remove_edge(edge);
         }
     }
 
-    let paths = all_simple_paths::<Vec<_>, _>(&graph, start, end, 0, None).collect::<Vec<_>>();
-
-    let path_lens = paths.iter().map(|path| {
-        path.windows(2)
-            .map(|w| {
-                let edge = graph.find_edge(w[0], w[1]).unwrap();
-                *graph.edge_weight(edge).unwrap()
-            })
-            .sum::<i32>()
-    });
-
-    path_lens.max()
+    let opening = |y| {
+        let x = grid.row(y).iter().position(|&c| c == '.')?;
+        trails.node((x, y))
+    };
+    let (start, goal) = (opening(0)?, opening(grid.height() - 1)?);
+    let junctions = contract_corridors(trails.graph(), |node| node == start || node == goal);
+    let junction = |node| junctions.node_indices().find(|&n| junctions[n] == node);
+
+    exhaustive_longest_path(&junctions, junction(start)?, junction(goal)?, |edge| {
+        *edge.weight()
+    })
 }
 
 #[aoc(2023, 23)]
-pub fn main() {
-    let data = aoc_input!(2023, 23).unwrap();
-    let map = parse(&data);
-
+pub fn main(data: &str) {
     // Part I
-    println!("{}", longest_path(&map, &(139, 140)).unwrap());
+    println!("{}", longest_hike(data, false).unwrap());
 
     // Part II
-    println!("{}", longest_hike(&data).unwrap());
+    println!("{}", longest_hike(data, true).unwrap());
 }
 
 #[cfg(test)]
@@ -181,20 +79,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let map = parse(EXAMPLE);
-        let n = longest_path(&map, &(21, 22)).unwrap();
-        assert_eq!(n, 94);
+        assert_eq!(longest_hike(EXAMPLE, false), Some(94));
     }
 
     #[test]
     fn test_part2() {
-        let mut map = parse(EXAMPLE);
-        for (_, v) in map.iter_mut() {
-            if *v != '#' {
-                *v = '.'
-            }
-        }
-        let n = longest_path(&map, &(21, 22)).unwrap();
-        assert_eq!(n, 154);
+        assert_eq!(longest_hike(EXAMPLE, true), Some(154));
     }
 }
@@ -1,222 +1,126 @@
-#![allow(non_snake_case)]
-/// Part 2
-///
-/// (r, v) - unknonwn
-///
-/// Because
-///  r_i + v_i t = r + v t => (r_i - r) = (v - v_i) t
-///
-/// we have
-///  0 = (r - r_i) ^ (v - v_i)
-///
-/// r ^ v = const, so from a pair of eqs for i and j:
-///
-///  0 = r ^ (v_j - v_i) ^ (r_j - r_i) + r_i ^ v_i ^ r_j + r_j ^ v_j ^ r_i
-///
-/// Having 3 such eqs allow to solve for all coordinates of r
-// Debug:
-// 20361
-// 558415252330828
-// Elapsed: 34ms
-
-// Day 24, 2023
-// 20361
-// 558415252330828
-// Elapsed: 6ms
-use aoc::{aoc, aoc_input};
+//! Part 2
+//!
+//! (r, v) - unknown
+//!
+//! Move to the frame of reference of the first hailstone: it stays at the origin, so the
+//! rock has to pass through the origin as well. The rock's path and the path of another
+//! hailstone then span a plane through the origin, and the rock hits any third hailstone
+//! exactly where that one crosses the plane:
+//!
+//!  t_j = -(r_j . n_i) / (v_j . n_i),  n_i = r_i x v_i
+//!
+//! Two such collisions (time and place) determine the rock's velocity and position.
+//! Everything is computed exactly in `i128`.
+use aoc::aoc;
+use aoc::linalg::{Matrix, Rational};
+use aoc::point::Point3;
 use itertools::Itertools;
-use std::fmt;
 
-type Vec3 = (f64, f64, f64);
+type Vec3 = Point3<i128>;
 
 struct Hailstone {
-    x: f64,
-    y: f64,
-    z: f64,
-    vx: f64,
-    vy: f64,
-    vz: f64,
-}
-
-impl fmt::Debug for Hailstone {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "[{}, {}, {} @ {}, {}, {}]",
-            self.x, self.y, self.z, self.vx, self.vy, self.vz
-        )
-    }
+    p: Vec3,
+    v: Vec3,
 }
 
-impl Hailstone {
-    fn eval(&self, t: f64) -> (f64, f64) {
-        (self.x + self.vx * t, self.y + self.vy * t)
-    }
-
-    fn cross(&self, other: &Hailstone) -> f64 {
-        let num = (other.y - self.y) + other.vy / other.vx * (self.x - other.x);
-        let den = self.vy - other.vy / other.vx * self.vx;
-
-        num / den
-    }
+fn parse_vec3(s: &str) -> Vec3 {
+    let (x, y, z) = s
+        .split(',')
+        .map(|x| x.trim().parse::<i128>().unwrap())
+        .collect_tuple()
+        .unwrap();
+    Vec3::new(x, y, z)
 }
 
 fn parse(data: &str) -> Vec<Hailstone> {
-    let mut hailstones = vec![];
-
-    for (pos, vel) in data
-        .trim()
+    data.trim()
         .lines()
         .filter(|x| !x.is_empty())
-        .map(|line| line.split_once('@').unwrap())
-    {
-        let (x, y, z) = pos
-            .split(',')
-            .map(|x| x.trim().parse::<f64>().unwrap())
-            .tuples::<(_, _, _)>()
-            .next()
-            .unwrap();
-        let (vx, vy, vz) = vel
-            .split(',')
-            .map(|x| x.trim().parse::<f64>().unwrap())
-            .tuples::<(_, _, _)>()
-            .next()
-            .unwrap();
-
-        hailstones.push(Hailstone {
-            x,
-            y,
-            z,
-            vx,
-            vy,
-            vz,
-        });
-    }
-
-    hailstones
-}
-
-fn count_intersections2d(hailstones: &[Hailstone], min: f64, max: f64) -> usize {
-    let mut n = 0;
-    for pair in hailstones.iter().combinations(2) {
-        let left = pair[0];
-        let right = pair[1];
-
-        let t = left.cross(right);
-        let s = right.cross(left);
-        let (x, y) = left.eval(t);
-
-        if min <= x && x <= max && min <= y && y <= max && t > 0.0 && s > 0.0 {
-            n += 1;
-        }
-    }
-
-    n
-}
-
-// TODO: Implement Vec3d struct + all needed funcs
-fn multiply(a: f64, u: Vec3) -> Vec3 {
-    (a * u.0, a * u.1, a * u.2)
-}
-
-fn add(u: Vec3, v: Vec3) -> Vec3 {
-    (u.0 + v.0, u.1 + v.1, u.2 + v.2)
-}
-
-fn subtract(u: Vec3, v: Vec3) -> Vec3 {
-    (u.0 - v.0, u.1 - v.1, u.2 - v.2)
-}
-
-fn dot(u: Vec3, v: Vec3) -> f64 {
-    u.0 * v.0 + u.1 * v.1 + u.2 * v.2
-}
-
-fn cross(v: Vec3, w: Vec3) -> Vec3 {
-    (
-        v.1 * w.2 - v.2 * w.1,
-        v.2 * w.0 - v.0 * w.2,
-        v.0 * w.1 - v.1 * w.0,
-    )
+        .map(|line| {
+            let (pos, vel) = line.split_once('@').unwrap();
+            Hailstone {
+                p: parse_vec3(pos),
+                v: parse_vec3(vel),
+            }
+        })
+        .collect()
 }
 
-// Coefficients in a row of: Ax = b
-fn get_coeffs(ri: Vec3, vi: Vec3, rj: Vec3, vj: Vec3) -> (Vec3, f64) {
-    let a = cross(subtract(vj, vi), subtract(rj, ri));
-    let b = dot(ri, cross(vi, rj)) + dot(rj, cross(vj, ri));
-    (a, -b)
+/// Times at which the paths of both hailstones cross in the XY plane.
+fn cross_xy(left: &Hailstone, right: &Hailstone) -> Option<(Rational, Rational)> {
+    let a = Matrix::from_rows([[left.v.x, -right.v.x], [left.v.y, -right.v.y]]);
+    let b = [right.p.x - left.p.x, right.p.y - left.p.y].map(Rational::from);
+    let times = a.solve(&b)?;
+    Some((times[0], times[1]))
 }
 
-fn gauss_elim(A: &[Vec3; 3], b: Vec3) -> Vec3 {
-    let mut A: [Vec3; 3] = *A;
-    let mut b = b;
-
-    // 1st round
-    let (a0, a1, a2) = (A[0].0, A[1].0, A[2].0);
-    A[1] = subtract(A[1], multiply(a1 / a0, A[0]));
-    A[2] = subtract(A[2], multiply(a2 / a0, A[0]));
-    b.1 -= a1 / a0 * b.0;
-    b.2 -= a2 / a0 * b.0;
+fn count_intersections2d(hailstones: &[Hailstone], min: i128, max: i128) -> usize {
+    let (min, max) = (Rational::from(min), Rational::from(max));
 
-    // 2nd round
-    let (b1, b2) = (A[1].1, A[2].1);
-    A[2] = subtract(A[2], multiply(b2 / b1, A[1]));
-    b.2 -= b2 / b1 * b.1;
-
-    // solve
-    let z = b.2 / A[2].2;
-    let y = (b.1 - A[1].2 * z) / A[1].1;
-    let x = (b.0 - A[0].1 * y - A[0].2 * z) / A[0].0;
-
-    (x, y, z)
+    hailstones
+        .iter()
+        .tuple_combinations()
+        .filter(|(left, right)| {
+            let Some((t, s)) = cross_xy(left, right) else {
+                return false;
+            };
+            let x = Rational::from(left.p.x) + Rational::from(left.v.x) * t;
+            let y = Rational::from(left.p.y) + Rational::from(left.v.y) * t;
+
+            t > Rational::ZERO
+                && s > Rational::ZERO
+                && (min..=max).contains(&x)
+                && (min..=max).contains(&y)
+        })
+        .count()
 }
 
-fn find_single_pos(r: &[Vec3; 6], v: &[Vec3; 6]) -> Vec3 {
-    let (A0, b0) = get_coeffs(r[0], v[0], r[1], v[1]);
-    let (A1, b1) = get_coeffs(r[2], v[2], r[3], v[3]);
-    let (A2, b2) = get_coeffs(r[4], v[4], r[5], v[5]);
-
-    let A = [A0, A1, A2];
-    let b = (b0, b1, b2);
-
-    gauss_elim(&A, b)
+/// Time at which a hailstone at `p` moving with `v` crosses the plane through the origin
+/// with the given `normal`, if it does so at an integer time.
+fn hit_time(p: Vec3, v: Vec3, normal: Vec3) -> Option<i128> {
+    let den = v.dot(&normal);
+    if den == 0 {
+        return None;
+    }
+    Rational::new(-p.dot(&normal), den).to_integer()
 }
 
-fn find_pos(hailstones: &[Hailstone]) -> Option<u64> {
-    let results = hailstones
-        .windows(6)
-        .map(|w| {
-            let (mut r, mut v) = (vec![], vec![]);
-            for h in w {
-                r.push((h.x, h.y, h.z));
-                v.push((h.vx, h.vy, h.vz));
-            }
-            find_single_pos(&r.try_into().unwrap(), &v.try_into().unwrap())
-        })
-        .take(30)
-        .collect::<Vec<Vec3>>();
+fn find_rock(hailstones: &[Hailstone]) -> Option<Vec3> {
+    let [h0, h1, h2] = hailstones.get(..3)? else {
+        return None;
+    };
+    let (p1, v1) = (h1.p - h0.p, h1.v - h0.v);
+    let (p2, v2) = (h2.p - h0.p, h2.v - h0.v);
 
-    // average over 30 hailstones
-    let r0 = *results.first()?;
-    let adj: Vec3 = results[1..].iter().map(|&r| subtract(r0, r)).reduce(add)?;
+    let t1 = hit_time(p1, v1, p2.cross(&v2))?;
+    let t2 = hit_time(p2, v2, p1.cross(&v1))?;
+    if t1 == t2 {
+        return None;
+    }
 
-    let res = subtract(r0, multiply(1.0 / results.len() as f64, adj));
+    let c1 = h1.p + h1.v * t1;
+    let c2 = h2.p + h2.v * t2;
+    let (d, dt) = (c1 - c2, t1 - t2);
+    if [d.x, d.y, d.z].iter().any(|x| x % dt != 0) {
+        return None;
+    }
 
-    Some(res.0.round() as u64 + res.1.round() as u64 + res.2.round() as u64)
+    let v = Vec3::new(d.x / dt, d.y / dt, d.z / dt);
+    Some(c1 - v * t1)
 }
 
 #[aoc(2023, 24)]
-pub fn main() {
-    let data = aoc_input!(2023, 24).unwrap();
-    let hailstones = parse(&data);
+pub fn main(data: &str) {
+    let hailstones = parse(data);
 
     // Part I
-    let min = 200_000_000_000_000.0;
-    let max = 400_000_000_000_000.0;
+    let min = 200_000_000_000_000;
+    let max = 400_000_000_000_000;
     println!("{}", count_intersections2d(&hailstones, min, max));
 
     // Part II
-    let r = find_pos(&hailstones).unwrap();
-    println!("{}", r);
+    let r = find_rock(&hailstones).unwrap();
+    println!("{}", r.x + r.y + r.z);
 }
 
 #[cfg(test)]
@@ -232,48 +136,13 @@ mod tests {
     #[test]
     fn test_part1() {
         let hailstones = parse(EXAMPLE);
-        let z = count_intersections2d(&hailstones, 7.0, 27.0);
+        let z = count_intersections2d(&hailstones, 7, 27);
         assert_eq!(z, 2);
     }
 
     #[test]
-    fn test_multiply() {
-        let v = (13.0, 0.0, -9.0);
-        let v2 = multiply(2.0, v);
-        assert_eq!(v2, (26.0, 0.0, -18.0));
-    }
-
-    #[test]
-    fn test_subtract() {
-        let u = (90.0, -18.0, 7.0);
-        let v = (20.0, 16.6, 3.4);
-        let expected = (70.0, -34.6, 3.6);
-        assert_eq!(subtract(u, v), expected);
-    }
-
-    #[test]
-    fn test_dot() {
-        let u = (17.0, 3.4, 0.0);
-        let v = (9.1, 2.9, 2.3);
-        let expected = 164.56;
-        assert_eq!(dot(u, v), expected);
-    }
-
-    #[test]
-    fn test_cross() {
-        let u = (1.0, 2.0, 3.0);
-        let v = (4.0, 5.0, 6.0);
-        let expected = (-3.0, 6.0, -3.0);
-        assert_eq!(cross(u, v), expected);
-    }
-
-    // Solve[{3x+2y+z==39, 2x+3y+z==34, x+2y+3z=26}, {x, y, z}]
-    // x = 37/4 and y = 17/4 and z = 11/4
-    #[test]
-    fn test_gauss_elim() {
-        let A = [(3.0, 2.0, 1.0), (2.0, 3.0, 1.0), (1.0, 2.0, 3.0)];
-        let b = (39.0, 34.0, 26.0);
-        let expected = (9.25, 4.25, 2.75);
-        assert_eq!(gauss_elim(&A, b), expected);
+    fn test_part2() {
+        let hailstones = parse(EXAMPLE);
+        assert_eq!(find_rock(&hailstones), Some(Vec3::new(24, 13, 10)));
     }
 }
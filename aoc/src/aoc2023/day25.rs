@@ -1,6 +1,6 @@
-use aoc::{aoc, aoc_input};
-use rustworkx_core::connectivity::stoer_wagner_min_cut;
-use rustworkx_core::petgraph::graphmap::UnGraphMap;
+use aoc::aoc;
+use aoc::graph::min_cut;
+use petgraph::graphmap::UnGraphMap;
 
 fn parse(data: &str) -> UnGraphMap<&str, ()> {
     let edges = data.trim().lines().flat_map(|line| {
@@ -11,18 +11,15 @@ fn parse(data: &str) -> UnGraphMap<&str, ()> {
 }
 
 fn solve(graph: &UnGraphMap<&str, ()>) -> Option<usize> {
-    let cut: rustworkx_core::Result<Option<(usize, Vec<_>)>> =
-        stoer_wagner_min_cut(&graph, |_| Ok(1));
-    let (_, partition) = cut.ok()??;
+    let (_, partition) = min_cut(graph, |_| 1)?;
     let (n, m) = (graph.node_count(), partition.len());
 
     Some(m * (n - m))
 }
 
 #[aoc(2023, 25)]
-pub fn main() {
-    let data = aoc_input!(2023, 25).unwrap();
-    let graph = parse(&data);
+pub fn main(data: &str) {
+    let graph = parse(data);
 
     // Part I
     println!("{}", solve(&graph).unwrap());
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use regex_lite::{Match, Regex};
 use std::sync::LazyLock;
 
@@ -104,9 +104,8 @@ fn parse(data: &str) -> Vec<&str> {
 }
 
 #[aoc(2023, 3)]
-pub fn main() {
-    let data = aoc_input!(2023, 3).unwrap();
-    let lines = parse(&data);
+pub fn main(data: &str) {
+    let lines = parse(data);
     let (numbers, symbols) = read_schema(&lines);
 
     // Part I
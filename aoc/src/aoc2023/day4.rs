@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use std::str::FromStr;
 
 #[derive(Debug)]
@@ -54,8 +54,7 @@ fn recursive_score(id: usize, cards: &[Card], cache: &mut [u32]) -> u32 {
 }
 
 #[aoc(2023, 4)]
-pub fn main() {
-    let data = aoc_input!(2023, 4).unwrap();
+pub fn main(data: &str) {
     let cards = data.lines().flat_map(Card::from_str).collect::<Vec<_>>();
 
     // Part I
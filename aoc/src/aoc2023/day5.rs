@@ -1,4 +1,5 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
+use aoc::interval::{Interval, IntervalSet};
 use std::str::FromStr;
 
 #[derive(Debug)]
@@ -60,18 +61,12 @@ impl Mapping {
         self.maps.iter().find_map(|m| m.convert(num)).unwrap_or(num)
     }
 
-    fn inv(&self) -> Self {
-        Mapping {
-            maps: self
-                .maps
+    fn convert_ranges(&self, ranges: &IntervalSet<u64>) -> IntervalSet<u64> {
+        ranges.map_ranges(
+            self.maps
                 .iter()
-                .map(|m| Map {
-                    src: m.dest,
-                    dest: m.src,
-                    length: m.length,
-                })
-                .collect::<Vec<_>>(),
-        }
+                .map(|m| (Interval::new(m.src, m.src + m.length), m.dest)),
+        )
     }
 }
 
@@ -104,29 +99,22 @@ fn part1(seeds: &[u64], mappings: &[Mapping]) -> Option<u64> {
     seeds.iter().map(|seed| project(*seed, mappings)).min()
 }
 
-/// Check if `seed` falls in any of the `seeds` intervals.
-#[inline]
-fn is_seed(seeds: &[u64], seed: u64) -> bool {
-    seeds
-        .chunks(2)
-        .any(|win| (win[0]..win[0] + win[1]).contains(&seed))
-}
-
-/// Iterate final `location`s and project them through the inverse
-/// `mappings` to get the seed.
+/// Project whole `seeds` ranges through the `mappings` and return the lowest location.
 fn part2(seeds: &[u64], mappings: &[Mapping]) -> Option<u64> {
-    let inv_maps = mappings.iter().rev().map(|m| m.inv()).collect::<Vec<_>>();
+    let seeds = seeds
+        .chunks(2)
+        .map(|win| Interval::new(win[0], win[0] + win[1]))
+        .collect::<IntervalSet<_>>();
 
-    (0..u64::MAX).find(|location| {
-        let seed = project(*location, &inv_maps);
-        is_seed(seeds, seed)
-    })
+    mappings
+        .iter()
+        .fold(seeds, |ranges, mapping| mapping.convert_ranges(&ranges))
+        .min()
 }
 
 #[aoc(2023, 5)]
-pub fn main() {
-    let data = aoc_input!(2023, 5).unwrap();
-    let (seeds, mappings) = parse(&data);
+pub fn main(data: &str) {
+    let (seeds, mappings) = parse(data);
 
     // Part I
     println!("{}", part1(&seeds, &mappings).unwrap());
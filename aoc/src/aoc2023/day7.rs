@@ -1,6 +1,5 @@
-use aoc::{aoc, aoc_input};
+use aoc::{aoc, counter::Counter};
 use itertools::Itertools;
-use rustc_hash::FxHashMap;
 use std::{cmp::Ordering, marker::PhantomData};
 
 /// Part I type of game.
@@ -16,7 +15,7 @@ trait GameType {
     fn joker_strength() -> u8;
 
     /// Count cards of the same type to determine HandType.
-    fn count(hand: &str) -> FxHashMap<char, usize>;
+    fn count(hand: &str) -> Counter<char>;
 }
 
 impl GameType for RegularGame {
@@ -24,12 +23,8 @@ impl GameType for RegularGame {
         100
     }
 
-    fn count(hand: &str) -> FxHashMap<char, usize> {
-        hand.chars()
-            .fold(FxHashMap::default(), |mut counter, card| {
-                *counter.entry(card).or_insert(0) += 1;
-                counter
-            })
+    fn count(hand: &str) -> Counter<char> {
+        hand.chars().collect()
     }
 }
 impl GameType for JokerGame {
@@ -37,20 +32,14 @@ impl GameType for JokerGame {
         1
     }
 
-    fn count(hand: &str) -> FxHashMap<char, usize> {
-        let mut counter = hand
-            .chars()
-            .fold(FxHashMap::default(), |mut counter, card| {
-                *counter.entry(card).or_insert(0) += 1;
-                counter
-            });
+    fn count(hand: &str) -> Counter<char> {
+        let mut counter = hand.chars().collect::<Counter<_>>();
         if let Some(n_jokers) = counter.remove(&'J') {
             let max_key = counter
-                .iter()
-                .max_by(|a, b| a.1.cmp(b.1))
-                .map(|(k, _)| k)
-                .unwrap_or(&'J');
-            *counter.entry(*max_key).or_insert(0) += n_jokers;
+                .most_common(1)
+                .first()
+                .map_or('J', |(card, _)| **card);
+            counter.increment_by(max_key, n_jokers);
         }
         counter
     }
@@ -110,26 +99,26 @@ where
         self.cards.chars().map(|c| c.card_strength::<G>()).collect()
     }
 
-    fn count(&self) -> FxHashMap<char, usize> {
+    fn count(&self) -> Counter<char> {
         G::count(self.cards)
     }
 
     fn hand_type(&self) -> HandType {
+        // The two largest groups of cards determine the type
         let counts = self
             .count()
-            .values()
-            .sorted()
-            .rev()
-            .copied()
+            .most_common(2)
+            .into_iter()
+            .map(|(_, n)| n)
             .collect::<Vec<_>>();
 
         match counts.as_slice() {
             [5] => HandType::FiveOfAKind,
             [4, 1] => HandType::FourOfAKind,
             [3, 2] => HandType::FullHouse,
-            [3, 1, 1] => HandType::ThreeOfAKind,
-            [2, 2, 1] => HandType::TwoPair,
-            [2, 1, 1, 1] => HandType::OnePair,
+            [3, 1] => HandType::ThreeOfAKind,
+            [2, 2] => HandType::TwoPair,
+            [2, 1] => HandType::OnePair,
             _ => HandType::HighCard,
         }
     }
@@ -205,15 +194,13 @@ where
 }
 
 #[aoc(2023, 7)]
-pub fn main() {
-    let data = aoc_input!(2023, 7).unwrap();
-
+pub fn main(data: &str) {
     // Part I
-    let (hands, bids) = parse::<RegularGame>(&data);
+    let (hands, bids) = parse::<RegularGame>(data);
     println!("{}", score(&hands, &bids));
 
     // Part II
-    let (hands, bids) = parse::<JokerGame>(&data);
+    let (hands, bids) = parse::<JokerGame>(data);
     println!("{}", score(&hands, &bids));
 }
 
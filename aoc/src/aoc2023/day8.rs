@@ -1,7 +1,7 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
+use aoc::math::lcm;
 use itertools::FoldWhile::{Continue, Done};
 use itertools::Itertools;
-use num::Integer;
 use rustc_hash::FxHashMap;
 
 type Network<'a> = FxHashMap<&'a str, (&'a str, &'a str)>;
@@ -55,13 +55,12 @@ fn travel_ghost(instruction: &str, network: &Network) -> Option<usize> {
         .keys()
         .filter(|node| node.ends_with('A'))
         .map(|node| travel(instruction, network, node, |n| n.ends_with('Z')))
-        .reduce(|a, b| a.lcm(&b))
+        .reduce(lcm)
 }
 
 #[aoc(2023, 8)]
-pub fn main() {
-    let data = aoc_input!(2023, 8).unwrap();
-    let (instruction, network) = parse(&data);
+pub fn main(data: &str) {
+    let (instruction, network) = parse(data);
 
     // Part I
     println!("{}", travel(instruction, &network, "AAA", |n| n == "ZZZ"));
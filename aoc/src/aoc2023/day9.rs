@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 
 fn parse(data: &str) -> Vec<Vec<i64>> {
     data.lines()
@@ -25,9 +25,8 @@ fn extrapolate(seq: &Vec<i64>) -> i64 {
 }
 
 #[aoc(2023, 9)]
-pub fn main() {
-    let data = aoc_input!(2023, 9).unwrap();
-    let seqs = parse(&data);
+pub fn main(data: &str) {
+    let seqs = parse(data);
 
     // Part I
     let res: i64 = seqs.iter().map(extrapolate).sum();
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use rustc_hash::FxHashMap;
 
@@ -15,9 +15,8 @@ fn parse(data: &str) -> (Vec<u64>, Vec<u64>) {
 }
 
 #[aoc(2024, 1)]
-pub fn main() {
-    let data = aoc_input!(2024, 1).unwrap();
-    let (left, right) = parse(&data);
+pub fn main(data: &str) {
+    let (left, right) = parse(data);
 
     // Part I
     let left_sorted: Vec<_> = left.iter().sorted_unstable().collect();
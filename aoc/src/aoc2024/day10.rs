@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::collections::VecDeque;
@@ -33,21 +33,21 @@ fn find_trails(map: &FxHashMap<(i8, i8), i8>, start: &(i8, i8)) -> FxHashSet<Vec
             continue;
         }
 
-        if let Some(pos) = path.last() {
-            if let Some(&height) = map.get(pos) {
-                if height == 9 {
-                    hiking_trails.insert(path);
-                    continue;
-                }
+        if let Some(pos) = path.last()
+            && let Some(&height) = map.get(pos)
+        {
+            if height == 9 {
+                hiking_trails.insert(path);
+                continue;
+            }
 
-                for n in neighbors(pos) {
-                    if let Some(&next) = map.get(&n) {
-                        if next == height + 1 {
-                            let mut next_path = path.clone();
-                            next_path.push(n);
-                            queue.push_back(next_path);
-                        }
-                    }
+            for n in neighbors(pos) {
+                if let Some(&next) = map.get(&n)
+                    && next == height + 1
+                {
+                    let mut next_path = path.clone();
+                    next_path.push(n);
+                    queue.push_back(next_path);
                 }
             }
         }
@@ -57,9 +57,8 @@ fn find_trails(map: &FxHashMap<(i8, i8), i8>, start: &(i8, i8)) -> FxHashSet<Vec
 }
 
 #[aoc(2024, 10)]
-pub fn main() {
-    let data = aoc_input!(2024, 10).unwrap();
-    let map = parse(&data);
+pub fn main(data: &str) {
+    let map = parse(data);
     let hiking_trails: Vec<_> = map
         .iter()
         .filter(|(_, v)| **v == 0)
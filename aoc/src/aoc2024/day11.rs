@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 
 #[memoize::memoize]
 fn split_num(num: usize) -> (usize, usize) {
@@ -19,18 +19,17 @@ fn count_stones(num: usize, n_blinks: usize) -> usize {
     if num == 0 {
         return count_stones(1, n_blinks - 1);
     }
-    if (num.ilog10() + 1) % 2 == 0 {
+    if (num.ilog10() + 1).is_multiple_of(2) {
         let (left, right) = split_num(num);
 
-        return count_stones(left, n_blinks - 1) + count_stones(right, n_blinks - 1);
+        count_stones(left, n_blinks - 1) + count_stones(right, n_blinks - 1)
     } else {
-        return count_stones(num * 2024, n_blinks - 1);
+        count_stones(num * 2024, n_blinks - 1)
     }
 }
 
 #[aoc(2024, 11)]
-pub fn main() {
-    let data = aoc_input!(2024, 11).unwrap();
+pub fn main(data: &str) {
     let stones: Vec<_> = data
         .trim()
         .split(' ')
@@ -1,4 +1,5 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
+use aoc::dsu::KeyedDisjointSet;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::str::FromStr;
 
@@ -39,50 +40,23 @@ impl FromStr for Garden {
 
 impl Garden {
     fn regions(&self) -> Vec<Region> {
-        let mut map = self.map.clone();
-        let mut regions = vec![];
+        let mut plots = KeyedDisjointSet::new();
 
-        loop {
-            let point = map.keys().next().unwrap();
-            let region = self.find_region(point);
-
-            for p in &region.points {
-                map.remove(p);
-            }
-
-            regions.push(region);
-
-            if map.is_empty() {
-                break;
-            }
-        }
-
-        regions
-    }
-
-    fn find_region(&self, start: &(i16, i16)) -> Region {
-        let mut region = Region::default();
-        let mut queue = vec![*start];
-        let mut visited = FxHashSet::default();
-        let plant = *self.map.get(start).unwrap();
-
-        while let Some(point) = queue.pop() {
-            if !visited.insert(point) {
-                continue;
-            }
-
-            if let Some(p) = self.map.get(&point) {
-                if *p == plant {
-                    region.points.insert(point);
-
-                    for adj in point.adj() {
-                        queue.push(adj);
-                    }
+        for (&point, plant) in &self.map {
+            plots.insert(point);
+            for adj in point.adj() {
+                if self.map.get(&adj) == Some(plant) {
+                    plots.union(point, adj);
                 }
             }
         }
 
-        region
+        plots
+            .groups()
+            .map(|points| Region {
+                points: points.into_iter().collect(),
+            })
+            .collect()
     }
 }
 
@@ -120,20 +94,39 @@ impl Region {
     }
 }
 
-#[aoc(2024, 12)]
-pub fn main() {
-    let data = aoc_input!(2024, 12).unwrap();
-    let garden: Garden = data.parse().unwrap();
-    let regions = garden.regions();
+fn fence_costs(garden: &Garden) -> (usize, usize) {
     let (mut cost1, mut cost2) = (0, 0);
 
-    for region in regions {
+    for region in garden.regions() {
         // Part I
         cost1 += region.area() * region.perimeter();
         // Part II
         cost2 += region.area() * region.sides();
     }
 
+    (cost1, cost2)
+}
+
+#[aoc(2024, 12)]
+pub fn main(data: &str) {
+    let garden: Garden = data.parse().unwrap();
+    let (cost1, cost2) = fence_costs(&garden);
+
     println!("{cost1}");
     println!("{cost2}");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let garden: Garden = "AAAA\nBBCD\nBBCC\nEEEC".parse().unwrap();
+        assert_eq!(fence_costs(&garden), (140, 80));
+
+        // Regions of the same plant which do not touch are separate
+        let garden: Garden = "OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO".parse().unwrap();
+        assert_eq!(fence_costs(&garden), (772, 436));
+    }
+}
@@ -1,11 +1,11 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
+use aoc::linalg::{Matrix, Rational};
 use glam::I64Vec2 as Coord;
 use regex_lite::Regex;
 use std::str::FromStr;
 use std::sync::LazyLock;
 
 static RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\d+").unwrap());
-static EPSILON: f64 = 1e-6;
 
 struct Machine {
     a: Coord,
@@ -22,23 +22,16 @@ impl Machine {
     }
 
     fn solve(&self) -> Option<usize> {
-        let (a, b, p) = (self.a.as_dvec2(), self.b.as_dvec2(), self.prize.as_dvec2());
-        let det = a.x * b.y - a.y * b.x;
-
-        if det.abs() < EPSILON {
-            return None;
-        }
-
-        let a_coeff = (b.y * p.x - b.x * p.y) / det;
-        let b_coeff = (a.x * p.y - a.y * p.x) / det;
+        let (a, b, p) = (self.a, self.b, self.prize);
+        let presses = Matrix::from_rows([[a.x, b.x], [a.y, b.y]])
+            .solve(&[Rational::from(p.x), Rational::from(p.y)])?;
 
         // solution must be a non-negative integer
-        if (a_coeff < 0.0)
-            || (b_coeff < 0.0 || a_coeff.fract() > EPSILON || b_coeff.fract() > EPSILON)
-        {
-            None
-        } else {
-            Some((3.0 * a_coeff + b_coeff) as usize)
+        match presses[..] {
+            [a, b] if a >= Rational::ZERO && b >= Rational::ZERO => {
+                Some((3 * a.to_integer()? + b.to_integer()?) as usize)
+            }
+            _ => None,
         }
     }
 }
@@ -60,8 +53,7 @@ impl FromStr for Machine {
 }
 
 #[aoc(2024, 13)]
-pub fn main() {
-    let data = aoc_input!(2024, 13).unwrap();
+pub fn main(data: &str) {
     let machines = data
         .trim()
         .split("\n\n")
@@ -79,3 +71,34 @@ pub fn main() {
         .sum::<usize>();
     println!("{tokens}");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = "\
+Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279";
+
+    #[test]
+    fn test_solve() {
+        let tokens = EXAMPLE
+            .split("\n\n")
+            .map(|m| Machine::from_str(m).unwrap().solve())
+            .collect::<Vec<_>>();
+        assert_eq!(tokens, vec![Some(280), None, Some(200), None]);
+    }
+}
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use std::str::FromStr;
 
 static X: i32 = 101;
@@ -50,19 +50,16 @@ fn variance(pos: &[(i32, i32)]) -> f64 {
     let n = pos.len();
     let total = pos.iter().fold((0, 0), |acc, x| (acc.0 + x.0, acc.1 + x.1));
     let mean = (total.0 as f64 / n as f64, total.1 as f64 / n as f64);
-    let variance = pos
-        .iter()
+
+    pos.iter()
         .map(|x| (x.0 as f64 - mean.0, x.1 as f64 - mean.1))
         .map(|x| x.0 * x.0 + x.1 * x.1)
         .sum::<f64>()
-        / (n as f64);
-
-    variance
+        / (n as f64)
 }
 
 #[aoc(2024, 14)]
-pub fn main() {
-    let data = aoc_input!(2024, 14).unwrap();
+pub fn main(data: &str) {
     let robots: Vec<_> = data.trim().lines().flat_map(Robot::from_str).collect();
 
     // Part I
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use glam::IVec2 as Point;
 use rustc_hash::FxHashMap;
 use std::collections::VecDeque;
@@ -89,11 +89,9 @@ fn gps(map: &FxHashMap<Point, char>, tile: char) -> i32 {
 }
 
 #[aoc(2024, 15)]
-pub fn main() {
-    let data = aoc_input!(2024, 15).unwrap();
-
+pub fn main(data: &str) {
     // Part I
-    let (mut map, moves) = parse(&data);
+    let (mut map, moves) = parse(data);
     simulate(&mut map, &moves);
     println!("{}", gps(&map, 'O'));
 
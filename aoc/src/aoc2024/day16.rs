@@ -1,7 +1,7 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
+use aoc::search::dijkstra;
 use itertools::Itertools;
 use rustc_hash::FxHashMap;
-use std::collections::VecDeque;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct State {
@@ -47,62 +47,26 @@ fn find_best_paths(
     map: &FxHashMap<(i32, i32), char>,
     start: (i32, i32),
     end: (i32, i32),
-) -> (Option<i32>, Vec<Vec<(i32, i32)>>) {
-    let mut best_score = i32::MAX;
-    let mut best_paths = vec![];
-    let mut visited = FxHashMap::default();
-    let mut queue = VecDeque::with_capacity(10_000);
-    queue.push_back((0, State::new(start), vec![start]));
-
-    while let Some((score, state, path)) = queue.pop_front() {
-        // can't be the best path
-        if score > best_score {
-            continue;
-        }
-
-        // has been visited?
-        if let Some(&prev_score) = visited.get(&state) {
-            // yes and with better score
-            if prev_score < score {
-                continue;
-            }
-        }
-        // prev_score >= score -> update the score
-        visited.insert(state.clone(), score);
-
-        // at the end
-        if state.pos == end {
-            if score == best_score {
-                best_paths.push(path);
-            } else {
-                best_score = score;
-                best_paths = vec![path];
-            }
-            continue;
-        }
-
-        // turn left or right
-        queue.push_back((score + 1000, state.turn_left(), path.clone()));
-        queue.push_back((score + 1000, state.turn_right(), path.clone()));
-
-        // move forward
-        let next_state = state.move_forward();
-
-        if map.get(&next_state.pos) != Some(&'#') {
-            let mut next_path = path.clone();
-            next_path.push(next_state.pos);
-            queue.push_back((score + 1, next_state, next_path));
-        }
-    }
-
-    (
-        if best_score < i32::MAX {
-            Some(best_score)
-        } else {
-            None
-        },
-        best_paths,
-    )
+) -> (Option<i32>, usize) {
+    let successors = |state: &State| {
+        let next = state.move_forward();
+        let forward = (map.get(&next.pos) != Some(&'#')).then_some((next, 1));
+
+        [(state.turn_left(), 1000), (state.turn_right(), 1000)]
+            .into_iter()
+            .chain(forward)
+    };
+    let search = dijkstra([State::new(start)], successors, |state| state.pos == end);
+
+    // Part II counts tiles, so states differing only by direction are merged
+    let tiles = search
+        .states_on_shortest_paths()
+        .into_iter()
+        .map(|state| state.pos)
+        .unique()
+        .count();
+
+    (search.cost(), tiles)
 }
 
 fn parse(data: &str) -> FxHashMap<(i32, i32), char> {
@@ -117,17 +81,46 @@ fn parse(data: &str) -> FxHashMap<(i32, i32), char> {
         .collect()
 }
 
-#[aoc(2024, 16)]
-pub fn main() {
-    let data = aoc_input!(2024, 16).unwrap();
-    let map = parse(&data);
+#[aoc(2024, 16, title = "Reindeer Maze", tags = ["bfs", "grid"])]
+pub fn main(data: &str) {
+    let map = parse(data);
     let start = find_in_map(&map, 'S').unwrap();
     let end = find_in_map(&map, 'E').unwrap();
-    let (best_score, best_paths) = find_best_paths(&map, start, end);
+    let (best_score, tiles) = find_best_paths(&map, start, end);
 
     // Part I
     println!("{}", best_score.unwrap());
 
     // Part II
-    println!("{}", best_paths.iter().flatten().unique().count());
+    println!("{tiles}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = "\
+###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############";
+
+    #[test]
+    fn test_best_paths() {
+        let map = parse(EXAMPLE);
+        let start = find_in_map(&map, 'S').unwrap();
+        let end = find_in_map(&map, 'E').unwrap();
+        assert_eq!(find_best_paths(&map, start, end), (Some(7036), 45));
+    }
 }
@@ -1,108 +1,231 @@
 use aoc::aoc;
+use aoc::vm::{Cpu, Instruction, Jump, Machine};
 use itertools::Itertools;
+use std::fmt;
 
-#[derive(Clone)]
-struct Computer {
-    a: usize,
-    b: usize,
-    c: usize,
-    program: Vec<usize>,
-    ptr: usize,
-    output: Vec<usize>,
+const A: usize = 0;
+const B: usize = 1;
+const C: usize = 2;
+
+/// An opcode and its operand, occupying two cells of the program.
+struct Op {
+    opcode: usize,
+    operand: usize,
 }
 
-impl Computer {
-    fn new(a: usize, b: usize, c: usize, program: &[usize]) -> Self {
-        Self {
-            a,
-            b,
-            c,
-            program: program.to_vec(),
-            ptr: 0,
-            output: vec![],
+impl Op {
+    /// Value of the combo operand, `None` for the reserved operand 7.
+    fn combo(&self, registers: &[usize]) -> Option<usize> {
+        match self.operand {
+            0..=3 => Some(self.operand),
+            4 => Some(registers[A]),
+            5 => Some(registers[B]),
+            6 => Some(registers[C]),
+            _ => None,
         }
     }
 
-    fn run(&mut self) {
-        while self.ptr < self.program.len() - 1 {
-            let opcode = self.program[self.ptr];
-            let operand = self.program[self.ptr + 1];
-            self.ptr += 2;
-
-            match opcode {
-                // adv
-                0 => {
-                    let num = self.a;
-                    let den = 2_i32.pow(self.combo(operand) as u32) as usize;
-                    self.a = num / den;
-                }
-                // bxl
-                1 => {
-                    self.b = self.b ^ operand;
-                }
-                // bst
-                2 => {
-                    self.b = self.combo(operand) % 8;
-                }
-                // jnz
-                3 => {
-                    if self.a != 0 {
-                        self.ptr = operand;
-                        continue;
-                    }
-                }
-                // bxc
-                4 => {
-                    self.b = self.b ^ self.c;
-                }
-                // out
-                5 => {
-                    self.output.push(self.combo(operand) % 8);
-                }
-                // bdv
-                6 => {
-                    let num = self.a;
-                    let den = 2_i32.pow(self.combo(operand) as u32) as usize;
-                    self.b = num / den;
-                }
-                // cdv
-                7 => {
-                    let num = self.a;
-                    let den = 2_i32.pow(self.combo(operand) as u32) as usize;
-                    self.c = num / den;
-                }
-                // stop
-                _ => break,
-            }
+    fn has_combo(&self) -> bool {
+        matches!(self.opcode, 0 | 2 | 5..=7)
+    }
+
+    /// Registers read and written, as bitmasks over `A`, `B` and `C`.
+    fn accesses(&self) -> (u8, u8) {
+        let combo = match self.operand {
+            4..=6 if self.has_combo() => 1 << (self.operand - 4),
+            _ => 0,
+        };
+        let (a, b, c) = (1 << A, 1 << B, 1 << C);
+        match self.opcode {
+            0 => (a | combo, a),
+            1 => (b, b),
+            2 | 5 => (combo, if self.opcode == 2 { b } else { 0 }),
+            3 => (a, 0),
+            4 => (b | c, b),
+            6 => (a | combo, b),
+            7 => (a | combo, c),
+            _ => (0, 0),
         }
     }
+}
+
+/// `a >> n`, which is zero for shifts of 64 bits and more.
+fn shr(a: usize, n: usize) -> usize {
+    u32::try_from(n)
+        .ok()
+        .and_then(|n| a.checked_shr(n))
+        .unwrap_or(0)
+}
+
+impl fmt::Display for Op {
+    /// Mnemonic and operand, followed by the equivalent pseudocode.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let combo = match self.operand {
+            0..=3 => self.operand.to_string(),
+            4 => "A".to_string(),
+            5 => "B".to_string(),
+            6 => "C".to_string(),
+            _ => "?".to_string(),
+        };
+        let literal = self.operand;
+
+        let (mnemonic, operand, code) = match self.opcode {
+            0 => ("adv", &combo, format!("A = A >> {combo}")),
+            1 => ("bxl", &literal.to_string(), format!("B = B ^ {literal}")),
+            2 => ("bst", &combo, format!("B = {combo} % 8")),
+            3 => (
+                "jnz",
+                &literal.to_string(),
+                format!("if A != 0 goto {literal}"),
+            ),
+            4 => ("bxc", &String::new(), "B = B ^ C".to_string()),
+            5 => ("out", &combo, format!("out {combo} % 8")),
+            6 => ("bdv", &combo, format!("B = A >> {combo}")),
+            7 => ("cdv", &combo, format!("C = A >> {combo}")),
+            _ => ("???", &literal.to_string(), "halt".to_string()),
+        };
+        write!(f, "{mnemonic} {operand:<3} {code}")
+    }
+}
+
+impl Instruction for Op {
+    type Word = usize;
 
-    fn combo(&self, value: usize) -> usize {
-        match value {
-            0..=3 => value,
-            4 => self.a,
-            5 => self.b,
-            6 => self.c,
-            _ => unreachable!(),
+    /// Invalid programs halt: on the reserved combo operand 7 and on jumps to an odd
+    /// address, which is in the middle of an instruction.
+    fn execute(&self, cpu: &mut Cpu<usize>) -> Jump {
+        let r = &mut cpu.registers;
+        let combo = match self.combo(r) {
+            None if self.has_combo() => return Jump::Halt,
+            combo => combo.unwrap_or_default(),
+        };
+        match self.opcode {
+            // adv
+            0 => r[A] = shr(r[A], combo),
+            // bxl
+            1 => r[B] ^= self.operand,
+            // bst
+            2 => r[B] = combo % 8,
+            // jnz
+            3 if r[A] != 0 && self.operand % 2 == 1 => return Jump::Halt,
+            3 if r[A] != 0 => return Jump::Absolute(self.operand / 2),
+            3 => {}
+            // bxc
+            4 => r[B] ^= r[C],
+            // out
+            5 => cpu.output.push(combo % 8),
+            // bdv
+            6 => r[B] = shr(r[A], combo),
+            // cdv
+            7 => r[C] = shr(r[A], combo),
+            _ => return Jump::Halt,
         }
+        Jump::Next
     }
 }
 
-fn find_a(program: &[usize], a: usize, idx: usize) -> Option<usize> {
-    let n = program.len();
+fn compile(program: &[usize]) -> Vec<Op> {
+    program
+        .chunks_exact(2)
+        .map(|pair| Op {
+            opcode: pair[0],
+            operand: pair[1],
+        })
+        .collect()
+}
+
+/// Initial registers and the program.
+fn parse(data: &str) -> Option<([usize; 3], Vec<usize>)> {
+    let (registers, program) = data.split_once("\n\n")?;
+    let registers = registers
+        .lines()
+        .map(|line| line.split_once(": ")?.1.trim().parse().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let program = program
+        .trim()
+        .strip_prefix("Program: ")?
+        .split(',')
+        .map(|x| x.parse().ok())
+        .collect::<Option<_>>()?;
+
+    Some((registers.try_into().ok()?, program))
+}
+
+/// Listing of the program with combo operands resolved, one instruction per line, for
+/// inspecting a new input.
+pub fn decompile(program: &[usize]) -> Vec<String> {
+    compile(program)
+        .iter()
+        .enumerate()
+        .map(|(i, op)| format!("{:>3}: {op}", 2 * i))
+        .collect()
+}
 
-    if idx == n {
-        return Some(a);
+fn run(program: &[usize], registers: [usize; 3]) -> Vec<usize> {
+    let mut computer = Machine::new(compile(program), 3);
+    computer.reset(&registers);
+    computer.run();
+    computer.take_output()
+}
+
+/// Whether the program is a single loop which outputs a value derived from A, shifts A
+/// right by 3 bits and repeats until A is zero, with B and C set from A before they are
+/// read. All puzzle inputs have this shape.
+fn is_loop_over_a(program: &[usize]) -> bool {
+    let ops = compile(program);
+    let Some((jump, body)) = ops.split_last() else {
+        return false;
+    };
+    let count = |opcode| body.iter().filter(|op| op.opcode == opcode).count();
+    let shifts_a = |op: &&Op| op.opcode == 0 && op.operand == 3;
+    if !program.len().is_multiple_of(2)
+        || (jump.opcode, jump.operand) != (3, 0)
+        || count(3) != 0
+        || count(5) != 1
+        || count(0) != 1
+        || !body.iter().any(|op| shifts_a(&op))
+    {
+        return false;
     }
 
-    for i in 0..8 {
-        let mut computer = Computer::new(a * 8 + i, 0, 0, &program);
-        computer.run();
+    // Registers B and C must not carry values over from the previous iteration
+    let mut written = 1 << A;
+    for op in body {
+        let (reads, writes) = op.accesses();
+        if reads & !written != 0 {
+            return false;
+        }
+        written |= writes;
+    }
+    true
+}
+
+/// Smallest initial value of register A for which the program outputs `target`, or
+/// `None` if there is none or the program is not a loop over A (see [`is_loop_over_a`]).
+///
+/// For such a loop, running on A without its lowest 3-bit chunk produces the same output
+/// minus its first value, so A can be built chunk by chunk from the most significant one,
+/// keeping only the candidates whose output is a suffix of the target.
+fn solve(program: &[usize], registers: [usize; 3], target: &[usize]) -> Option<usize> {
+    if !is_loop_over_a(program) {
+        return None;
+    }
+    let [_, b, c] = registers;
+    let output = |a| run(program, [a, b, c]);
+    if output(0) == target {
+        return Some(0);
+    }
 
-        if computer.output[0] == program[n - idx - 1] {
-            if let Some(a) = find_a(program, a * 8 + i, idx + 1) {
-                return Some(a);
-            }
+    let mut candidates = vec![0];
+    for _ in 0..=usize::BITS / 3 {
+        candidates = candidates
+            .iter()
+            .flat_map(|&a: &usize| (0..8).filter_map(move |d| a.checked_mul(8).map(|a| a + d)))
+            .filter(|&a| a != 0 && target.ends_with(&output(a)))
+            .collect();
+
+        if let Some(&a) = candidates.iter().find(|&&a| output(a) == target) {
+            return Some(a);
         }
     }
 
@@ -110,17 +233,75 @@ fn find_a(program: &[usize], a: usize, idx: usize) -> Option<usize> {
 }
 
 #[aoc(2024, 17)]
-pub fn main() {
-    let program = [2, 4, 1, 1, 7, 5, 1, 5, 4, 3, 5, 5, 0, 3, 3, 0];
+pub fn main(data: &str) {
+    let (registers, program) = parse(data).unwrap();
 
     // Part I
-    let mut computer = Computer::new(38610541, 0, 0, &program);
-    computer.run();
-    println!(
-        "{}",
-        computer.output.iter().map(|x| x.to_string()).join(",")
-    );
+    println!("{}", run(&program, registers).iter().join(","));
 
     // Part II
-    println!("{}", find_a(&program, 0, 0).unwrap());
+    let a = solve(&program, registers, &program)
+        .expect("No value of A makes the program output itself");
+    println!("{a}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let (registers, program) = parse(
+            "Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0",
+        )
+        .unwrap();
+        assert_eq!(run(&program, registers), [4, 6, 3, 5, 6, 3, 5, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_decompile() {
+        assert_eq!(
+            decompile(&[2, 4, 1, 3, 7, 5, 4, 0, 0, 3, 5, 5, 3, 0]),
+            [
+                "  0: bst A   B = A % 8",
+                "  2: bxl 3   B = B ^ 3",
+                "  4: cdv B   C = A >> B",
+                "  6: bxc     B = B ^ C",
+                "  8: adv 3   A = A >> 3",
+                " 10: out B   out B % 8",
+                " 12: jnz 0   if A != 0 goto 0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        let program = [0, 3, 5, 4, 3, 0];
+        assert_eq!(solve(&program, [2024, 0, 0], &program), Some(117440));
+
+        let program = [2, 4, 1, 1, 7, 5, 1, 5, 4, 3, 5, 5, 0, 3, 3, 0];
+        let a = solve(&program, [0, 0, 0], &program).unwrap();
+        assert_eq!(a, 164278899142333);
+        assert_eq!(run(&program, [a, 0, 0]), program);
+
+        // Shifts by 1 bit, and reads B before setting it
+        assert!(!is_loop_over_a(&[0, 1, 5, 4, 3, 0]));
+        assert_eq!(solve(&[0, 1, 5, 4, 3, 0], [0, 0, 0], &[1]), None);
+        assert!(!is_loop_over_a(&[0, 3, 5, 5, 3, 0]));
+        assert!(is_loop_over_a(&[2, 4, 0, 3, 5, 5, 3, 0]));
+    }
+
+    #[test]
+    fn test_invalid_programs() {
+        // Operand 7 is reserved
+        assert_eq!(run(&[5, 4, 5, 7, 5, 4], [1, 0, 0]), [1]);
+        // Jump into the middle of an instruction
+        assert_eq!(run(&[5, 4, 3, 1, 5, 4], [1, 0, 0]), [1]);
+        // Shifting by 64 bits or more
+        assert_eq!(run(&[0, 5, 5, 4], [1, 64, 0]), [0]);
+    }
 }
@@ -1,8 +1,8 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
+use aoc::search::bfs;
 use glam::IVec2 as Point;
 use itertools::Itertools;
-use rustc_hash::{FxHashMap, FxHashSet};
-use std::collections::VecDeque;
+use rustc_hash::FxHashMap;
 
 static DIRS: [Point; 4] = [
     Point::new(1, 0),
@@ -53,35 +53,8 @@ fn neighbors(p: Point) -> impl Iterator<Item = Point> {
 }
 
 fn find_shortest_path(grid: &FxHashMap<Point, char>, start: &Point, end: &Point) -> Option<usize> {
-    let mut best_dist = usize::MAX;
-    let mut visited = FxHashSet::default();
-    let mut queue = VecDeque::new();
-    queue.push_back((0, *start));
-
-    while let Some((dist, pos)) = queue.pop_front() {
-        if dist > best_dist {
-            continue;
-        }
-        if !visited.insert(pos) {
-            continue;
-        }
-        if pos == *end {
-            best_dist = dist;
-            continue;
-        }
-
-        for adj in neighbors(pos) {
-            if grid.get(&adj) == Some(&'.') {
-                queue.push_back((dist + 1, adj));
-            }
-        }
-    }
-
-    if best_dist == usize::MAX {
-        None
-    } else {
-        Some(best_dist)
-    }
+    let successors = |&pos: &Point| neighbors(pos).filter(move |adj| grid.get(adj) == Some(&'.'));
+    bfs([*start], successors, |pos| pos == end).cost()
 }
 
 fn parse(data: &str) -> Vec<Point> {
@@ -94,10 +67,9 @@ fn parse(data: &str) -> Vec<Point> {
         .collect()
 }
 
-#[aoc(2024, 18)]
-pub fn main() {
-    let data = aoc_input!(2024, 18).unwrap();
-    let bytes = parse(&data);
+#[aoc(2024, 18, title = "RAM Run", tags = ["bfs", "grid"])]
+pub fn main(data: &str) {
+    let bytes = parse(data);
     let start = Point::new(0, 0);
     let end = Point::new(70, 70);
 
@@ -109,11 +81,11 @@ pub fn main() {
     // Part II
     let n = bytes.len();
 
-    for i in 1024..n {
-        memory.insert(bytes[i]);
+    for byte in &bytes[1024..n] {
+        memory.insert(*byte);
 
         if find_shortest_path(&memory.grid, &start, &end).is_none() {
-            println!("{},{}", bytes[i].x, bytes[i].y);
+            println!("{},{}", byte.x, byte.y);
             break;
         }
     }
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use rustc_hash::FxHashMap;
 
@@ -42,9 +42,8 @@ fn check_design<'a>(
 }
 
 #[aoc(2024, 19)]
-pub fn main() {
-    let data = aoc_input!(2024, 19).unwrap();
-    let (towels, patterns) = parse(&data);
+pub fn main(data: &str) {
+    let (towels, patterns) = parse(data);
     let mut cache = FxHashMap::default();
     let designs: Vec<_> = patterns
         .iter()
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 
 fn parse(data: &str) -> Vec<Vec<i64>> {
@@ -37,14 +37,14 @@ fn is_clamped(report: &[i64], min: i64, max: i64) -> bool {
         .all(|elem| elem >= min && elem <= max)
 }
 
-fn is_safe_tol(report: &Vec<i64>) -> bool {
+fn is_safe_tol(report: &[i64]) -> bool {
     match is_safe(report) {
         true => true,
         false => {
             let n = report.len();
 
             (0..n).any(|i| {
-                let mut copy = report.clone();
+                let mut copy = report.to_vec();
                 copy.remove(i);
                 is_safe(&copy)
             })
@@ -53,9 +53,8 @@ fn is_safe_tol(report: &Vec<i64>) -> bool {
 }
 
 #[aoc(2024, 2)]
-pub fn main() {
-    let data = aoc_input!(2024, 2).unwrap();
-    let reports = parse(&data);
+pub fn main(data: &str) {
+    let reports = parse(data);
 
     // Part I
     let n = reports.iter().filter(|report| is_safe(report)).count();
@@ -1,10 +1,7 @@
-use aoc::{aoc, aoc_input, heap::MinHeap};
+use aoc::{aoc, heap::IndexedMinHeap};
 use glam::IVec2 as Point;
 use rustc_hash::{FxHashMap, FxHashSet};
-use std::collections::{
-    hash_map::Entry::{Occupied, Vacant},
-    VecDeque,
-};
+use std::collections::VecDeque;
 
 static DIRS: [Point; 4] = [
     Point::new(1, 0),
@@ -58,35 +55,21 @@ fn dijkstra(
     map: &FxHashMap<Point, char>,
     start: &Point,
 ) -> (FxHashMap<Point, i32>, FxHashMap<Point, Point>) {
-    let mut visited = FxHashSet::default();
     let mut prev = FxHashMap::default();
     let mut dist = FxHashMap::default();
     dist.insert(*start, 0);
 
-    let mut queue = MinHeap::new();
+    let mut queue = IndexedMinHeap::new();
     queue.push(0, *start);
 
     while let Some((score, node)) = queue.pop() {
-        if !visited.insert(node) {
-            continue;
-        }
-
         for adj in map.neighbors(node) {
             let next_score = score + 1;
 
-            match dist.entry(adj) {
-                Occupied(ent) => {
-                    if next_score < *ent.get() {
-                        *ent.into_mut() = next_score;
-                        queue.push(next_score, adj);
-                        prev.insert(adj, node);
-                    }
-                }
-                Vacant(ent) => {
-                    ent.insert(next_score);
-                    queue.push(next_score, adj);
-                    prev.insert(adj, node);
-                }
+            if dist.get(&adj).is_none_or(|&d| next_score < d) {
+                dist.insert(adj, next_score);
+                prev.insert(adj, node);
+                queue.decrease_key(next_score, adj);
             }
         }
     }
@@ -103,7 +86,7 @@ fn shortest_path(end: &Point, prev: &FxHashMap<Point, Point>) -> Vec<Point> {
         u = v;
     }
 
-    path.iter().copied().collect()
+    path.to_vec()
 }
 
 fn solve(map: &FxHashMap<Point, char>, duration: usize) -> usize {
@@ -119,13 +102,14 @@ fn solve(map: &FxHashMap<Point, char>, duration: usize) -> usize {
         .unwrap();
 
     let (dist, prev) = dijkstra(map, end);
-    let path = shortest_path(&start, &prev);
+    let path = shortest_path(start, &prev);
 
     let mut ans = vec![];
 
     for (i, elem) in path.iter().enumerate() {
         for cheat in map.cheats(*elem, duration) {
-            let shortcut = (elem.x - cheat.x).abs() as usize + (elem.y - cheat.y).abs() as usize;
+            let shortcut = (elem.x - cheat.x).unsigned_abs() as usize
+                + (elem.y - cheat.y).unsigned_abs() as usize;
 
             if cheat == *end {
                 ans.push(i + shortcut);
@@ -154,10 +138,9 @@ fn parse(data: &str) -> FxHashMap<Point, char> {
         .collect()
 }
 
-#[aoc(2024, 20)]
-pub fn main() {
-    let data = aoc_input!(2024, 20).unwrap();
-    let maze = parse(&data);
+#[aoc(2024, 20, title = "Race Condition", tags = ["dijkstra", "bfs", "grid"])]
+pub fn main(data: &str) {
+    let maze = parse(data);
 
     // Part I
     println!("{}", solve(&maze, 2));
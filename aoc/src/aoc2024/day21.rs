@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use glam::I16Vec2 as Point;
 use itertools::Itertools;
 use memoize::memoize;
@@ -102,8 +102,7 @@ fn complexity(code: &str, n_layers: usize) -> usize {
 }
 
 #[aoc(2024, 21)]
-pub fn main() {
-    let data = aoc_input!(2024, 21).unwrap();
+pub fn main(data: &str) {
     let codes = data.trim().lines();
 
     // Part I
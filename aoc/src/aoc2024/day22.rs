@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use rustc_hash::FxHashMap;
 use std::iter::successors;
 
@@ -22,8 +22,7 @@ fn next_secret(secret: usize) -> usize {
 }
 
 #[aoc(2024, 22)]
-pub fn main() {
-    let data = aoc_input!(2024, 22).unwrap();
+pub fn main(data: &str) {
     let secrets: Vec<usize> = data.lines().filter_map(|line| line.parse().ok()).collect();
 
     // Part I
@@ -55,7 +54,7 @@ pub fn main() {
                     win[3] - win[2],
                     win[4] - win[3],
                 );
-                price_map.entry(diff).or_insert((win[4] % 10) as i64);
+                price_map.entry(diff).or_insert(win[4] % 10);
             }
 
             price_map
@@ -1,104 +1,46 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
+use aoc::circuit::Circuit;
 use itertools::Itertools;
-use rustc_hash::{FxHashMap, FxHashSet};
-use std::collections::VecDeque;
 
-fn parse(data: &str) -> (FxHashMap<&str, bool>, Vec<(&str, &str, &str, &str)>) {
-    let (inits, instructions) = data.trim().split_once("\n\n").unwrap();
+/// The gate network with the initial values of its input wires set.
+fn parse(data: &str) -> Circuit {
+    let (inits, netlist) = data.trim().split_once("\n\n").unwrap();
+    let mut circuit = Circuit::parse(netlist, 1).unwrap();
 
-    let inits = inits
-        .lines()
-        .map(|line| {
-            let (var, val) = line.split_once(": ").unwrap();
-            (var, val.parse::<i64>().unwrap() != 0)
-        })
-        .collect();
-
-    let instructions = instructions
-        .lines()
-        .map(|line| {
-            let (op, out) = line.split_once(" -> ").unwrap();
-            let op = op.split_whitespace().collect::<Vec<_>>();
-            (op[0], op[1], op[2], out)
-        })
-        .collect();
-
-    (inits, instructions)
-}
-
-fn read(memory: &FxHashMap<&str, bool>, ch: char) -> usize {
-    let output = memory
-        .iter()
-        .filter(|(k, _)| k.starts_with(ch))
-        .sorted_unstable()
-        .rev()
-        .map(|(_, v)| if *v { '1' } else { '0' })
-        .collect::<String>();
-    usize::from_str_radix(&output, 2).unwrap()
+    for line in inits.lines() {
+        let (wire, value) = line.split_once(": ").unwrap();
+        circuit.set(wire, value.parse().unwrap());
+    }
+    circuit
 }
 
 #[aoc(2024, 24)]
-pub fn main() {
-    let data = aoc_input!(2024, 24).unwrap();
-    let (inits, instructions) = parse(&data);
+pub fn main(data: &str) {
+    let mut circuit = parse(data);
 
     // Part I
-    let mut memory = inits.clone();
-    let mut queue = VecDeque::from(instructions.clone());
-
-    while let Some((x, op, y, out)) = queue.pop_front() {
-        if let (Some(&left), Some(&right)) = (memory.get(x), memory.get(y)) {
-            let res = match op {
-                "AND" => left & right,
-                "OR" => left | right,
-                "XOR" => left ^ right,
-                _ => unreachable!(),
-            };
-            memory.insert(out, res);
-        } else {
-            queue.push_back((x, op, y, out));
-            continue;
-        }
-    }
-
-    println!("{}", read(&memory, 'z'));
+    println!("{}", circuit.read_number("z").unwrap());
 
     // Part II
-    let mut lookup = FxHashSet::default();
-    let mut swapped = FxHashSet::default();
-
-    for &(x, op, y, _) in &instructions {
-        lookup.insert((x, op));
-        lookup.insert((y, op));
-    }
+    println!("{}", circuit.adder_faults("x", "y", "z").iter().join(","));
+}
 
-    for (x, op, y, out) in instructions {
-        match op {
-            "AND" => {
-                if x != "x00" && y != "x00" && !lookup.contains(&(out, "OR")) {
-                    swapped.insert(out);
-                }
-            }
-            "OR" => {
-                if out.starts_with('z') && out != "z45" {
-                    swapped.insert(out);
-                }
-                if lookup.contains(&(out, "OR")) {
-                    swapped.insert(out);
-                }
-            }
-            "XOR" => {
-                if x.starts_with('x') || y.starts_with('x') {
-                    if x != "x00" && y != "x00" && !lookup.contains(&(out, "XOR")) {
-                        swapped.insert(out);
-                    }
-                } else if !out.starts_with('z') {
-                    swapped.insert(out);
-                }
-            }
-            _ => unreachable!(),
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let example = "x00: 1
+x01: 1
+x02: 1
+y00: 0
+y01: 1
+y02: 0
+
+x00 AND y00 -> z00
+x01 XOR y01 -> z01
+x02 OR y02 -> z02";
+        assert_eq!(parse(example).read_number("z"), Some(4));
     }
-
-    println!("{}", swapped.iter().sorted_unstable().join(","));
 }
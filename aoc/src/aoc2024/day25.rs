@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use std::str::FromStr;
 
@@ -12,7 +12,7 @@ impl FromStr for Schematics {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let line = s.lines().next().clone().unwrap();
+        let line = s.lines().next().unwrap();
         let mut schema = [0, 0, 0, 0, 0];
         let is_lock = line.chars().all(|ch| ch == '#');
 
@@ -68,9 +68,8 @@ fn parse(data: &str) -> (Vec<Schematics>, Vec<Schematics>) {
 }
 
 #[aoc(2024, 25)]
-pub fn main() {
-    let data = aoc_input!(2024, 25).unwrap();
-    let (locks, keys) = parse(&data);
+pub fn main(data: &str) {
+    let (locks, keys) = parse(data);
 
     let n_pairs = locks
         .iter()
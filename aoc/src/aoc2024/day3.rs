@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use regex_lite::{Match, Regex};
 
 fn add_muls(txt: &str) -> u64 {
@@ -26,10 +26,8 @@ fn add_muls_flagged(txt: &str) -> u64 {
                 "do()" => flag = true,
                 "don't()" => flag = false,
                 _ => {
-                    if flag {
-                        if let (Some(x), Some(y)) = (caps.get(1), caps.get(2)) {
-                            sum += to_u64(x) * to_u64(y);
-                        }
+                    if flag && let (Some(x), Some(y)) = (caps.get(1), caps.get(2)) {
+                        sum += to_u64(x) * to_u64(y);
                     }
                 }
             }
@@ -40,8 +38,7 @@ fn add_muls_flagged(txt: &str) -> u64 {
 }
 
 #[aoc(2024, 3)]
-pub fn main() {
-    let data = aoc_input!(2024, 3).unwrap();
+pub fn main(data: &str) {
     let memory = data.replace('\n', "");
 
     // Part I
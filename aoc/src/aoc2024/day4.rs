@@ -1,92 +1,52 @@
-use aoc::{aoc, aoc_input};
-use rustc_hash::FxHashMap;
+use aoc::aoc;
+use aoc::grid::{DIRS8, Grid, Pos};
 
-static XMAS: [char; 4] = ['X', 'M', 'A', 'S'];
-
-fn parse(data: &str) -> FxHashMap<(i32, i32), char> {
-    data.trim()
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .map(move |(x, ch)| ((x as i32, y as i32), ch))
-        })
-        .collect()
-}
+static XMAS: &[u8] = b"XMAS";
 
 // Part I
-fn xmas_count(grid: &FxHashMap<(i32, i32), char>) -> usize {
-    grid.iter()
-        .filter(|&(_, &v)| v == 'X')
-        .map(|(k, _)| count_all_dirs(&grid, k))
+fn xmas_count(grid: &Grid<u8>) -> usize {
+    grid.positions_where(|&ch| ch == XMAS[0])
+        .map(|pos| count_all_dirs(grid, pos))
         .sum::<usize>()
 }
 
-fn count_all_dirs(grid: &FxHashMap<(i32, i32), char>, key: &(i32, i32)) -> usize {
-    let mut count = 0;
-    let dirs = vec![
-        (1, 0),
-        (-1, 0),
-        (0, 1),
-        (0, -1),
-        (1, 1),
-        (1, -1),
-        (-1, 1),
-        (-1, -1),
-    ];
-
-    for dir in dirs {
-        let mut is_xmas = true;
+fn count_all_dirs(grid: &Grid<u8>, pos: Pos) -> usize {
+    DIRS8
+        .into_iter()
+        .filter(|&dir| {
+            let mut pos = pos;
 
-        for i in 1..4 {
-            let new_key = (key.0 + i * dir.0, key.1 + i * dir.1);
-
-            if let Some(ch) = grid.get(&new_key) {
-                if *ch != XMAS[i as usize] {
-                    is_xmas = false;
+            XMAS[1..].iter().all(|ch| match grid.step(pos, dir) {
+                Some(next) if grid[next] == *ch => {
+                    pos = next;
+                    true
                 }
-            } else {
-                is_xmas = false;
-                break;
-            }
-        }
-
-        if is_xmas {
-            count += 1;
-        }
-    }
-
-    count
+                _ => false,
+            })
+        })
+        .count()
 }
 
 // Part II
-fn x_mas_count(grid: &FxHashMap<(i32, i32), char>) -> usize {
-    grid.iter()
-        .filter(|&(_, &v)| v == 'A')
-        .filter(|(k, _)| is_mas_cross(&grid, k))
+fn x_mas_count(grid: &Grid<u8>) -> usize {
+    grid.positions_where(|&ch| ch == b'A')
+        .filter(|&pos| is_mas_cross(grid, pos))
         .count()
 }
 
-fn is_mas_cross(grid: &FxHashMap<(i32, i32), char>, key: &(i32, i32)) -> bool {
-    let is_ms = |x: &(char, char)| *x == ('M', 'S') || *x == ('S', 'M');
+fn is_mas_cross(grid: &Grid<u8>, pos: Pos) -> bool {
+    let at = |dir| grid.step(pos, dir).map_or(b' ', |p| grid[p]);
+    let is_ms = |x: (u8, u8)| x == (b'M', b'S') || x == (b'S', b'M');
 
-    let left = (
-        *grid.get(&(key.0 - 1, key.1 + 1)).unwrap_or(&' '),
-        *grid.get(&(key.0 + 1, key.1 - 1)).unwrap_or(&' '),
-    );
-    let right = (
-        *grid.get(&(key.0 - 1, key.1 - 1)).unwrap_or(&' '),
-        *grid.get(&(key.0 + 1, key.1 + 1)).unwrap_or(&' '),
-    );
+    let left = (at((-1, 1)), at((1, -1)));
+    let right = (at((-1, -1)), at((1, 1)));
 
-    is_ms(&left) && is_ms(&right)
+    is_ms(left) && is_ms(right)
 }
 
 #[aoc(2024, 4)]
-pub fn main() {
-    let data = aoc_input!(2024, 4).unwrap();
-    let grid = parse(&data);
+pub fn main(data: &str) {
+    let grid: Grid<u8> = data.parse().unwrap();
 
     // Part I
     let n = xmas_count(&grid);
@@ -96,3 +56,32 @@ pub fn main() {
     let n = x_mas_count(&grid);
     println!("{n}");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = "\
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+
+    #[test]
+    fn test_part1() {
+        let grid: Grid<u8> = EXAMPLE.parse().unwrap();
+        assert_eq!(xmas_count(&grid), 18);
+    }
+
+    #[test]
+    fn test_part2() {
+        let grid: Grid<u8> = EXAMPLE.parse().unwrap();
+        assert_eq!(x_mas_count(&grid), 9);
+    }
+}
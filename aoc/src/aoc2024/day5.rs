@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 
 fn parse(data: &str) -> (Vec<(u8, u8)>, Vec<Vec<u8>>) {
     let (rules, updates) = data.split_once("\n\n").unwrap();
@@ -34,10 +34,10 @@ fn get_middle(vec: &[u8]) -> u8 {
 // Part I
 fn validate(update: &[u8], rules: &[(u8, u8)]) -> bool {
     for (x, y) in rules {
-        if let (Some(i), Some(j)) = (index(update, x), index(update, y)) {
-            if i > j {
-                return false;
-            }
+        if let (Some(i), Some(j)) = (index(update, x), index(update, y))
+            && i > j
+        {
+            return false;
         }
     }
 
@@ -53,12 +53,12 @@ fn correct(update: &[u8], rules: &[(u8, u8)]) -> Vec<u8> {
         is_valid = true;
 
         for (x, y) in rules {
-            if let (Some(i), Some(j)) = (index(&tmp, x), index(&tmp, y)) {
-                if i > j {
-                    tmp.swap(i, j);
-                    is_valid = false;
-                    break;
-                }
+            if let (Some(i), Some(j)) = (index(&tmp, x), index(&tmp, y))
+                && i > j
+            {
+                tmp.swap(i, j);
+                is_valid = false;
+                break;
             }
         }
     }
@@ -67,9 +67,8 @@ fn correct(update: &[u8], rules: &[(u8, u8)]) -> Vec<u8> {
 }
 
 #[aoc(2024, 5)]
-pub fn main() {
-    let data = aoc_input!(2024, 5).unwrap();
-    let (rules, updates) = parse(&data);
+pub fn main(data: &str) {
+    let (rules, updates) = parse(data);
 
     // Part I
     let sum = updates
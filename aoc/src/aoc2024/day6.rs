@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::str::FromStr;
@@ -73,7 +73,7 @@ impl GuardMap {
             }
             return GuardState::Continue;
         }
-        return GuardState::Finished;
+        GuardState::Finished
     }
 
     fn pos_count(&mut self) -> usize {
@@ -100,20 +100,47 @@ fn find_loops(map: &GuardMap) -> usize {
         .filter_map(|(pos, _)| {
             let mut new_map = map.clone();
             new_map.grid.insert(*pos, '#');
-            new_map.has_loop().then(|| ())
+            new_map.has_loop().then_some(())
+        })
+        .count()
+}
+
+// Only cells on the guard's original route can change it
+fn find_loops_on_route(map: &GuardMap) -> usize {
+    let mut route = map.clone();
+    route.pos_count();
+
+    route
+        .visited
+        .iter()
+        .map(|(pos, _)| *pos)
+        .unique()
+        .filter(|pos| {
+            let mut new_map = map.clone();
+            new_map.grid.insert(*pos, '#');
+            new_map.has_loop()
         })
         .count()
 }
 
 #[aoc(2024, 6)]
-pub fn main() {
-    let data = aoc_input!(2024, 6).unwrap();
+pub fn main(data: &str) -> (usize, usize) {
+    // Part I
+    let mut map: GuardMap = data.parse().unwrap();
+    let visited = map.pos_count();
+
+    // Part II
+    let map: GuardMap = data.parse().unwrap();
+    (visited, find_loops(&map))
+}
 
+#[aoc(2024, 6, variant = "route")]
+pub fn main_route(data: &str) -> (usize, usize) {
     // Part I
     let mut map: GuardMap = data.parse().unwrap();
-    println!("{}", map.pos_count());
+    let visited = map.pos_count();
 
     // Part II
     let map: GuardMap = data.parse().unwrap();
-    println!("{}", find_loops(&map));
+    (visited, find_loops_on_route(&map))
 }
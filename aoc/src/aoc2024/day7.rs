@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use std::str::FromStr;
 
 struct Equation {
@@ -64,9 +64,8 @@ fn parse(data: &str) -> Vec<Equation> {
 }
 
 #[aoc(2024, 7)]
-pub fn main() {
-    let data = aoc_input!(2024, 7).unwrap();
-    let equations = parse(&data);
+pub fn main(data: &str) {
+    let equations = parse(data);
 
     // Part I
     let total_calibration = equations
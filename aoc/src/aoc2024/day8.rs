@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::str::FromStr;
@@ -31,7 +31,7 @@ impl Map {
         let dh = y.1 - x.1;
         let antinode = (x.0 - dw, x.1 - dh);
 
-        self.is_within(&antinode).then(|| antinode)
+        self.is_within(&antinode).then_some(antinode)
     }
 
     // Part I
@@ -44,15 +44,15 @@ impl Map {
             for pair in antenas.iter().combinations(2) {
                 let (x, y) = (pair[0], pair[1]);
 
-                if let Some(antinode) = self.antinode(x, y) {
-                    if !self.antenas.contains_key(&antinode) {
-                        count += 1;
-                    }
+                if let Some(antinode) = self.antinode(x, y)
+                    && !self.antenas.contains_key(&antinode)
+                {
+                    count += 1;
                 }
-                if let Some(antinode) = self.antinode(y, x) {
-                    if !self.antenas.contains_key(&antinode) {
-                        count += 1;
-                    }
+                if let Some(antinode) = self.antinode(y, x)
+                    && !self.antenas.contains_key(&antinode)
+                {
+                    count += 1;
                 }
             }
         }
@@ -116,8 +116,7 @@ impl FromStr for Map {
 }
 
 #[aoc(2024, 8)]
-pub fn main() {
-    let data = aoc_input!(2024, 8).unwrap();
+pub fn main(data: &str) {
     let map: Map = data.parse().unwrap();
 
     // Part I
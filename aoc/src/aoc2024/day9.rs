@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use std::str::FromStr;
 
 #[derive(Debug, Clone)]
@@ -40,7 +40,7 @@ impl DiskMap {
         self.blocks
             .iter()
             .enumerate()
-            .map(|(i, val)| i * (val.unwrap_or(0) as usize))
+            .map(|(i, val)| i * val.unwrap_or(0))
             .sum::<usize>()
     }
 
@@ -94,8 +94,7 @@ impl DiskMap {
 }
 
 #[aoc(2024, 9)]
-pub fn main() {
-    let data = aoc_input!(2024, 9).unwrap();
+pub fn main(data: &str) {
     let mut disk: DiskMap = data.parse().unwrap();
 
     // Part I
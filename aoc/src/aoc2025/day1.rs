@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use std::num::ParseIntError;
 
 #[derive(Debug)]
@@ -55,8 +55,7 @@ impl Dial {
 }
 
 #[aoc(2025, 1)]
-pub fn main() {
-    let data = aoc_input!(2025, 1).unwrap();
+pub fn main(data: &str) {
     let rotations = data
         .lines()
         .flat_map(Rotation::try_from)
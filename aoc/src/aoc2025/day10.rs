@@ -1,13 +1,12 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
+use aoc::linalg::Matrix;
 use itertools::Itertools;
-use rustc_hash::FxHashMap;
 
 fn parse_line(line: &str) -> (Vec<i16>, Vec<Vec<i16>>, Vec<i16>) {
     let (id, rem) = line.split_once("] ").unwrap();
     let (buttons, joltage) = rem.split_once('{').unwrap();
 
-    let indicator = id[1..]
-        .as_bytes()
+    let indicator = id.as_bytes()[1..]
         .iter()
         .map(|ch| if *ch == b'.' { 0 } else { 1 })
         .collect();
@@ -33,9 +32,7 @@ fn parse_line(line: &str) -> (Vec<i16>, Vec<Vec<i16>>, Vec<i16>) {
 }
 
 fn all_binary_vectors(n: usize) -> impl Iterator<Item = Vec<i16>> {
-    std::iter::repeat([0, 1].iter().copied())
-        .take(n)
-        .multi_cartesian_product()
+    std::iter::repeat_n([0, 1].iter().copied(), n).multi_cartesian_product()
 }
 
 fn diagram_from_buttons<'a>(
@@ -53,86 +50,40 @@ fn diagram_from_buttons<'a>(
     out
 }
 
-fn solve(target: &Vec<i16>, buttons: &Vec<Vec<i16>>, joltage: &Vec<i16>) -> (usize, usize) {
+fn solve(target: &[i16], buttons: &[Vec<i16>], joltage: &[i16]) -> (usize, usize) {
     let (n, m) = (target.len(), buttons.len());
-    let mut schemas = FxHashMap::default();
-    let mut patterns = FxHashMap::default();
 
-    // Part I
-    for cfg in all_binary_vectors(m) {
-        let pattern = diagram_from_buttons(
-            n,
-            buttons
-                .iter()
-                .zip(cfg.iter())
-                .filter_map(|(b, i)| if *i == 1 { Some(b) } else { None }),
-        );
-        schemas.insert(cfg.clone(), pattern.clone());
-        let pattern = pattern.iter().map(|x| *x % 2).collect::<Vec<_>>();
-        patterns.entry(pattern).or_insert(vec![]).push(cfg);
-    }
-
-    let p1 = patterns[target]
-        .iter()
-        .map(|v| v.iter().sum::<i16>() as usize)
+    // Part I - pressing a button twice cancels out, so each is pressed at most once
+    let p1 = all_binary_vectors(m)
+        .filter(|cfg| {
+            let pressed = buttons.iter().zip(cfg).filter(|(_, i)| **i == 1);
+            let diagram = diagram_from_buttons(n, pressed.map(|(b, _)| b));
+            diagram.iter().map(|x| *x % 2).eq(target.iter().copied())
+        })
+        .map(|cfg| cfg.iter().sum::<i16>() as usize)
         .min()
         .unwrap();
 
-    // Part II
-    let mut cache = FxHashMap::default();
-
-    fn find(
-        joltage: &Vec<i16>,
-        patterns: &FxHashMap<Vec<i16>, Vec<Vec<i16>>>,
-        schemas: &FxHashMap<Vec<i16>, Vec<i16>>,
-        cache: &mut FxHashMap<Vec<i16>, usize>,
-    ) -> usize {
-        if joltage.iter().all(|x| *x == 0) {
-            return 0;
-        }
-        if let Some(&cached) = cache.get(joltage) {
-            return cached;
-        }
-        let mut res = usize::MAX;
-        let indicator = joltage.iter().map(|x| *x % 2).collect::<Vec<_>>();
-        let configurations = patterns
-            .get(&indicator)
-            .unwrap_or(&vec![])
+    // Part II - one equation per counter, one variable per button
+    let matrix = Matrix::from_rows((0..n).map(|counter| {
+        buttons
             .iter()
-            .cloned()
-            .collect::<Vec<_>>();
-
-        for cfg in configurations {
-            if let Some(diff) = schemas.get(&cfg) {
-                let new_joltage = joltage
-                    .iter()
-                    .zip(diff.iter())
-                    .map(|(a, b)| (*a - *b) / 2)
-                    .collect::<Vec<_>>();
-
-                if new_joltage.iter().any(|x| *x < 0) {
-                    continue;
-                }
-
-                let val = (cfg.iter().sum::<i16>() as usize).saturating_add(
-                    2usize.saturating_mul(find(&new_joltage, patterns, schemas, cache)),
-                );
-                res = res.min(val);
-            }
-        }
-        cache.insert(joltage.clone(), res);
-        res
-    }
-
-    let p2 = find(joltage, &patterns, &schemas, &mut cache);
+            .map(move |b| b.contains(&(counter as i16)) as i128)
+    }));
+    let rhs = joltage.iter().map(|&j| j as i128).collect::<Vec<_>>();
+    let upper = buttons
+        .iter()
+        .map(|b| b.iter().map(|&c| rhs[c as usize]).min().unwrap_or(0))
+        .collect::<Vec<_>>();
+    let (p2, _) = matrix
+        .min_integer_solution(&rhs, &upper, &vec![1; m])
+        .unwrap();
 
-    (p1, p2)
+    (p1, p2 as usize)
 }
 
 #[aoc(2025, 10)]
-pub fn main() {
-    let data = aoc_input!(2025, 10).unwrap();
-
+pub fn main(data: &str) {
     let (p1, p2) = data
         .trim()
         .lines()
@@ -148,3 +99,25 @@ pub fn main() {
     // Part II
     println!("{p2}");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = "\
+[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
+
+    #[test]
+    fn test_solve() {
+        let results = EXAMPLE
+            .lines()
+            .map(|line| {
+                let (target, buttons, joltage) = parse_line(line);
+                solve(&target, &buttons, &joltage)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(results, vec![(2, 10), (3, 12), (2, 11)]);
+    }
+}
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use rustc_hash::{FxHashMap, FxHashSet};
 
@@ -65,9 +65,8 @@ where
 }
 
 #[aoc(2025, 11)]
-pub fn main() {
-    let data = aoc_input!(2025, 11).unwrap();
-    let g = parse(&data);
+pub fn main(data: &str) {
+    let g = parse(data);
 
     // Part I
     println!("{}", count_paths(&g, "you", "out"));
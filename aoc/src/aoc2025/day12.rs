@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 
 fn parse(data: &str) -> (u32, u32, Vec<u32>) {
     let w = data[..2].parse::<u32>().unwrap();
@@ -11,8 +11,7 @@ fn parse(data: &str) -> (u32, u32, Vec<u32>) {
 }
 
 #[aoc(2025, 12)]
-pub fn main() {
-    let data = aoc_input!(2025, 12).unwrap();
+pub fn main(data: &str) {
     let input = data.trim().lines().skip(30);
 
     // Part I
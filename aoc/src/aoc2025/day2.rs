@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 
 fn parse(value: &str) -> std::ops::RangeInclusive<u64> {
@@ -15,7 +15,7 @@ fn is_invalid(num: u64) -> bool {
     let n = num.len();
 
     match n % 2 {
-        0 => &num[..n / 2] == &num[n / 2..],
+        0 => num[..n / 2] == num[n / 2..],
         _ => false,
     }
 }
@@ -26,7 +26,7 @@ fn is_invalid2(num: u64) -> bool {
     let n = num.len();
 
     for i in 1..=n / 2 {
-        if n % i != 0 {
+        if !n.is_multiple_of(i) {
             continue;
         }
         if num.as_bytes().chunks(i).all_equal() {
@@ -38,8 +38,7 @@ fn is_invalid2(num: u64) -> bool {
 }
 
 #[aoc(2025, 2)]
-pub fn main() {
-    let data = aoc_input!(2025, 2).unwrap();
+pub fn main(data: &str) {
     let ranges = data.trim().split(',').map(parse);
 
     let (mut n1, mut n2) = (0, 0);
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 
 fn max_joltage(bank: &str, sz: usize) -> usize {
     let n = bank.len();
@@ -7,8 +7,7 @@ fn max_joltage(bank: &str, sz: usize) -> usize {
 
     for i in 1..=sz {
         let (jdx, ch) =
-            bank[idx..n - sz + i]
-                .as_bytes()
+            bank.as_bytes()[idx..n - sz + i]
                 .iter()
                 .enumerate()
                 .fold(
@@ -24,8 +23,7 @@ fn max_joltage(bank: &str, sz: usize) -> usize {
 }
 
 #[aoc(2025, 3)]
-pub fn main() {
-    let data = aoc_input!(2025, 3).unwrap();
+pub fn main(data: &str) {
     let solve = |n| {
         data.trim()
             .lines()
@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use rustc_hash::FxHashSet;
 
@@ -30,9 +30,8 @@ fn parse(data: &str) -> FxHashSet<(i32, i32)> {
 }
 
 #[aoc(2025, 4)]
-pub fn main() {
-    let data = aoc_input!(2025, 4).unwrap();
-    let mut diagram = parse(&data);
+pub fn main(data: &str) {
+    let mut diagram = parse(data);
 
     // Part I
     let n_rolls = diagram
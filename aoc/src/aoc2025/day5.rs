@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use std::ops::RangeInclusive;
 
@@ -25,9 +25,8 @@ fn parse(data: &str) -> (Vec<RangeInclusive<u64>>, Vec<u64>) {
 }
 
 #[aoc(2025, 5)]
-pub fn main() {
-    let data = aoc_input!(2025, 5).unwrap();
-    let (ranges, ids) = parse(&data);
+pub fn main(data: &str) {
+    let (ranges, ids) = parse(data);
 
     // Part I
     let n_fresh = ids.iter().filter(|id| is_fresh(id, &ranges)).count();
@@ -1,5 +1,5 @@
+use aoc::aoc;
 use aoc::utils::transpose;
-use aoc::{aoc, aoc_input};
 
 /// Sum or multiply numbers in a group.
 fn calculate<'a, N, S>(group: N, op: S) -> u64
@@ -71,14 +71,12 @@ fn parse2(data: &str) -> (Vec<Vec<u64>>, Vec<&str>) {
 }
 
 #[aoc(2025, 6)]
-pub fn main() {
-    let data = aoc_input!(2025, 6).unwrap();
-
+pub fn main(data: &str) {
     // Part I
-    let (numbers, ops) = parse(&data);
+    let (numbers, ops) = parse(data);
     println!("{}", grand_total(&numbers, &ops));
 
     // Part II
-    let (numbers, ops) = parse2(&data);
+    let (numbers, ops) = parse2(data);
     println!("{}", grand_total(&numbers, &ops));
 }
@@ -1,7 +1,7 @@
-use aoc::{aoc, aoc_input, counter::Counter};
+use aoc::{aoc, counter::Counter};
 
 /// Run both part I and part II simulations.
-fn simulate(manifold: &Vec<Vec<u8>>) -> (usize, usize) {
+fn simulate(manifold: &[Vec<u8>]) -> (usize, usize) {
     let start = manifold[0].iter().position(|x| *x == b'S').unwrap();
     let mut beams: Counter<usize> = Counter::from_iter([(start, 1)]);
     let mut n_splits = 0;
@@ -34,9 +34,8 @@ fn parse(data: &str) -> Vec<Vec<u8>> {
 }
 
 #[aoc(2025, 7)]
-pub fn main() {
-    let data = aoc_input!(2025, 7).unwrap();
-    let manifold = parse(&data);
+pub fn main(data: &str) {
+    let manifold = parse(data);
     let (n_splits, n_paths) = simulate(&manifold);
 
     // Part I
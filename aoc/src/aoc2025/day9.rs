@@ -1,4 +1,4 @@
-use aoc::{aoc, aoc_input};
+use aoc::aoc;
 use itertools::Itertools;
 use std::num::ParseIntError;
 
@@ -55,8 +55,7 @@ impl Rectangle {
 }
 
 #[aoc(2025, 9)]
-pub fn main() {
-    let data = aoc_input!(2025, 9).unwrap();
+pub fn main(data: &str) {
     let red_tiles = data.lines().flat_map(Point::try_from).collect::<Vec<_>>();
 
     let (mut a, mut b) = (0, 0);
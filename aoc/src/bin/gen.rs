@@ -7,11 +7,10 @@ use std::{fs::File, process::ExitCode};
 
 fn get_template(year: u16, day: u16) -> String {
     format!(
-        "use aoc::{{aoc, aoc_input}};
+        "use aoc::aoc;
 
 #[aoc({year}, {day})]
-pub fn main() {{
-    let data = aoc_input!({year}, {day}).unwrap();
+pub fn main(data: &str) {{
 }}
 "
     )
@@ -3,4 +3,6 @@ extern crate aoc_derive;
 
 // pub use aoc_core::macros::aoc_input;
 pub use aoc_core::*;
-pub use aoc_derive::{aoc, aoc_main};
+pub use aoc_derive::aoc;
+
+pub mod status;
@@ -0,0 +1,252 @@
+//! Catalogue of solutions: which days are solved, which are stubs, which have tests
+//! and which are missing. A day is solved when the [`Solution`] registry has it; the
+//! `aoc/src/aoc<YEAR>/day<DAY>.rs` sources tell stubs from missing days and which
+//! days have tests.
+use aoc_core::{date::days_in, solution::Solution};
+use serde_json::{Value, json};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+pub static SOURCE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    Solved,
+    Stub,
+    Missing,
+}
+
+impl State {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Solved => "solved",
+            Self::Stub => "stub",
+            Self::Missing => "missing",
+        }
+    }
+
+    fn symbol(&self) -> char {
+        match self {
+            Self::Solved => '*',
+            Self::Stub => '~',
+            Self::Missing => '.',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayStatus {
+    pub day: u16,
+    pub state: State,
+    pub has_tests: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Totals {
+    pub solved: usize,
+    pub stub: usize,
+    pub tested: usize,
+    pub missing: usize,
+}
+
+impl Totals {
+    fn add(&mut self, other: &Totals) {
+        self.solved += other.solved;
+        self.stub += other.stub;
+        self.tested += other.tested;
+        self.missing += other.missing;
+    }
+
+    fn to_json(self) -> Value {
+        json!({
+            "solved": self.solved,
+            "stub": self.stub,
+            "tested": self.tested,
+            "missing": self.missing,
+        })
+    }
+}
+
+impl std::fmt::Display for Totals {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "solved: {}, stub: {}, tested: {}, missing: {}",
+            self.solved, self.stub, self.tested, self.missing
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct YearStatus {
+    pub year: u16,
+    pub days: Vec<DayStatus>,
+}
+
+impl YearStatus {
+    pub fn totals(&self) -> Totals {
+        self.days.iter().fold(Totals::default(), |mut acc, day| {
+            match day.state {
+                State::Solved => acc.solved += 1,
+                State::Stub => acc.stub += 1,
+                State::Missing => acc.missing += 1,
+            }
+            if day.has_tests {
+                acc.tested += 1;
+            }
+            acc
+        })
+    }
+}
+
+pub fn has_tests(source: &str) -> bool {
+    source.contains("#[test]")
+}
+
+fn source_path(dir: &Path, year: u16, day: u16) -> PathBuf {
+    dir.join(format!("aoc{year}")).join(format!("day{day}.rs"))
+}
+
+/// Classify a single day given whether it is registered and its source code (if found).
+/// A source file which registers no solution is a stub.
+pub fn classify(day: u16, registered: bool, source: Option<&str>) -> DayStatus {
+    let state = match (registered, source) {
+        (true, _) => State::Solved,
+        (false, Some(_)) => State::Stub,
+        (false, None) => State::Missing,
+    };
+    let has_tests = source.is_some_and(has_tests);
+
+    DayStatus {
+        day,
+        state,
+        has_tests,
+    }
+}
+
+/// Build the catalogue for given years using registered solutions and sources in `dir`.
+pub fn catalogue(years: impl IntoIterator<Item = u16>, dir: impl AsRef<Path>) -> Vec<YearStatus> {
+    let dir = dir.as_ref();
+
+    years
+        .into_iter()
+        .map(|year| {
+            let days = (1..=days_in(year))
+                .map(|day| {
+                    let registered = inventory::iter::<Solution>().any(|sol| *sol == (year, day));
+                    let source = std::fs::read_to_string(source_path(dir, year, day)).ok();
+                    classify(day, registered, source.as_deref())
+                })
+                .collect();
+            YearStatus { year, days }
+        })
+        .collect()
+}
+
+/// Render a calendar grid (5 days per row) for each year followed by totals.
+pub fn render(catalogue: &[YearStatus]) -> String {
+    let mut out = String::new();
+    let mut totals = Totals::default();
+
+    writeln!(out, "Legend: * solved, ~ stub, . missing, t has tests").unwrap();
+
+    for year in catalogue {
+        writeln!(out, "\n{}", year.year).unwrap();
+
+        for row in year.days.chunks(5) {
+            let line = row
+                .iter()
+                .map(|d| {
+                    let tests = if d.has_tests { 't' } else { ' ' };
+                    format!("{:>4} {}{}", d.day, d.state.symbol(), tests)
+                })
+                .collect::<String>();
+            writeln!(out, "{}", line.trim_end()).unwrap();
+        }
+
+        let year_totals = year.totals();
+        writeln!(out, "{year_totals}").unwrap();
+        totals.add(&year_totals);
+    }
+
+    writeln!(out, "\nTotal: {totals}").unwrap();
+    out
+}
+
+pub fn to_json(catalogue: &[YearStatus]) -> Value {
+    let mut totals = Totals::default();
+    let years = catalogue
+        .iter()
+        .map(|year| {
+            let year_totals = year.totals();
+            totals.add(&year_totals);
+
+            let days = year
+                .days
+                .iter()
+                .map(|d| json!({"day": d.day, "status": d.state.name(), "tests": d.has_tests}))
+                .collect::<Vec<_>>();
+            json!({"year": year.year, "days": days, "totals": year_totals.to_json()})
+        })
+        .collect::<Vec<_>>();
+
+    json!({"years": years, "totals": totals.to_json()})
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SOLVED: &str = "
+#[aoc(2015, 1)]
+pub fn main() {
+    println!(\"{}\", 42);
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_part1() {}
+}
+";
+
+    static STUB: &str = "
+pub fn main(data: &str) {
+}
+";
+
+    #[test]
+    fn test_classify() {
+        let solved = classify(1, true, Some(SOLVED));
+        assert_eq!(solved.state, State::Solved);
+        assert!(solved.has_tests);
+
+        let stub = classify(2, false, Some(STUB));
+        assert_eq!(stub.state, State::Stub);
+        assert!(!stub.has_tests);
+
+        assert_eq!(classify(3, false, None).state, State::Missing);
+        assert_eq!(classify(4, true, None).state, State::Solved);
+    }
+
+    #[test]
+    fn test_totals() {
+        let year = YearStatus {
+            year: 2015,
+            days: vec![
+                classify(1, true, Some(SOLVED)),
+                classify(2, false, Some(STUB)),
+                classify(3, false, None),
+            ],
+        };
+        let totals = Totals {
+            solved: 1,
+            stub: 1,
+            tested: 1,
+            missing: 1,
+        };
+
+        assert_eq!(year.totals(), totals);
+        assert_eq!(to_json(&[year])["totals"]["missing"], 1);
+    }
+}
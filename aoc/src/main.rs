@@ -10,13 +10,150 @@ pub mod aoc2023;
 pub mod aoc2024;
 pub mod aoc2025;
 
-use aoc::cli::{Args, ArgsError};
-use aoc_core::solution::Solution;
+use aoc::cli::{Args, ArgsError, IntcodeArgs, StatusArgs};
+use aoc::intcode::{IntcodeComputer, State, disassemble};
+use aoc::io::{AOC_DIR, get_from_cache};
+use aoc::status;
+use aoc_core::solution::{self, Solution};
 use itertools::Itertools;
+use std::io::BufRead;
+use std::panic;
 use std::process::ExitCode;
 
+fn run_status(pargs: pico_args::Arguments) -> ExitCode {
+    let args = match StatusArgs::try_from(pargs) {
+        Ok(args) => args,
+        Err(help @ ArgsError::Help) => {
+            eprintln!("{help}");
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let years = match args.year {
+        Some(year) => year..=year,
+        None => {
+            let last = inventory::iter::<Solution>()
+                .map(|sol| sol.year)
+                .max()
+                .unwrap_or(2015);
+            2015..=last
+        }
+    };
+    let catalogue = status::catalogue(years, status::SOURCE_DIR);
+
+    if args.json {
+        println!("{}", status::to_json(&catalogue));
+    } else {
+        print!("{}", status::render(&catalogue));
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn run_intcode(pargs: pico_args::Arguments) -> ExitCode {
+    let args = match IntcodeArgs::try_from(pargs) {
+        Ok(args) => args,
+        Err(help @ ArgsError::Help) => {
+            eprintln!("{help}");
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let program = match get_from_cache(AOC_DIR, 2019u16, args.day) {
+        Ok(data) => data,
+        Err(err) => {
+            eprintln!("No cached input for day {}, 2019: {err}", args.day);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut computer = match program.parse::<IntcodeComputer>() {
+        Ok(computer) => computer,
+        Err(err) => {
+            eprintln!(
+                "Input of day {}, 2019 is not an Intcode program: {err}",
+                args.day
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if args.disasm {
+        for line in disassemble(computer.memory()) {
+            println!("{line}");
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    computer.set_tracing(true);
+    computer.extend_input(args.inputs);
+
+    // Interactive stepping executes one instruction per line read from stdin
+    let mut lines = std::io::stdin().lock().lines();
+    let mut steps = 0;
+    let state = loop {
+        if args.steps.is_some_and(|max| steps >= max) {
+            break State::Running;
+        }
+        if args.interactive {
+            if let Some(instruction) = computer.current() {
+                eprint!("{instruction}  (rb = {}) ", computer.relative_base());
+            }
+            if !matches!(lines.next(), Some(Ok(_))) {
+                break State::Running;
+            }
+        }
+
+        let state = computer.step();
+        if matches!(state, State::NeedsInput | State::Fault { .. }) {
+            break state;
+        }
+        steps += 1;
+        for event in computer.take_trace() {
+            println!("{event}");
+        }
+        if state == State::Halted {
+            break state;
+        }
+    };
+
+    if let State::Fault { code, address } = state {
+        eprintln!("Invalid Intcode instruction {code} at {address} after {steps} steps");
+        return ExitCode::FAILURE;
+    }
+    println!("{state:?} after {steps} steps at {}", computer.ip());
+    ExitCode::SUCCESS
+}
+
 fn main() -> ExitCode {
-    let pargs = pico_args::Arguments::from_env();
+    if let Err(err) = solution::validate_registry() {
+        eprintln!("{err}");
+        return ExitCode::FAILURE;
+    }
+
+    let mut pargs = pico_args::Arguments::from_env();
+
+    match pargs.subcommand() {
+        Ok(Some(cmd)) if cmd == "status" => return run_status(pargs),
+        Ok(Some(cmd)) if cmd == "intcode" => return run_intcode(pargs),
+        Ok(Some(cmd)) => {
+            eprintln!("Unknown command: {cmd}");
+            return ExitCode::FAILURE;
+        }
+        Ok(None) => {}
+        Err(err) => {
+            eprintln!("{}", ArgsError::Error(format!("{err}")));
+            return ExitCode::FAILURE;
+        }
+    }
+
     let args = match Args::try_from(pargs) {
         Ok(args) => args,
         Err(help @ ArgsError::Help) => {
@@ -31,14 +168,20 @@ fn main() -> ExitCode {
 
     let mut solutions = inventory::iter::<Solution>()
         .filter(|sol| {
-            let is_year = sol.year == args.year;
+            let is_year = args.all || sol.year == args.year;
             let is_day = match args.day {
                 Some(d) => sol.day == d,
                 _ => true,
             };
-            is_day && is_year
+            let is_tagged = args.tags.is_empty() || args.tags.iter().any(|t| sol.has_tag(t));
+            let is_variant = match &args.variant {
+                _ if args.compare => true,
+                Some(v) => sol.variant_name() == v,
+                None => sol.is_default(),
+            };
+            is_day && is_year && is_tagged && is_variant
         })
-        .sorted_by_key(|sol| (sol.year, sol.day))
+        .sorted_by_key(|sol| (sol.year, sol.day, !sol.is_default(), sol.variant_name()))
         .peekable();
 
     if solutions.peek().is_none() {
@@ -46,11 +189,90 @@ fn main() -> ExitCode {
         return ExitCode::FAILURE;
     }
 
+    if args.compare {
+        return compare_variants(solutions);
+    }
+
     for solution in solutions {
-        println!("Day {}, {}", solution.day, solution.year);
-        solution.run();
+        for line in header(solution) {
+            println!("{line}");
+        }
+        for answer in solution.run() {
+            println!("{answer}");
+        }
         println!();
     }
 
     ExitCode::SUCCESS
 }
+
+/// Lines printed before running a solution.
+fn header(solution: &Solution) -> Vec<String> {
+    let mut lines = vec![];
+    let day = match solution.variant {
+        Some(variant) => format!("Day {}, {} ({variant})", solution.day, solution.year),
+        None => format!("Day {}, {}", solution.day, solution.year),
+    };
+
+    match solution.title {
+        Some(title) => lines.push(format!("{day}: {title}")),
+        None => lines.push(day),
+    }
+    if !solution.tags.is_empty() {
+        lines.push(format!("Tags: {}", solution.tags.join(", ")));
+    }
+    if let Some(complexity) = solution.complexity {
+        lines.push(format!("Expected complexity: {complexity}"));
+    }
+    lines
+}
+
+/// Run a single variant and collect the answers it returns.
+fn variant_answers(solution: &Solution) -> Result<Vec<String>, String> {
+    let answers = panic::catch_unwind(|| solution.run()).map_err(|err| {
+        err.downcast_ref::<&str>()
+            .map(|msg| msg.to_string())
+            .or_else(|| err.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panicked".to_string())
+    })?;
+
+    match answers.is_empty() {
+        true => Err("prints its answers instead of returning them".to_string()),
+        false => Ok(answers),
+    }
+}
+
+/// Run every variant of the selected days and check that they print the same answers.
+fn compare_variants<'a>(solutions: impl Iterator<Item = &'a Solution>) -> ExitCode {
+    let mut agree = true;
+
+    for ((year, day), variants) in &solutions.chunk_by(|sol| (sol.year, sol.day)) {
+        let variants = variants.collect::<Vec<_>>();
+        if variants.len() < 2 {
+            continue;
+        }
+
+        println!("Day {day}, {year}");
+        let mut results = vec![];
+        for solution in variants {
+            let answers = variant_answers(solution);
+            match &answers {
+                Ok(answers) => println!("{:>12}: {}", solution.variant_name(), answers.join(" | ")),
+                Err(err) => println!("{:>12}: failed: {err}", solution.variant_name()),
+            }
+            results.push(answers);
+        }
+
+        if results.iter().all_equal() && results[0].is_ok() {
+            println!("Variants agree\n");
+        } else {
+            println!("Variants disagree\n");
+            agree = false;
+        }
+    }
+
+    match agree {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::FAILURE,
+    }
+}
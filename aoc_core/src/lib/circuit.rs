@@ -0,0 +1,549 @@
+//! Netlists of logic gates driving named wires (2015/7, 2024/24).
+//!
+//! A [`Circuit`] is parsed from lines such as `x AND y -> z`, `NOT x -> y` or `123 -> x`.
+//! Signals are `width` bits wide and resolved lazily with memoization; wires can be
+//! overridden with constant signals, which invalidates every resolved signal.
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::fmt::Write;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Gate {
+    /// Copies its input.
+    Buf,
+    Not,
+    And,
+    Or,
+    Xor,
+    LShift,
+    RShift,
+}
+
+impl Gate {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Gate::Buf => "BUF",
+            Gate::Not => "NOT",
+            Gate::And => "AND",
+            Gate::Or => "OR",
+            Gate::Xor => "XOR",
+            Gate::LShift => "LSHIFT",
+            Gate::RShift => "RSHIFT",
+        }
+    }
+}
+
+impl FromStr for Gate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "AND" => Ok(Gate::And),
+            "OR" => Ok(Gate::Or),
+            "XOR" => Ok(Gate::Xor),
+            "LSHIFT" => Ok(Gate::LShift),
+            "RSHIFT" => Ok(Gate::RShift),
+            _ => Err(format!("Unknown gate: {s}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operand {
+    Wire(usize),
+    Const(u64),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Driver {
+    gate: Gate,
+    inputs: Vec<Operand>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Signal {
+    Unknown,
+    Resolving,
+    Known(u64),
+    /// Depends on an undriven wire or a cycle.
+    Failed,
+}
+
+#[derive(Debug, Clone)]
+pub struct Circuit {
+    mask: u64,
+    names: Vec<String>,
+    index: FxHashMap<String, usize>,
+    drivers: Vec<Option<Driver>>,
+    overrides: Vec<Option<u64>>,
+    signals: Vec<Signal>,
+}
+
+impl Circuit {
+    /// An empty circuit carrying signals of `width` bits, which must be 1 to 64.
+    pub fn new(width: u32) -> Result<Self, String> {
+        if !(1..=64).contains(&width) {
+            return Err(format!("Signal width must be 1 to 64 bits, not {width}"));
+        }
+        Ok(Self {
+            mask: u64::MAX >> (64 - width),
+            names: vec![],
+            index: FxHashMap::default(),
+            drivers: vec![],
+            overrides: vec![],
+            signals: vec![],
+        })
+    }
+
+    /// Parse a netlist with one `<expression> -> <wire>` connection per line.
+    pub fn parse(netlist: &str, width: u32) -> Result<Self, String> {
+        let mut circuit = Self::new(width)?;
+        for line in netlist.lines().filter(|line| !line.trim().is_empty()) {
+            circuit.connect(line)?;
+        }
+        Ok(circuit)
+    }
+
+    /// Add a single connection such as `x AND y -> z`.
+    pub fn connect(&mut self, line: &str) -> Result<(), String> {
+        let (expr, output) = line
+            .split_once("->")
+            .ok_or(format!("Missing output in: {line}"))?;
+
+        let tokens = expr.split_whitespace().collect::<Vec<_>>();
+        let (gate, inputs) = match tokens[..] {
+            [a] => (Gate::Buf, vec![a]),
+            ["NOT", a] => (Gate::Not, vec![a]),
+            [a, gate, b] => (gate.parse()?, vec![a, b]),
+            _ => return Err(format!("Invalid gate in: {line}")),
+        };
+
+        let inputs = inputs
+            .into_iter()
+            .map(|input| match input.parse() {
+                Ok(value) => Operand::Const(value),
+                Err(_) => Operand::Wire(self.wire(input)),
+            })
+            .collect();
+        let output = self.wire(output.trim());
+        self.drivers[output] = Some(Driver { gate, inputs });
+        self.invalidate();
+        Ok(())
+    }
+
+    /// Index of the wire, adding it if unseen.
+    fn wire(&mut self, name: &str) -> usize {
+        if let Some(&i) = self.index.get(name) {
+            return i;
+        }
+        self.index.insert(name.to_string(), self.names.len());
+        self.names.push(name.to_string());
+        self.drivers.push(None);
+        self.overrides.push(None);
+        self.signals.push(Signal::Unknown);
+        self.names.len() - 1
+    }
+
+    /// Names of all wires, in order of appearance.
+    pub fn wires(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+
+    /// Gate driving the wire, ignoring overrides.
+    pub fn gate(&self, wire: &str) -> Option<Gate> {
+        let driver = self.drivers[*self.index.get(wire)?].as_ref()?;
+        Some(driver.gate)
+    }
+
+    /// Input wires of the gate driving the wire. Constant inputs are skipped.
+    pub fn inputs(&self, wire: &str) -> Vec<&str> {
+        let Some(Some(driver)) = self.index.get(wire).map(|&i| &self.drivers[i]) else {
+            return vec![];
+        };
+        driver
+            .inputs
+            .iter()
+            .filter_map(|input| match input {
+                Operand::Wire(i) => Some(self.names[*i].as_str()),
+                Operand::Const(_) => None,
+            })
+            .collect()
+    }
+
+    /// Drive the wire with a constant signal instead of its gate.
+    pub fn set(&mut self, wire: &str, value: u64) {
+        let i = self.wire(wire);
+        self.overrides[i] = Some(value & self.mask);
+        self.invalidate();
+    }
+
+    /// Drive the wire by its gate again.
+    pub fn unset(&mut self, wire: &str) {
+        if let Some(&i) = self.index.get(wire) {
+            self.overrides[i] = None;
+            self.invalidate();
+        }
+    }
+
+    fn invalidate(&mut self) {
+        self.signals.fill(Signal::Unknown);
+    }
+
+    /// Signal on the wire, or `None` if it depends on an undriven wire or a cycle.
+    pub fn signal(&mut self, wire: &str) -> Option<u64> {
+        let i = *self.index.get(wire)?;
+        self.resolve(i)
+    }
+
+    fn resolve(&mut self, i: usize) -> Option<u64> {
+        match self.signals[i] {
+            Signal::Known(value) => return Some(value),
+            Signal::Resolving | Signal::Failed => return None,
+            Signal::Unknown => {}
+        }
+        if let Some(value) = self.overrides[i] {
+            self.signals[i] = Signal::Known(value);
+            return Some(value);
+        }
+
+        let Some(driver) = self.drivers[i].clone() else {
+            self.signals[i] = Signal::Failed;
+            return None;
+        };
+        self.signals[i] = Signal::Resolving;
+        let mut inputs = Vec::with_capacity(driver.inputs.len());
+        for input in driver.inputs {
+            let value = match input {
+                Operand::Wire(j) => self.resolve(j),
+                Operand::Const(value) => Some(value),
+            };
+            // Remembered until the next invalidation, so other queries do not walk
+            // the same inputs again
+            let Some(value) = value else {
+                self.signals[i] = Signal::Failed;
+                return None;
+            };
+            inputs.push(value);
+        }
+
+        let value = self.apply(driver.gate, &inputs);
+        self.signals[i] = Signal::Known(value);
+        Some(value)
+    }
+
+    fn apply(&self, gate: Gate, inputs: &[u64]) -> u64 {
+        let value = match (gate, inputs) {
+            (Gate::Buf, &[a]) => a,
+            (Gate::Not, &[a]) => !a,
+            (Gate::And, &[a, b]) => a & b,
+            (Gate::Or, &[a, b]) => a | b,
+            (Gate::Xor, &[a, b]) => a ^ b,
+            (Gate::LShift, &[a, b]) => a.checked_shl(b as u32).unwrap_or(0),
+            (Gate::RShift, &[a, b]) => a.checked_shr(b as u32).unwrap_or(0),
+            _ => unreachable!(),
+        };
+        value & self.mask
+    }
+
+    /// Wires feeding the wire, which overridden wires do not have.
+    fn sources(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+        let driver = self.drivers[i]
+            .as_ref()
+            .filter(|_| self.overrides[i].is_none());
+        driver
+            .into_iter()
+            .flat_map(|driver| driver.inputs.iter())
+            .filter_map(|input| match input {
+                Operand::Wire(j) => Some(*j),
+                Operand::Const(_) => None,
+            })
+    }
+
+    /// Wires forming a feedback loop, each feeding the next and the last the first.
+    pub fn find_cycle(&self) -> Option<Vec<&str>> {
+        // 0: unvisited, 1: on the current path, 2: done
+        let mut state = vec![0u8; self.names.len()];
+        let mut path = vec![];
+
+        for start in 0..self.names.len() {
+            if state[start] != 0 {
+                continue;
+            }
+            let mut stack = vec![(start, self.sources(start).collect::<Vec<_>>())];
+            state[start] = 1;
+            path.push(start);
+
+            while let Some((_, sources)) = stack.last_mut() {
+                match sources.pop() {
+                    Some(j) if state[j] == 1 => {
+                        let k = path.iter().position(|&x| x == j).unwrap();
+                        let cycle = path[k..].iter().rev().map(|&x| self.names[x].as_str());
+                        return Some(cycle.collect());
+                    }
+                    Some(j) if state[j] == 0 => {
+                        state[j] = 1;
+                        path.push(j);
+                        stack.push((j, self.sources(j).collect()));
+                    }
+                    Some(_) => {}
+                    None => {
+                        let (i, _) = stack.pop().unwrap();
+                        state[i] = 2;
+                        path.pop();
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Wires ordered so that every wire comes after the wires feeding it.
+    pub fn topological_order(&self) -> Result<Vec<&str>, String> {
+        let mut consumers = vec![vec![]; self.names.len()];
+        let mut pending = vec![0; self.names.len()];
+        for (i, count) in pending.iter_mut().enumerate() {
+            for j in self.sources(i) {
+                consumers[j].push(i);
+                *count += 1;
+            }
+        }
+
+        let mut order = (0..self.names.len())
+            .filter(|&i| pending[i] == 0)
+            .collect::<Vec<_>>();
+        let mut k = 0;
+        while k < order.len() {
+            for &j in &consumers[order[k]] {
+                pending[j] -= 1;
+                if pending[j] == 0 {
+                    order.push(j);
+                }
+            }
+            k += 1;
+        }
+
+        if order.len() < self.names.len() {
+            let cycle = self.find_cycle().unwrap_or_default();
+            return Err(format!("Cycle through wires: {}", cycle.join(" -> ")));
+        }
+        Ok(order.into_iter().map(|i| self.names[i].as_str()).collect())
+    }
+
+    /// Resolve every wire in topological order, so that later lookups are cached.
+    pub fn evaluate(&mut self) -> Result<(), String> {
+        let order = self
+            .topological_order()?
+            .into_iter()
+            .map(|wire| self.index[wire])
+            .collect::<Vec<_>>();
+        for i in order {
+            self.resolve(i);
+        }
+        Ok(())
+    }
+
+    /// Number whose bit `k` is the signal on wire `{prefix}{k}`, e.g. `z00`, `z01`, ...
+    pub fn read_number(&mut self, prefix: &str) -> Option<u64> {
+        let mut bits = self
+            .names
+            .iter()
+            .enumerate()
+            .filter_map(|(i, name)| Some((name.strip_prefix(prefix)?.parse().ok()?, i)))
+            .collect::<Vec<(u32, usize)>>();
+        bits.sort_unstable();
+
+        let mut number = 0;
+        for (k, i) in bits {
+            number |= (self.resolve(i)? & 1) << k;
+        }
+        Some(number)
+    }
+
+    /// Override wires `{prefix}{k}` with bit `k` of `number`.
+    pub fn write_number(&mut self, prefix: &str, number: u64) {
+        let bits = self
+            .names
+            .iter()
+            .filter_map(|name| Some((name.strip_prefix(prefix)?.parse().ok()?, name.clone())))
+            .collect::<Vec<(u32, String)>>();
+        for (k, name) in bits {
+            self.set(&name, (number >> k) & 1);
+        }
+    }
+
+    /// Graphviz description with a node per wire and an edge per gate input, labelled
+    /// with the gate.
+    pub fn to_dot(&self) -> String {
+        let mut dot = "digraph circuit {\n".to_string();
+        for (i, name) in self.names.iter().enumerate() {
+            if let Some(value) = self.overrides[i] {
+                writeln!(dot, "  \"{name}\" [label=\"{name} = {value}\"];").unwrap();
+            }
+            let Some(driver) = &self.drivers[i] else {
+                continue;
+            };
+            for input in &driver.inputs {
+                let source = match input {
+                    Operand::Wire(j) => format!("\"{}\"", self.names[*j]),
+                    Operand::Const(value) => format!("{value}"),
+                };
+                writeln!(
+                    dot,
+                    "  {source} -> \"{name}\" [label={}];",
+                    driver.gate.name()
+                )
+                .unwrap();
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Output wires of gates which do not fit a ripple-carry adder of the numbers on
+    /// wires `{a}{k}` and `{b}{k}` into `{sum}{k}`, sorted by name.
+    ///
+    /// Bit 0 is a half adder. Every other bit `k` computes `a XOR b` and `a AND b`, feeds
+    /// the first into a XOR and an AND with the incoming carry, producing `{sum}{k}` and
+    /// a partial carry, and ORs both partial carries into the outgoing carry. The carry
+    /// out of the top bit is the highest sum wire.
+    pub fn adder_faults(&self, a: &str, b: &str, sum: &str) -> Vec<&str> {
+        let bit = |name: &str, prefix: &str| name.strip_prefix(prefix)?.parse::<u32>().ok();
+        let is_input = |name: &str| bit(name, a).is_some() || bit(name, b).is_some();
+        let top = self.names.iter().filter_map(|name| bit(name, sum)).max();
+
+        let mut feeds = FxHashSet::default();
+        for driver in self.drivers.iter().flatten() {
+            for input in &driver.inputs {
+                if let Operand::Wire(j) = input {
+                    feeds.insert((*j, driver.gate));
+                }
+            }
+        }
+
+        let mut faults = vec![];
+        for (i, name) in self.names.iter().enumerate() {
+            let Some(driver) = &self.drivers[i] else {
+                continue;
+            };
+            let inputs = driver
+                .inputs
+                .iter()
+                .filter_map(|input| match input {
+                    Operand::Wire(j) => Some(self.names[*j].as_str()),
+                    Operand::Const(_) => None,
+                })
+                .collect::<Vec<_>>();
+            let first_bit = inputs
+                .iter()
+                .any(|x| bit(x, a) == Some(0) || bit(x, b) == Some(0));
+            let from_inputs = inputs.iter().all(|x| is_input(x));
+
+            let faulty = match (driver.gate, bit(name, sum)) {
+                (Gate::Or, Some(k)) => Some(k) != top,
+                (Gate::Xor, Some(k)) => from_inputs && k != 0,
+                (_, Some(_)) => true,
+                (Gate::Xor, None) => !from_inputs || !feeds.contains(&(i, Gate::Xor)),
+                (Gate::And, None) => !first_bit && !feeds.contains(&(i, Gate::Or)),
+                (Gate::Or, None) => feeds.contains(&(i, Gate::Or)),
+                _ => false,
+            };
+            if faulty {
+                faults.push(name.as_str());
+            }
+        }
+
+        faults.sort_unstable();
+        faults
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static ADDER: &str = "x00 XOR y00 -> z00
+x00 AND y00 -> c00
+x01 XOR y01 -> s01
+x01 AND y01 -> a01
+s01 XOR c00 -> z01
+s01 AND c00 -> b01
+a01 OR b01 -> c01
+x02 XOR y02 -> s02
+x02 AND y02 -> a02
+s02 XOR c01 -> z02
+s02 AND c01 -> b02
+a02 OR b02 -> z03";
+
+    #[test]
+    fn test_signals() {
+        let netlist = "123 -> x
+NOT x -> h
+456 -> y
+x AND y -> d
+x OR y -> e
+x LSHIFT 2 -> f
+y RSHIFT 2 -> g
+NOT y -> i";
+        let mut circuit = Circuit::parse(netlist, 16).unwrap();
+        assert_eq!(circuit.signal("d"), Some(72));
+        assert_eq!(circuit.signal("h"), Some(65412));
+        assert_eq!(circuit.signal("nope"), None);
+
+        circuit.set("x", 1);
+        assert_eq!(circuit.signal("f"), Some(4));
+        circuit.unset("x");
+        assert_eq!(circuit.signal("f"), Some(492));
+
+        circuit.evaluate().unwrap();
+        assert_eq!(circuit.signal("i"), Some(65079));
+        assert!(Circuit::parse("x NAND y -> z", 16).is_err());
+        assert!(Circuit::new(0).is_err());
+        assert!(Circuit::new(65).is_err());
+        assert_eq!(
+            Circuit::parse("NOT 0 -> a", 64).unwrap().signal("a"),
+            Some(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn test_undriven() {
+        let mut circuit = Circuit::parse("u AND 1 -> a\na -> b\nNOT a -> c", 1).unwrap();
+        assert_eq!(circuit.signal("b"), None);
+        assert_eq!(circuit.signal("c"), None);
+
+        circuit.set("u", 1);
+        assert_eq!(circuit.signal("b"), Some(1));
+        assert_eq!(circuit.signal("c"), Some(0));
+    }
+
+    #[test]
+    fn test_cycles() {
+        let mut circuit = Circuit::parse("a -> b\nb AND c -> d\nd -> a\n1 -> c", 1).unwrap();
+        assert_eq!(circuit.signal("a"), None);
+        assert_eq!(circuit.find_cycle(), Some(vec!["b", "d", "a"]));
+        assert!(circuit.topological_order().is_err());
+
+        circuit.set("b", 1);
+        assert_eq!(circuit.find_cycle(), None);
+        assert_eq!(circuit.topological_order().unwrap(), ["b", "c", "d", "a"]);
+        assert_eq!(circuit.signal("a"), Some(1));
+        assert!(circuit.to_dot().contains("\"b\" -> \"d\" [label=AND];"));
+    }
+
+    #[test]
+    fn test_adder() {
+        let mut circuit = Circuit::parse(ADDER, 1).unwrap();
+        circuit.write_number("x", 5);
+        circuit.write_number("y", 3);
+        assert_eq!(circuit.read_number("z"), Some(8));
+        assert!(circuit.adder_faults("x", "y", "z").is_empty());
+
+        let swapped = ADDER
+            .replace("-> a02", "-> tmp")
+            .replace("-> z02", "-> a02")
+            .replace("-> tmp", "-> z02");
+        let circuit = Circuit::parse(&swapped, 1).unwrap();
+        assert_eq!(circuit.adder_faults("x", "y", "z"), ["a02", "z02"]);
+    }
+}
@@ -2,13 +2,26 @@ use crate::date::get_current_year;
 
 pub const HELP: &str = "\
 USAGE: {} [FLAGS] [OPTIONS] -y <YEAR>
+       {} status [--json] [-y <YEAR>]
+       {} intcode -d <DAY> [--disasm] [--input N,N,...] [--steps N] [--step]
+
+COMMANDS:
+  status        Prints solved / stub / tested / missing days per year
+  intcode       Disassembles or traces the cached Intcode program of a 2019 day
+
+STATUS FLAGS:
+  --json        Prints status as JSON
 
 FLAGS:
   -h, --help    Prints help information
+  -a, --all     Runs solutions for all years
+  --compare     Runs all variants of each day and checks that their answers agree
 
 OPTIONS:
-  -y YEAR       Sets AoC year; use current year if not provided
+  -y YEAR       Sets AoC year; use current year if not provided (all years for status)
   -d DAY        Sets AoC day; if not present - iterate over 1..=25
+  --tag TAG     Runs only solutions tagged with TAG; may be repeated
+  --variant V   Runs variant V of each day instead of the default one
 ";
 
 fn prog() -> Option<String> {
@@ -46,6 +59,10 @@ impl std::fmt::Display for ArgsError {
 pub struct Args {
     pub day: Option<u16>,
     pub year: u16,
+    pub all: bool,
+    pub tags: Vec<String>,
+    pub variant: Option<String>,
+    pub compare: bool,
 }
 
 impl TryFrom<pico_args::Arguments> for Args {
@@ -56,6 +73,14 @@ impl TryFrom<pico_args::Arguments> for Args {
             return Err(ArgsError::Help);
         }
 
+        let all = args.contains(["-a", "--all"]);
+        let compare = args.contains("--compare");
+        let variant = args
+            .opt_value_from_str("--variant")
+            .map_err(|e| ArgsError::Error(format!("{e}")))?;
+        let tags = args
+            .values_from_str("--tag")
+            .map_err(|e| ArgsError::Error(format!("{e}")))?;
         let day = args
             .opt_value_from_str("-d")
             .map_err(|e| ArgsError::Error(format!("{e}")))?;
@@ -63,15 +88,95 @@ impl TryFrom<pico_args::Arguments> for Args {
             .value_from_str("-y")
             .unwrap_or_else(|_| get_current_year());
 
-        if let Some(d) = day {
-            if !(0..=25).contains(&(d as i32)) {
-                return Err(ArgsError::InvalidDay);
-            }
+        if let Some(d) = day
+            && !(0..=25).contains(&(d as i32))
+        {
+            return Err(ArgsError::InvalidDay);
         }
         if year < 2015 {
             return Err(ArgsError::InvalidYear);
         }
 
-        Ok(Args { day, year })
+        Ok(Args {
+            day,
+            year,
+            all,
+            tags,
+            variant,
+            compare,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StatusArgs {
+    pub year: Option<u16>,
+    pub json: bool,
+}
+
+impl TryFrom<pico_args::Arguments> for StatusArgs {
+    type Error = ArgsError;
+
+    fn try_from(mut args: pico_args::Arguments) -> Result<Self, Self::Error> {
+        if args.contains(["-h", "--help"]) {
+            return Err(ArgsError::Help);
+        }
+
+        let json = args.contains("--json");
+        let year: Option<u16> = args
+            .opt_value_from_str("-y")
+            .map_err(|e| ArgsError::Error(format!("{e}")))?;
+
+        if year.is_some_and(|y| y < 2015) {
+            return Err(ArgsError::InvalidYear);
+        }
+
+        Ok(StatusArgs { year, json })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IntcodeArgs {
+    pub day: u16,
+    pub disasm: bool,
+    pub inputs: Vec<i64>,
+    pub steps: Option<usize>,
+    pub interactive: bool,
+}
+
+impl TryFrom<pico_args::Arguments> for IntcodeArgs {
+    type Error = ArgsError;
+
+    fn try_from(mut args: pico_args::Arguments) -> Result<Self, Self::Error> {
+        if args.contains(["-h", "--help"]) {
+            return Err(ArgsError::Help);
+        }
+
+        let disasm = args.contains("--disasm");
+        let interactive = args.contains("--step");
+        let steps = args
+            .opt_value_from_str("--steps")
+            .map_err(|e| ArgsError::Error(format!("{e}")))?;
+        let inputs = args
+            .opt_value_from_fn("--input", |s| {
+                s.split(',').map(|x| x.trim().parse::<i64>()).collect()
+            })
+            .map_err(|e| ArgsError::Error(format!("{e}")))?
+            .unwrap_or_default();
+        let day: u16 = args
+            .value_from_str("-d")
+            .map_err(|e| ArgsError::Error(format!("{e}")))?;
+
+        if !(1..=25).contains(&day) {
+            return Err(ArgsError::InvalidDay);
+        }
+
+        Ok(IntcodeArgs {
+            day,
+            disasm,
+            inputs,
+            steps,
+            interactive,
+        })
     }
 }
@@ -1,15 +1,42 @@
 use rustc_hash::FxHashMap;
 use std::hash::Hash;
+use std::ops::{Add, AddAssign, BitAnd, BitOr, Index, Sub, SubAssign};
 
-#[derive(Debug, Default)]
+/// Multiset counting occurrences of keys, modelled after Python's `collections.Counter`.
+///
+/// Only positive counts are stored: a key whose count drops to zero is removed.
+#[derive(Debug, Clone)]
 pub struct Counter<K> {
     data: FxHashMap<K, usize>,
 }
 
+impl<K> Default for Counter<K> {
+    fn default() -> Self {
+        Self {
+            data: FxHashMap::default(),
+        }
+    }
+}
+
+impl<K> PartialEq for Counter<K>
+where
+    K: Eq + Hash,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<K> Eq for Counter<K> where K: Eq + Hash {}
+
 impl<K> Counter<K>
 where
     K: Eq + Hash,
 {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
     #[inline(always)]
     pub fn keys(&self) -> std::collections::hash_map::Keys<'_, K, usize> {
         self.data.keys()
@@ -20,38 +47,220 @@ where
         self.data.values()
     }
 
+    #[inline(always)]
+    pub fn iter(&self) -> std::collections::hash_map::Iter<'_, K, usize> {
+        self.data.iter()
+    }
+
+    /// Count of `key`, zero if absent.
+    #[inline(always)]
+    pub fn get(&self, key: &K) -> usize {
+        self.data.get(key).copied().unwrap_or(0)
+    }
+
+    /// Number of distinct keys.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Sum of all counts.
+    pub fn total(&self) -> usize {
+        self.data.values().sum()
+    }
+
     #[inline(always)]
     pub fn increment(&mut self, key: K) {
         self.increment_by(key, 1);
     }
 
     pub fn increment_by(&mut self, key: K, value: usize) {
+        if value == 0 {
+            return;
+        }
         self.data
             .entry(key)
             .and_modify(|c| *c += value)
             .or_insert(value);
     }
+
+    #[inline(always)]
+    pub fn decrement(&mut self, key: &K) {
+        self.decrement_by(key, 1);
+    }
+
+    /// Lower the count of `key`, removing it once the count reaches zero.
+    pub fn decrement_by(&mut self, key: &K, value: usize) {
+        if let Some(count) = self.data.get_mut(key) {
+            if *count > value {
+                *count -= value;
+            } else {
+                self.data.remove(key);
+            }
+        }
+    }
+
+    /// Remove `key` and return its count.
+    pub fn remove(&mut self, key: &K) -> Option<usize> {
+        self.data.remove(key)
+    }
+
+    /// The `n` keys with the highest counts, most common first. Ties are in key order.
+    pub fn most_common(&self, n: usize) -> Vec<(&K, usize)>
+    where
+        K: Ord,
+    {
+        let mut items = self.data.iter().map(|(k, &c)| (k, c)).collect::<Vec<_>>();
+        items.sort_unstable_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        items.truncate(n);
+        items
+    }
+
+    /// The `n` keys with the lowest counts, least common first. Ties are in key order.
+    pub fn least_common(&self, n: usize) -> Vec<(&K, usize)>
+    where
+        K: Ord,
+    {
+        let mut items = self.data.iter().map(|(k, &c)| (k, c)).collect::<Vec<_>>();
+        items.sort_unstable_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(b.0)));
+        items.truncate(n);
+        items
+    }
+}
+
+impl<K> Index<&K> for Counter<K>
+where
+    K: Eq + Hash,
+{
+    type Output = usize;
+
+    /// Count of `key`, zero if absent.
+    fn index(&self, key: &K) -> &usize {
+        self.data.get(key).unwrap_or(&0)
+    }
+}
+
+impl<K> Extend<K> for Counter<K>
+where
+    K: Eq + Hash,
+{
+    fn extend<T: IntoIterator<Item = K>>(&mut self, iter: T) {
+        for key in iter {
+            self.increment(key);
+        }
+    }
+}
+
+impl<K> Extend<(K, usize)> for Counter<K>
+where
+    K: Eq + Hash,
+{
+    fn extend<T: IntoIterator<Item = (K, usize)>>(&mut self, iter: T) {
+        for (key, count) in iter {
+            self.increment_by(key, count);
+        }
+    }
 }
 
 impl<K> FromIterator<K> for Counter<K>
 where
-    K: Default + Eq + Hash,
+    K: Eq + Hash,
 {
     fn from_iter<T: IntoIterator<Item = K>>(iter: T) -> Self {
-        iter.into_iter().map(|elem| (elem, 1)).collect()
+        let mut counter = Self::default();
+        counter.extend(iter);
+        counter
     }
 }
 
 impl<K> FromIterator<(K, usize)> for Counter<K>
 where
-    K: Default + Eq + Hash,
+    K: Eq + Hash,
 {
     fn from_iter<T: IntoIterator<Item = (K, usize)>>(iter: T) -> Self {
-        iter.into_iter()
-            .fold(Self::default(), |mut counter, (elem, count)| {
-                counter.increment_by(elem, count);
-                counter
-            })
+        let mut counter = Self::default();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<K> AddAssign for Counter<K>
+where
+    K: Eq + Hash,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        self.extend(rhs);
+    }
+}
+
+impl<K> SubAssign for Counter<K>
+where
+    K: Eq + Hash,
+{
+    /// Subtract counts, dropping keys which would not stay positive.
+    fn sub_assign(&mut self, rhs: Self) {
+        for (key, count) in rhs {
+            self.decrement_by(&key, count);
+        }
+    }
+}
+
+impl<K> Add for Counter<K>
+where
+    K: Eq + Hash,
+{
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self {
+        self += rhs;
+        self
+    }
+}
+
+impl<K> Sub for Counter<K>
+where
+    K: Eq + Hash,
+{
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self {
+        self -= rhs;
+        self
+    }
+}
+
+impl<K> BitOr for Counter<K>
+where
+    K: Eq + Hash,
+{
+    type Output = Self;
+
+    /// Union: the maximum of both counts.
+    fn bitor(mut self, rhs: Self) -> Self {
+        for (key, count) in rhs {
+            let entry = self.data.entry(key).or_insert(0);
+            *entry = (*entry).max(count);
+        }
+        self
+    }
+}
+
+impl<K> BitAnd for Counter<K>
+where
+    K: Eq + Hash,
+{
+    type Output = Self;
+
+    /// Intersection: the minimum of both counts.
+    fn bitand(mut self, rhs: Self) -> Self {
+        self.data.retain(|key, count| {
+            *count = (*count).min(rhs.get(key));
+            *count > 0
+        });
+        self
     }
 }
 
@@ -73,3 +282,46 @@ impl<'a, K> IntoIterator for &'a Counter<K> {
         self.data.iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts() {
+        let mut counter = "abracadabra".chars().collect::<Counter<_>>();
+        assert_eq!(counter.get(&'a'), 5);
+        assert_eq!(counter[&'z'], 0);
+        assert_eq!(counter.len(), 5);
+        assert_eq!(counter.total(), 11);
+        assert_eq!(counter.most_common(1), [(&'a', 5)]);
+        assert_eq!(counter.most_common(3), [(&'a', 5), (&'b', 2), (&'r', 2)]);
+        assert_eq!(counter.least_common(2), [(&'c', 1), (&'d', 1)]);
+
+        counter.decrement(&'c');
+        counter.decrement_by(&'a', 2);
+        counter.extend("zz".chars());
+        assert_eq!(counter.get(&'c'), 0);
+        assert!(!counter.keys().any(|&k| k == 'c'));
+        assert_eq!(counter[&'a'], 3);
+        assert_eq!(counter.remove(&'z'), Some(2));
+        assert_eq!(counter.total(), 8);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a: Counter<char> = Counter::from_iter([('x', 3), ('y', 1)]);
+        let b: Counter<char> = Counter::from_iter([('x', 1), ('y', 2), ('z', 1)]);
+
+        assert_eq!(
+            a.clone() + b.clone(),
+            Counter::from_iter([('x', 4), ('y', 3), ('z', 1)])
+        );
+        assert_eq!(a.clone() - b.clone(), Counter::from_iter([('x', 2)]));
+        assert_eq!(
+            a.clone() | b.clone(),
+            Counter::from_iter([('x', 3), ('y', 2), ('z', 1)])
+        );
+        assert_eq!(a & b, Counter::from_iter([('x', 1), ('y', 1)]));
+    }
+}
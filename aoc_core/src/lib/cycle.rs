@@ -0,0 +1,213 @@
+//! Cycle detection for iterated functions `x, f(x), f(f(x)), ...`, used to skip ahead
+//! to iteration counts far too large to simulate.
+//!
+//! [Floyd] and [Brent] only need `PartialEq` and constant memory. The hashed variants
+//! remember every state, which lets them also return the history needed to look up the
+//! state, or a value derived from it, at any iteration.
+//!
+//! [Floyd]: https://en.wikipedia.org/wiki/Cycle_detection#Floyd's_tortoise_and_hare
+//! [Brent]: https://en.wikipedia.org/wiki/Cycle_detection#Brent's_algorithm
+use crate::num::Integer;
+use rustc_hash::FxHashMap;
+use std::collections::hash_map::Entry;
+use std::hash::Hash;
+
+/// A sequence which, after `start` iterations, repeats every `period` iterations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The earliest iteration with the same state as iteration `n`.
+    pub fn index(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+
+    /// Value at iteration `n` of a quantity which grows by the same amount every period,
+    /// e.g. a height or a score. `values[i]` is the value at iteration `i` and must be
+    /// known at least up to `start + period`. Periodic values simply grow by zero.
+    pub fn extrapolate<T>(&self, n: usize, values: &[T]) -> T
+    where
+        T: Integer + TryFrom<usize>,
+    {
+        if n < self.start {
+            return values[n];
+        }
+
+        let growth = values[self.start + self.period] - values[self.start];
+        let periods = T::try_from((n - self.start) / self.period)
+            .unwrap_or_else(|_| panic!("Number of periods does not fit the value type"));
+        values[self.index(n)] + growth * periods
+    }
+}
+
+/// Floyd's tortoise and hare.
+pub fn floyd<S: Clone + PartialEq>(init: &S, mut f: impl FnMut(&S) -> S) -> Cycle {
+    let mut tortoise = f(init);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        let next = f(&hare);
+        hare = f(&next);
+    }
+
+    let mut start = 0;
+    tortoise = init.clone();
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    let mut period = 1;
+    hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        period += 1;
+    }
+
+    Cycle { start, period }
+}
+
+/// Brent's algorithm, usually needing fewer evaluations of `f` than [`floyd`].
+pub fn brent<S: Clone + PartialEq>(init: &S, mut f: impl FnMut(&S) -> S) -> Cycle {
+    let (mut power, mut period) = (1, 1);
+    let mut tortoise = init.clone();
+    let mut hare = f(init);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = f(&hare);
+        period += 1;
+    }
+
+    tortoise = init.clone();
+    hare = init.clone();
+    for _ in 0..period {
+        hare = f(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    Cycle { start, period }
+}
+
+/// Hashed cycle detection. Returns the cycle and all states from iteration 0 up to and
+/// including the first repeated one, i.e. `start + period + 1` states.
+///
+/// Never returns if the sequence does not cycle.
+pub fn find_cycle<S>(init: S, f: impl FnMut(&S) -> S) -> (Cycle, Vec<S>)
+where
+    S: Clone + Hash + Eq,
+{
+    find_cycle_by_key(init, f, S::clone)
+}
+
+/// Like [`find_cycle`] but compares states by `key`, e.g. a fingerprint of the part of
+/// a larger state which determines its future. States with equal keys are assumed to be
+/// equivalent.
+pub fn find_cycle_by_key<S, K>(
+    init: S,
+    mut f: impl FnMut(&S) -> S,
+    mut key: impl FnMut(&S) -> K,
+) -> (Cycle, Vec<S>)
+where
+    K: Hash + Eq,
+{
+    let mut seen = FxHashMap::default();
+    let mut history = vec![init];
+
+    loop {
+        let state = history.last().unwrap();
+        match seen.entry(key(state)) {
+            Entry::Occupied(entry) => {
+                let start = *entry.get();
+                let period = history.len() - 1 - start;
+                return (Cycle { start, period }, history);
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(history.len() - 1);
+            }
+        }
+        history.push(f(state));
+    }
+}
+
+/// State after `n` iterations of `f`, simulating at most until the first repeat.
+pub fn nth<S>(init: S, f: impl FnMut(&S) -> S, n: usize) -> S
+where
+    S: Clone + Hash + Eq,
+{
+    let (cycle, mut history) = find_cycle(init, f);
+    history.swap_remove(cycle.index(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 2: a prefix of 2 and a period of 4
+    fn step(x: &u32) -> u32 {
+        if *x == 5 { 2 } else { x + 1 }
+    }
+
+    #[test]
+    fn test_detection() {
+        let expected = Cycle {
+            start: 2,
+            period: 4,
+        };
+
+        assert_eq!(floyd(&0, step), expected);
+        assert_eq!(brent(&0, step), expected);
+
+        let (cycle, history) = find_cycle(0, step);
+        assert_eq!(cycle, expected);
+        assert_eq!(history, vec![0, 1, 2, 3, 4, 5, 2]);
+
+        // Already on the cycle
+        let cycle = brent(&3, step);
+        assert_eq!((cycle.start, cycle.period), (0, 4));
+    }
+
+    #[test]
+    fn test_skip_ahead() {
+        assert_eq!(nth(0, step, 1), 1);
+        assert_eq!(nth(0, step, 6), 2);
+        assert_eq!(nth(0, step, 1_000_000_000), 4);
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        // Every state adds its value to a running total
+        let (cycle, history) = find_cycle_by_key(
+            (0u32, 0u64),
+            |&(x, sum)| (step(&x), sum + x as u64),
+            |s| s.0,
+        );
+        let sums = history.iter().map(|s| s.1).collect::<Vec<_>>();
+
+        let simulate = |n| {
+            (0..n)
+                .fold((0, 0), |(x, sum), _| (step(&x), sum + x as u64))
+                .1
+        };
+        for n in [0, 1, 5, 17, 1234] {
+            assert_eq!(cycle.extrapolate(n, &sums), simulate(n));
+        }
+    }
+}
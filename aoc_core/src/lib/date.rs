@@ -16,3 +16,12 @@ pub fn get_current_year() -> u16 {
 
     (yoe + era * 400) as u16
 }
+
+/// Latest Advent of Code event. A fixed bound keeps builds independent of the clock;
+/// bump it when a new event starts.
+pub const LAST_YEAR: u16 = 2025;
+
+/// Number of puzzles released in a given year (AoC was shortened to 12 days in 2025).
+pub fn days_in(year: u16) -> u16 {
+    if year >= 2025 { 12 } else { 25 }
+}
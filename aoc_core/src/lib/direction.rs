@@ -0,0 +1,143 @@
+//! The four grid directions. Unit vectors use screen coordinates: `x` grows to the
+//! right and `y` grows downwards, so `Up` is `(0, -1)`.
+use crate::num::Signed;
+use crate::point::Point2;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions in clockwise order starting from `Up`.
+    pub const ALL: [Direction; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// Turn 90° clockwise.
+    pub fn turn_right(self) -> Self {
+        match self {
+            Self::Up => Self::Right,
+            Self::Right => Self::Down,
+            Self::Down => Self::Left,
+            Self::Left => Self::Up,
+        }
+    }
+
+    /// Turn 90° counterclockwise.
+    pub fn turn_left(self) -> Self {
+        match self {
+            Self::Up => Self::Left,
+            Self::Left => Self::Down,
+            Self::Down => Self::Right,
+            Self::Right => Self::Up,
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::Right => Self::Left,
+            Self::Down => Self::Up,
+            Self::Left => Self::Right,
+        }
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Self::Up | Self::Down)
+    }
+
+    /// Unit vector pointing in this direction.
+    pub fn unit<T: Signed>(self) -> Point2<T> {
+        let (zero, one) = (T::ZERO, T::ONE);
+        match self {
+            Self::Up => Point2::new(zero, -one),
+            Self::Right => Point2::new(one, zero),
+            Self::Down => Point2::new(zero, one),
+            Self::Left => Point2::new(-one, zero),
+        }
+    }
+
+    /// Offset `(dx, dy)` usable with [`crate::grid::Grid::step`].
+    pub fn offset(self) -> (isize, isize) {
+        self.unit::<isize>().into()
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = char;
+
+    /// Parse arrows (`^v<>`), letters (`UDLR`) or compass points (`NSEW`).
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '^' | 'U' | 'N' => Ok(Self::Up),
+            '>' | 'R' | 'E' => Ok(Self::Right),
+            'v' | 'D' | 'S' => Ok(Self::Down),
+            '<' | 'L' | 'W' => Ok(Self::Left),
+            _ => Err(value),
+        }
+    }
+}
+
+impl TryFrom<u8> for Direction {
+    type Error = char;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::try_from(value as char)
+    }
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.trim().chars();
+        match (chars.next(), chars.next()) {
+            (Some(ch), None) => Self::try_from(ch).map_err(|ch| format!("Invalid direction: {ch}")),
+            _ => Err(format!("Invalid direction: {s}")),
+        }
+    }
+}
+
+impl<T: Signed> From<Direction> for Point2<T> {
+    fn from(dir: Direction) -> Self {
+        dir.unit()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.reverse());
+            assert_eq!(dir.reverse().reverse(), dir);
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Direction::try_from('^'), Ok(Direction::Up));
+        assert_eq!(Direction::try_from(b'v'), Ok(Direction::Down));
+        assert_eq!("L".parse::<Direction>(), Ok(Direction::Left));
+        assert_eq!("E".parse::<Direction>(), Ok(Direction::Right));
+        assert!("X".parse::<Direction>().is_err());
+        assert!("UD".parse::<Direction>().is_err());
+    }
+
+    #[test]
+    fn test_unit() {
+        let p = Point2::new(5, 5);
+        assert_eq!(p + Direction::Up.unit(), Point2::new(5, 4));
+        assert_eq!(p + Direction::Left.into(), Point2::new(4, 5));
+        assert_eq!(Direction::Down.offset(), (0, 1));
+        assert!(Direction::Down.is_vertical());
+    }
+}
@@ -0,0 +1,250 @@
+//! [Disjoint-set] (union-find) forest with path compression and union by size, for
+//! growing connected components one edge at a time.
+//!
+//! [`DisjointSet`] works on dense `usize` indices; [`KeyedDisjointSet`] maps arbitrary
+//! hashable keys to indices as they are first seen.
+//!
+//! [Disjoint-set]: https://en.wikipedia.org/wiki/Disjoint-set_data_structure
+use rustc_hash::FxHashMap;
+use std::hash::Hash;
+
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl DisjointSet {
+    /// `n` singleton sets `0..n`.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            components: n,
+        }
+    }
+
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Add a new singleton set and return its index.
+    pub fn push(&mut self) -> usize {
+        let x = self.parent.len();
+        self.parent.push(x);
+        self.size.push(1);
+        self.components += 1;
+        x
+    }
+
+    /// Representative of the set containing `x`.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        let mut x = x;
+        while self.parent[x] != root {
+            (x, self.parent[x]) = (self.parent[x], root);
+        }
+        root
+    }
+
+    /// Merge the sets containing `a` and `b`. Returns `false` if they already were one.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.size[a] < self.size[b] {
+            (a, b) = (b, a);
+        }
+
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.components -= 1;
+        true
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Size of the set containing `x`.
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Number of disjoint sets.
+    pub fn components(&self) -> usize {
+        self.components
+    }
+
+    /// Sizes of all sets, in no particular order.
+    pub fn sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len())
+            .filter(|&x| self.parent[x] == x)
+            .map(|x| self.size[x])
+    }
+
+    /// Members of every set, ordered by their smallest element.
+    pub fn groups(&mut self) -> impl Iterator<Item = Vec<usize>> + use<> {
+        let mut index = FxHashMap::default();
+        let mut groups: Vec<Vec<usize>> = vec![];
+
+        for x in 0..self.len() {
+            let root = self.find(x);
+            let i = *index.entry(root).or_insert_with(|| {
+                groups.push(vec![]);
+                groups.len() - 1
+            });
+            groups[i].push(x);
+        }
+
+        groups.into_iter()
+    }
+}
+
+/// A [`DisjointSet`] over hashable keys, which are added on first use.
+#[derive(Debug, Clone)]
+pub struct KeyedDisjointSet<K> {
+    index: FxHashMap<K, usize>,
+    keys: Vec<K>,
+    set: DisjointSet,
+}
+
+impl<K> Default for KeyedDisjointSet<K> {
+    fn default() -> Self {
+        Self {
+            index: FxHashMap::default(),
+            keys: vec![],
+            set: DisjointSet::new(0),
+        }
+    }
+}
+
+impl<K: Clone + Hash + Eq> KeyedDisjointSet<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Index of `key`, adding it as a singleton set if unseen.
+    pub fn insert(&mut self, key: K) -> usize {
+        if let Some(&i) = self.index.get(&key) {
+            return i;
+        }
+        let i = self.set.push();
+        self.index.insert(key.clone(), i);
+        self.keys.push(key);
+        i
+    }
+
+    /// Representative key of the set containing `key`, if it was added.
+    pub fn find(&mut self, key: &K) -> Option<&K> {
+        let i = *self.index.get(key)?;
+        let root = self.set.find(i);
+        Some(&self.keys[root])
+    }
+
+    /// Merge the sets containing `a` and `b`, adding unseen keys first. Returns `false` if
+    /// they already were one.
+    pub fn union(&mut self, a: K, b: K) -> bool {
+        let (a, b) = (self.insert(a), self.insert(b));
+        self.set.union(a, b)
+    }
+
+    pub fn same(&mut self, a: &K, b: &K) -> bool {
+        match (self.index.get(a), self.index.get(b)) {
+            (Some(&a), Some(&b)) => self.set.same(a, b),
+            _ => a == b,
+        }
+    }
+
+    /// Size of the set containing `key`, zero if it was never added.
+    pub fn size(&mut self, key: &K) -> usize {
+        self.index.get(key).map_or(0, |&i| self.set.size(i))
+    }
+
+    pub fn components(&self) -> usize {
+        self.set.components()
+    }
+
+    pub fn sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.set.sizes()
+    }
+
+    /// Members of every set, in insertion order of their first key.
+    pub fn groups(&mut self) -> impl Iterator<Item = Vec<K>> + '_ {
+        let keys = &self.keys;
+        self.set
+            .groups()
+            .map(move |group| group.into_iter().map(|i| keys[i].clone()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_find() {
+        let mut dsu = DisjointSet::new(6);
+        assert_eq!(dsu.components(), 6);
+
+        assert!(dsu.union(0, 1));
+        assert!(dsu.union(2, 3));
+        assert!(dsu.union(1, 3));
+        assert!(!dsu.union(0, 2));
+
+        assert!(dsu.same(0, 3));
+        assert!(!dsu.same(0, 4));
+        assert_eq!(dsu.size(2), 4);
+        assert_eq!(dsu.components(), 3);
+
+        let mut sizes = dsu.sizes().collect::<Vec<_>>();
+        sizes.sort();
+        assert_eq!(sizes, [1, 1, 4]);
+        assert_eq!(
+            dsu.groups().collect::<Vec<_>>(),
+            [vec![0, 1, 2, 3], vec![4], vec![5]]
+        );
+    }
+
+    #[test]
+    fn test_keyed() {
+        let mut dsu = KeyedDisjointSet::new();
+        dsu.union("a", "b");
+        dsu.union("c", "d");
+        dsu.insert("e");
+        dsu.union("b", "a");
+
+        assert_eq!(dsu.len(), 5);
+        assert_eq!(dsu.components(), 3);
+        assert!(dsu.same(&"a", &"b"));
+        assert!(!dsu.same(&"a", &"c"));
+        assert!(!dsu.same(&"a", &"z"));
+        assert_eq!(dsu.size(&"d"), 2);
+        assert_eq!(dsu.size(&"z"), 0);
+        assert_eq!(dsu.find(&"z"), None);
+        assert_eq!(
+            dsu.groups().collect::<Vec<_>>(),
+            [vec!["a", "b"], vec!["c", "d"], vec!["e"]]
+        );
+    }
+}
@@ -0,0 +1,428 @@
+//! Expression parsing with operators configured at runtime (2020/18, 2022/21).
+//!
+//! A [`Grammar`] lists the prefix and infix operators with their binding powers and
+//! semantics, and parses text into an [`Expr`] with a [Pratt parser]. Operators are
+//! either symbolic (`+`, `<<`) or keywords (`AND`); operands are unsigned numbers,
+//! identifiers and parenthesised sub-expressions.
+//!
+//! [Pratt parser]: https://matklad.github.io/2020/04/13/simple-but-powerful-pratt-parsing.html
+use crate::num::Integer;
+use rustc_hash::FxHashMap;
+use std::fmt;
+use std::iter::Peekable;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token<'a> {
+    Number(&'a str),
+    Ident(&'a str),
+    Op(&'a str),
+    LParen,
+    RParen,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Assoc {
+    Left,
+    Right,
+}
+
+#[derive(Clone, Copy)]
+struct Infix<T> {
+    power: u8,
+    assoc: Assoc,
+    apply: fn(T, T) -> T,
+}
+
+#[derive(Clone, Copy)]
+struct Prefix<T> {
+    power: u8,
+    apply: fn(T) -> T,
+}
+
+/// Operator table: binding powers, associativity and semantics of every operator.
+/// A higher power binds tighter.
+#[derive(Clone)]
+pub struct Grammar<T> {
+    infix: FxHashMap<String, Infix<T>>,
+    prefix: FxHashMap<String, Prefix<T>>,
+}
+
+impl<T> Default for Grammar<T> {
+    fn default() -> Self {
+        Self {
+            infix: FxHashMap::default(),
+            prefix: FxHashMap::default(),
+        }
+    }
+}
+
+impl<T> Grammar<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a left-associative binary operator.
+    pub fn infix(self, symbol: &str, power: u8, apply: fn(T, T) -> T) -> Self {
+        self.infix_with(symbol, power, Assoc::Left, apply)
+    }
+
+    pub fn infix_with(
+        mut self,
+        symbol: &str,
+        power: u8,
+        assoc: Assoc,
+        apply: fn(T, T) -> T,
+    ) -> Self {
+        let op = Infix {
+            power,
+            assoc,
+            apply,
+        };
+        self.infix.insert(symbol.to_string(), op);
+        self
+    }
+
+    /// Add a unary operator written before its operand.
+    pub fn prefix(mut self, symbol: &str, power: u8, apply: fn(T) -> T) -> Self {
+        self.prefix
+            .insert(symbol.to_string(), Prefix { power, apply });
+        self
+    }
+
+    fn is_operator(&self, symbol: &str) -> bool {
+        self.infix.contains_key(symbol) || self.prefix.contains_key(symbol)
+    }
+
+    /// Split `source` into tokens. Symbolic operators are matched longest first.
+    pub fn tokenize<'a>(&self, source: &'a str) -> Result<Vec<Token<'a>>, String> {
+        let symbols = self
+            .infix
+            .keys()
+            .chain(self.prefix.keys())
+            .filter(|s| !s.starts_with(|c: char| c.is_alphanumeric() || c == '_'))
+            .collect::<Vec<_>>();
+
+        let mut tokens = vec![];
+        let mut rest = source.trim_start();
+        while let Some(c) = rest.chars().next() {
+            let word = |rest: &'a str, f: fn(char) -> bool| {
+                let end = rest.find(|c: char| !f(c)).unwrap_or(rest.len());
+                rest.split_at(end)
+            };
+
+            let (token, tail) = match c {
+                '(' => (Token::LParen, &rest[1..]),
+                ')' => (Token::RParen, &rest[1..]),
+                '0'..='9' => {
+                    let (number, tail) = word(rest, |c| c.is_ascii_digit());
+                    (Token::Number(number), tail)
+                }
+                c if c.is_alphabetic() || c == '_' => {
+                    let (ident, tail) = word(rest, |c| c.is_alphanumeric() || c == '_');
+                    if self.is_operator(ident) {
+                        (Token::Op(ident), tail)
+                    } else {
+                        (Token::Ident(ident), tail)
+                    }
+                }
+                _ => {
+                    let symbol = symbols
+                        .iter()
+                        .filter(|s| rest.starts_with(s.as_str()))
+                        .max_by_key(|s| s.len())
+                        .ok_or(format!("Unexpected character: {c}"))?;
+                    let (op, tail) = rest.split_at(symbol.len());
+                    (Token::Op(op), tail)
+                }
+            };
+
+            tokens.push(token);
+            rest = tail.trim_start();
+        }
+
+        Ok(tokens)
+    }
+
+    pub fn parse(&self, source: &str) -> Result<Expr<T>, String>
+    where
+        T: FromStr,
+    {
+        let mut tokens = self.tokenize(source)?.into_iter().peekable();
+        let expr = self.parse_expr(&mut tokens, 0)?;
+        match tokens.next() {
+            None => Ok(expr),
+            Some(token) => Err(format!("Unexpected token: {token:?}")),
+        }
+    }
+
+    fn parse_expr<'a, I>(&self, tokens: &mut Peekable<I>, min_power: u8) -> Result<Expr<T>, String>
+    where
+        I: Iterator<Item = Token<'a>>,
+        T: FromStr,
+    {
+        let mut lhs = match tokens.next() {
+            Some(Token::Number(number)) => number
+                .parse()
+                .map(Expr::Num)
+                .map_err(|_| format!("Invalid number: {number}"))?,
+            Some(Token::Ident(name)) => Expr::Var(name.to_string()),
+            Some(Token::LParen) => {
+                let expr = self.parse_expr(tokens, 0)?;
+                if tokens.next() != Some(Token::RParen) {
+                    return Err("Expected a closing parenthesis".to_string());
+                }
+                expr
+            }
+            Some(Token::Op(op)) if self.prefix.contains_key(op) => {
+                let power = 2 * self.prefix[op].power + 1;
+                let operand = self.parse_expr(tokens, power)?;
+                Expr::Unary(op.to_string(), Box::new(operand))
+            }
+            Some(token) => return Err(format!("Unexpected token: {token:?}")),
+            None => return Err("Unexpected end of input".to_string()),
+        };
+
+        while let Some(&Token::Op(op)) = tokens.peek() {
+            let infix = self
+                .infix
+                .get(op)
+                .ok_or(format!("Not a binary operator: {op}"))?;
+            let (left, right) = match infix.assoc {
+                Assoc::Left => (2 * infix.power, 2 * infix.power + 1),
+                Assoc::Right => (2 * infix.power + 1, 2 * infix.power),
+            };
+            if left < min_power {
+                break;
+            }
+
+            tokens.next();
+            let rhs = self.parse_expr(tokens, right)?;
+            lhs = Expr::Binary(op.to_string(), Box::new(lhs), Box::new(rhs));
+        }
+
+        Ok(lhs)
+    }
+}
+
+/// Syntax tree of an expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr<T> {
+    Num(T),
+    Var(String),
+    Unary(String, Box<Expr<T>>),
+    Binary(String, Box<Expr<T>>, Box<Expr<T>>),
+}
+
+impl<T: Copy> Expr<T> {
+    pub fn as_num(&self) -> Option<T> {
+        match self {
+            Expr::Num(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Value of the expression, or `None` if `env` does not know a variable.
+    pub fn eval<F>(&self, grammar: &Grammar<T>, env: &mut F) -> Option<T>
+    where
+        F: FnMut(&str) -> Option<T>,
+    {
+        match self {
+            Expr::Num(value) => Some(*value),
+            Expr::Var(name) => env(name),
+            Expr::Unary(op, operand) => {
+                let value = operand.eval(grammar, env)?;
+                Some((grammar.prefix[op].apply)(value))
+            }
+            Expr::Binary(op, lhs, rhs) => {
+                let (lhs, rhs) = (lhs.eval(grammar, env)?, rhs.eval(grammar, env)?);
+                Some((grammar.infix[op].apply)(lhs, rhs))
+            }
+        }
+    }
+
+    /// Fold every sub-expression whose variables are all known to `env` into a number.
+    pub fn partial_eval<F>(&self, grammar: &Grammar<T>, env: &mut F) -> Expr<T>
+    where
+        F: FnMut(&str) -> Option<T>,
+    {
+        match self {
+            Expr::Num(_) => self.clone(),
+            Expr::Var(name) => env(name).map_or_else(|| self.clone(), Expr::Num),
+            Expr::Unary(op, operand) => match operand.partial_eval(grammar, env) {
+                Expr::Num(value) => Expr::Num((grammar.prefix[op].apply)(value)),
+                operand => Expr::Unary(op.clone(), Box::new(operand)),
+            },
+            Expr::Binary(op, lhs, rhs) => {
+                match (
+                    lhs.partial_eval(grammar, env),
+                    rhs.partial_eval(grammar, env),
+                ) {
+                    (Expr::Num(a), Expr::Num(b)) => Expr::Num((grammar.infix[op].apply)(a, b)),
+                    (lhs, rhs) => Expr::Binary(op.clone(), Box::new(lhs), Box::new(rhs)),
+                }
+            }
+        }
+    }
+
+    /// Replace variables by the expressions `f` returns for them, which are substituted in
+    /// turn, so `f` must not define a variable in terms of itself.
+    pub fn substitute<F>(&self, f: &mut F) -> Expr<T>
+    where
+        F: FnMut(&str) -> Option<Expr<T>>,
+    {
+        match self {
+            Expr::Num(_) => self.clone(),
+            Expr::Var(name) => match f(name) {
+                Some(expr) => expr.substitute(f),
+                None => self.clone(),
+            },
+            Expr::Unary(op, operand) => Expr::Unary(op.clone(), Box::new(operand.substitute(f))),
+            Expr::Binary(op, lhs, rhs) => Expr::Binary(
+                op.clone(),
+                Box::new(lhs.substitute(f)),
+                Box::new(rhs.substitute(f)),
+            ),
+        }
+    }
+}
+
+impl<T: Integer> Expr<T> {
+    /// Value of `unknown` for which the expression equals `target`.
+    ///
+    /// The expression must be partially evaluated so that `unknown` is its only variable
+    /// and occurs once, and may only use `+`, `-`, `*` and `/` with their usual meaning.
+    /// `None` if there is no integer solution, e.g. for `2 * x = 7` or `x * 0 = 1`.
+    pub fn solve(&self, unknown: &str, target: T) -> Option<T> {
+        let divide = |a: T, b: T| (b != T::ZERO && a % b == T::ZERO).then(|| a / b);
+        match self {
+            Expr::Var(name) if name == unknown => Some(target),
+            Expr::Binary(op, lhs, rhs) => match (lhs.as_num(), op.as_str(), rhs.as_num()) {
+                (Some(a), "+", None) => rhs.solve(unknown, target.checked_sub(a)?),
+                (Some(a), "-", None) => rhs.solve(unknown, a.checked_sub(target)?),
+                (Some(a), "*", None) => rhs.solve(unknown, divide(target, a)?),
+                (Some(a), "/", None) => rhs.solve(unknown, divide(a, target)?),
+                (None, "+", Some(b)) => lhs.solve(unknown, target.checked_sub(b)?),
+                (None, "-", Some(b)) => lhs.solve(unknown, target.checked_add(b)?),
+                (None, "*", Some(b)) => lhs.solve(unknown, divide(target, b)?),
+                (None, "/", Some(b)) if b != T::ZERO => lhs.solve(unknown, target.checked_mul(b)?),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Expr<T> {
+    /// Infix notation, parenthesising every nested binary expression.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nested = |expr: &Expr<T>, f: &mut fmt::Formatter| match expr {
+            Expr::Binary(..) => write!(f, "({expr})"),
+            _ => write!(f, "{expr}"),
+        };
+
+        match self {
+            Expr::Num(value) => write!(f, "{value}"),
+            Expr::Var(name) => write!(f, "{name}"),
+            Expr::Unary(op, operand) => {
+                let space = if op.ends_with(char::is_alphanumeric) {
+                    " "
+                } else {
+                    ""
+                };
+                write!(f, "{op}{space}")?;
+                nested(operand, f)
+            }
+            Expr::Binary(op, lhs, rhs) => {
+                nested(lhs, f)?;
+                write!(f, " {op} ")?;
+                nested(rhs, f)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arithmetic() -> Grammar<i64> {
+        Grammar::<i64>::new()
+            .infix("+", 1, |a, b| a + b)
+            .infix("-", 1, |a, b| a - b)
+            .infix("*", 2, |a, b| a * b)
+            .infix("/", 2, |a, b| a / b)
+            .infix_with("**", 3, Assoc::Right, |a, b| a.pow(b as u32))
+            .prefix("-", 4, |a| -a)
+    }
+
+    fn eval(source: &str) -> Option<i64> {
+        arithmetic()
+            .parse(source)
+            .ok()?
+            .eval(&arithmetic(), &mut |_| None)
+    }
+
+    #[test]
+    fn test_precedence() {
+        assert_eq!(eval("1 + 2 * 3"), Some(7));
+        assert_eq!(eval("(1 + 2) * 3"), Some(9));
+        assert_eq!(eval("10 - 4 - 3"), Some(3));
+        assert_eq!(eval("2 ** 3 ** 2"), Some(512));
+        assert_eq!(eval("-2 ** 2 - -3"), Some(7));
+
+        let grammar = arithmetic();
+        assert!(grammar.parse("1 +").is_err());
+        assert!(grammar.parse("(1 + 2").is_err());
+        assert!(grammar.parse("1 2").is_err());
+        assert!(grammar.parse("1 $ 2").is_err());
+    }
+
+    #[test]
+    fn test_keywords_and_display() {
+        let grammar: Grammar<u16> = Grammar::new()
+            .infix("AND", 1, |a, b| a & b)
+            .infix("LSHIFT", 1, |a, b| a << b)
+            .prefix("NOT", 2, |a| !a);
+
+        let expr = grammar.parse("NOT x LSHIFT 2 AND y").unwrap();
+        assert_eq!(expr.to_string(), "(NOT x LSHIFT 2) AND y");
+
+        let mut env = |name: &str| (name == "x").then_some(123);
+        assert_eq!(expr.eval(&grammar, &mut env), None);
+        assert_eq!(
+            expr.partial_eval(&grammar, &mut env).to_string(),
+            "65040 AND y"
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        let grammar = arithmetic();
+        let expr = grammar.parse("(4 + 2 * (x - 3)) / 4").unwrap();
+        assert_eq!(expr.to_string(), "(4 + (2 * (x - 3))) / 4");
+
+        let expr = expr.partial_eval(&grammar, &mut |_| None);
+        let x = expr.solve("x", 150).unwrap();
+        assert_eq!(x, 301);
+        assert_eq!(expr.eval(&grammar, &mut |_| Some(x)), Some(150));
+
+        let y = grammar.parse("y").unwrap();
+        let expr = grammar.parse("2 * x + x").unwrap();
+        assert_eq!(
+            expr.substitute(&mut |name| (name == "x").then(|| y.clone()))
+                .to_string(),
+            "(2 * y) + y"
+        );
+        assert_eq!(expr.solve("x", 3), None);
+
+        let solve = |text: &str, target: i64| grammar.parse(text).unwrap().solve("x", target);
+        assert_eq!(solve("2 * x", 8), Some(4));
+        assert_eq!(solve("2 * x", 7), None);
+        assert_eq!(solve("x * 0", 1), None);
+        assert_eq!(solve("12 / x", 4), Some(3));
+        assert_eq!(solve("12 / x", 5), None);
+        assert_eq!(solve("12 / x", 0), None);
+        assert_eq!(solve("x / 0", 1), None);
+    }
+}
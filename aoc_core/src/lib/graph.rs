@@ -1,43 +1,622 @@
-use crate::num::Unsigned;
-use petgraph::graph::{Graph, NodeIndex};
+//! Graphs of grid cells and algorithms petgraph lacks.
+//!
+//! [`GridGraph`] turns a [`Grid`] into a petgraph [`Graph`], and [`contract_corridors`]
+//! shrinks it to the junctions of its corridors. The algorithms work on any petgraph
+//! graph through its visit traits; [`from_adjacency`] turns a plain adjacency map into a
+//! [`GraphMap`] for them.
+use crate::grid::{DIRS4, DIRS8, Grid, Pos};
+use crate::num::Integer;
 use petgraph::EdgeType;
-use std::str::FromStr;
+use petgraph::graph::{Graph, NodeIndex};
+use petgraph::graphmap::{GraphMap, NodeTrait};
+use petgraph::visit::{
+    EdgeRef, GraphProp, IntoEdgeReferences, IntoEdges, IntoNeighbors, IntoNodeIdentifiers,
+    NodeIndexable,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::BuildHasher;
+
+/// Which cells of a grid are adjacent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbors {
+    /// Orthogonal neighbours.
+    Four,
+    /// Orthogonal and diagonal neighbours.
+    Eight,
+}
+
+impl Neighbors {
+    fn offsets(&self) -> &'static [(isize, isize)] {
+        match self {
+            Neighbors::Four => &DIRS4,
+            Neighbors::Eight => &DIRS8,
+        }
+    }
+}
+
+/// A graph with a node for every open cell of a grid and edges between adjacent open
+/// cells, together with the mapping between positions and nodes.
+///
+/// Directed graphs get an edge in each direction, undirected ones a single edge.
+#[derive(Debug, Clone)]
+pub struct GridGraph<N, E, Ty: EdgeType> {
+    graph: Graph<N, E, Ty>,
+    nodes: Grid<Option<NodeIndex>>,
+    positions: Vec<Pos>,
+}
+
+impl<N, E, Ty: EdgeType> GridGraph<N, E, Ty> {
+    /// Nodes are added in row-major order and weighted by their cell. Cells for which
+    /// `open` is false get no node. The weight of an edge is `weight(from, to)` of the
+    /// cells it connects; for undirected graphs `from` is the cell first in row-major
+    /// order.
+    pub fn new<O, W>(grid: &Grid<N>, neighbors: Neighbors, open: O, weight: W) -> Self
+    where
+        N: Clone,
+        O: Fn(&N) -> bool,
+        W: Fn(&N, &N) -> E,
+    {
+        let mut graph = Graph::default();
+        let mut nodes = Grid::new(grid.width(), grid.height(), None);
+        let mut positions = vec![];
 
-/// Parse a string into a 2D grid graph
+        for (pos, cell) in grid.iter().filter(|(_, cell)| open(cell)) {
+            nodes[pos] = Some(graph.add_node(cell.clone()));
+            positions.push(pos);
+        }
+
+        for &from in &positions {
+            let targets = neighbors
+                .offsets()
+                .iter()
+                .filter_map(|&dir| grid.step(from, dir));
+            for to in targets {
+                let Some(b) = nodes[to] else {
+                    continue;
+                };
+                if !graph.is_directed() && (to.1, to.0) < (from.1, from.0) {
+                    continue;
+                }
+                let a = nodes[from].unwrap();
+                graph.add_edge(a, b, weight(&grid[from], &grid[to]));
+            }
+        }
+
+        Self {
+            graph,
+            nodes,
+            positions,
+        }
+    }
+
+    pub fn graph(&self) -> &Graph<N, E, Ty> {
+        &self.graph
+    }
+
+    /// The graph, e.g. to remove edges. Removing nodes breaks the position mapping.
+    pub fn graph_mut(&mut self) -> &mut Graph<N, E, Ty> {
+        &mut self.graph
+    }
+
+    pub fn into_graph(self) -> Graph<N, E, Ty> {
+        self.graph
+    }
+
+    /// Node of the cell at `pos`, if it is open.
+    pub fn node(&self, pos: Pos) -> Option<NodeIndex> {
+        *self.nodes.get(pos)?
+    }
+
+    /// Position of the cell of `node`.
+    pub fn position(&self, node: NodeIndex) -> Pos {
+        self.positions[node.index()]
+    }
+}
+
+/// Parse a string into a 2D grid graph of orthogonally adjacent cells, parsing every
+/// character with `parse`.
 pub fn parse_graph<N, F, Ty>(text: &str, parse: F) -> Graph<N, (), Ty>
 where
-    N: Unsigned + FromStr + Copy,
+    N: Clone,
     F: Fn(&str) -> N,
     Ty: EdgeType,
 {
-    let text = text.trim();
-    let lines = text.lines().filter(|l| !l.is_empty());
-    let n_rows = text.matches('\n').count();
-    let n_cols = text.find('\n').unwrap();
-    let get_idx = |row, col| row * n_cols + col;
-    let mut g = Graph::with_capacity(n_cols * n_cols, 4 * n_rows * n_cols);
-
-    for (row, line) in lines.enumerate() {
-        for (col, word) in line.split("").filter(|c| !c.is_empty()).enumerate() {
-            let current_node = parse(word);
-            let current_index = g.add_node(current_node);
-
-            if col > 0 {
-                let previous_index = NodeIndex::new(get_idx(row, col - 1));
-                g.add_edge(previous_index, current_index, ());
-                if g.is_directed() {
-                    g.add_edge(current_index, previous_index, ());
+    let grid = Grid::parse_with(text, |c| parse(c.encode_utf8(&mut [0; 4])))
+        .unwrap_or_else(|err| panic!("Invalid grid: {err}"));
+    GridGraph::new(&grid, Neighbors::Four, |_| true, |_, _| ()).into_graph()
+}
+
+/// A graph with a node for every key of `adjacency` and an edge to each of its values.
+pub fn from_adjacency<N, Ty, S>(adjacency: &HashMap<N, Vec<N>, S>) -> GraphMap<N, (), Ty>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+    S: BuildHasher,
+{
+    let mut graph = GraphMap::new();
+    for (&node, neighbors) in adjacency {
+        graph.add_node(node);
+        for &neighbor in neighbors {
+            graph.add_edge(node, neighbor, ());
+        }
+    }
+    graph
+}
+
+/// Replace every corridor, a chain of nodes with exactly two neighbours, by a single
+/// edge weighted by the sum of its edges. The nodes left are those with any other number
+/// of neighbours and those for which `keep` is true; their weights are their indices in
+/// `graph`.
+///
+/// In directed graphs corridors are followed along their edges only, so a corridor with
+/// a one-way edge is contracted into a single one-way edge.
+pub fn contract_corridors<N, E, Ty, K>(graph: &Graph<N, E, Ty>, keep: K) -> Graph<NodeIndex, E, Ty>
+where
+    E: Integer,
+    Ty: EdgeType,
+    K: Fn(NodeIndex) -> bool,
+{
+    let degree = |node| {
+        let mut neighbors = graph.neighbors_undirected(node).collect::<Vec<_>>();
+        neighbors.sort_unstable();
+        neighbors.dedup();
+        neighbors.len()
+    };
+    let junctions = graph
+        .node_indices()
+        .filter(|&node| keep(node) || degree(node) != 2)
+        .collect::<Vec<_>>();
+
+    let mut contracted = Graph::default();
+    let mut index = FxHashMap::default();
+    for &junction in &junctions {
+        index.insert(junction, contracted.add_node(junction));
+    }
+
+    for &junction in &junctions {
+        for edge in graph.edges(junction) {
+            let (mut prev, mut node, mut length) = (junction, edge.target(), *edge.weight());
+            while !index.contains_key(&node) {
+                let Some(next) = graph.edges(node).find(|edge| edge.target() != prev) else {
+                    break;
+                };
+                (prev, node, length) = (node, next.target(), length + *next.weight());
+            }
+
+            let Some(&end) = index.get(&node) else {
+                continue;
+            };
+            // Undirected corridors are walked from both ends
+            if node != junction && (graph.is_directed() || junction < node) {
+                contracted.add_edge(index[&junction], end, length);
+            }
+        }
+    }
+
+    contracted
+}
+
+/// Shortest distance between every pair of nodes, indexed by [`NodeIndexable::to_index`],
+/// or `None` if there is no path ([Floyd–Warshall]).
+///
+/// [Floyd–Warshall]: https://en.wikipedia.org/wiki/Floyd%E2%80%93Warshall_algorithm
+pub fn all_pairs_distances<G, F, K>(graph: G, mut cost: F) -> Vec<Vec<Option<K>>>
+where
+    G: IntoEdgeReferences + NodeIndexable + GraphProp,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Integer,
+{
+    let n = graph.node_bound();
+    let mut dist = vec![vec![None; n]; n];
+    for (i, row) in dist.iter_mut().enumerate() {
+        row[i] = Some(K::ZERO);
+    }
+
+    for edge in graph.edge_references() {
+        let (a, b) = (graph.to_index(edge.source()), graph.to_index(edge.target()));
+        let c = cost(edge);
+        let mut relax = |a: usize, b: usize| {
+            if dist[a][b].is_none_or(|d| c < d) {
+                dist[a][b] = Some(c);
+            }
+        };
+        relax(a, b);
+        if !graph.is_directed() {
+            relax(b, a);
+        }
+    }
+
+    for k in 0..n {
+        let via = dist[k].clone();
+        for row in &mut dist {
+            let Some(ik) = row[k] else {
+                continue;
+            };
+            for (d, &kj) in row.iter_mut().zip(&via) {
+                if let Some(kj) = kj
+                    && d.is_none_or(|d| ik + kj < d)
+                {
+                    *d = Some(ik + kj);
                 }
             }
-            if row > 0 {
-                let previous_index = NodeIndex::new(get_idx(row - 1, col));
-                g.add_edge(previous_index, current_index, ());
-                if g.is_directed() {
-                    g.add_edge(current_index, previous_index, ());
+        }
+    }
+
+    dist
+}
+
+/// Extend the clique `r` with candidates `p`, skipping cliques extendable by `x`
+/// ([Bron–Kerbosch] with pivoting).
+///
+/// [Bron–Kerbosch]: https://en.wikipedia.org/wiki/Bron%E2%80%93Kerbosch_algorithm
+fn bron_kerbosch(
+    adjacency: &[FxHashSet<usize>],
+    r: &mut Vec<usize>,
+    mut p: Vec<usize>,
+    mut x: Vec<usize>,
+    cliques: &mut Vec<Vec<usize>>,
+) {
+    let Some(&pivot) = p.iter().chain(&x).max_by_key(|&&u| adjacency[u].len()) else {
+        cliques.push(r.clone());
+        return;
+    };
+
+    let candidates = p
+        .iter()
+        .copied()
+        .filter(|v| !adjacency[pivot].contains(v))
+        .collect::<Vec<_>>();
+    for v in candidates {
+        let neighbors = &adjacency[v];
+        r.push(v);
+        bron_kerbosch(
+            adjacency,
+            r,
+            p.iter()
+                .copied()
+                .filter(|u| neighbors.contains(u))
+                .collect(),
+            x.iter()
+                .copied()
+                .filter(|u| neighbors.contains(u))
+                .collect(),
+            cliques,
+        );
+        r.pop();
+        p.retain(|&u| u != v);
+        x.push(v);
+    }
+}
+
+/// All cliques of an undirected graph which cannot be extended by another node.
+pub fn maximal_cliques<G>(graph: G) -> Vec<Vec<G::NodeId>>
+where
+    G: IntoNodeIdentifiers + IntoNeighbors + NodeIndexable,
+{
+    let mut adjacency = vec![FxHashSet::default(); graph.node_bound()];
+    for node in graph.node_identifiers() {
+        let i = graph.to_index(node);
+        adjacency[i].extend(graph.neighbors(node).map(|n| graph.to_index(n)));
+        adjacency[i].remove(&i);
+    }
+
+    let nodes = graph
+        .node_identifiers()
+        .map(|n| graph.to_index(n))
+        .collect();
+    let mut cliques = vec![];
+    bron_kerbosch(&adjacency, &mut vec![], nodes, vec![], &mut cliques);
+
+    cliques
+        .into_iter()
+        .map(|clique| clique.into_iter().map(|i| graph.from_index(i)).collect())
+        .collect()
+}
+
+/// A largest clique of an undirected graph.
+pub fn max_clique<G>(graph: G) -> Vec<G::NodeId>
+where
+    G: IntoNodeIdentifiers + IntoNeighbors + NodeIndexable,
+{
+    maximal_cliques(graph)
+        .into_iter()
+        .max_by_key(Vec::len)
+        .unwrap_or_default()
+}
+
+/// Minimum total weight of the edges to remove to disconnect an undirected graph, and the
+/// nodes on one side of such a cut ([Stoer–Wagner]). `None` for less than two nodes.
+///
+/// [Stoer–Wagner]: https://en.wikipedia.org/wiki/Stoer%E2%80%93Wagner_algorithm
+pub fn min_cut<G, F, K>(graph: G, mut weight: F) -> Option<(K, Vec<G::NodeId>)>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Integer,
+{
+    let n = graph.node_bound();
+    let mut adjacency: Vec<FxHashMap<usize, K>> = vec![FxHashMap::default(); n];
+    for edge in graph.edge_references() {
+        let (a, b) = (graph.to_index(edge.source()), graph.to_index(edge.target()));
+        if a != b {
+            let w = weight(edge);
+            for (u, v) in [(a, b), (b, a)] {
+                let total = adjacency[u].entry(v).or_insert(K::ZERO);
+                *total = *total + w;
+            }
+        }
+    }
+
+    // Every remaining node stands for the group of nodes merged into it
+    let mut active = graph
+        .node_identifiers()
+        .map(|n| graph.to_index(n))
+        .collect::<Vec<_>>();
+    let mut groups = (0..n).map(|i| vec![i]).collect::<Vec<_>>();
+    let mut best: Option<(K, Vec<usize>)> = None;
+
+    while active.len() > 1 {
+        // Add nodes in order of decreasing connection to those already added
+        let mut connection = vec![K::ZERO; n];
+        let mut added = vec![false; n];
+        let mut order = Vec::with_capacity(active.len());
+        for &start in &active {
+            if added[start] {
+                continue;
+            }
+            let mut heap = BinaryHeap::from([(K::ZERO, start)]);
+            while let Some((w, v)) = heap.pop() {
+                if added[v] || w != connection[v] {
+                    continue;
                 }
+                added[v] = true;
+                order.push(v);
+                for (&u, &c) in &adjacency[v] {
+                    if !added[u] {
+                        connection[u] = connection[u] + c;
+                        heap.push((connection[u], u));
+                    }
+                }
+            }
+        }
+
+        // The last node is cut off by its connection; then merge it into the one before
+        let (s, t) = (order[order.len() - 2], order[order.len() - 1]);
+        if best.as_ref().is_none_or(|(cut, _)| connection[t] < *cut) {
+            best = Some((connection[t], groups[t].clone()));
+        }
+
+        for (u, c) in std::mem::take(&mut adjacency[t]) {
+            adjacency[u].remove(&t);
+            if u != s {
+                let w = adjacency[s].get(&u).copied().unwrap_or_default() + c;
+                adjacency[s].insert(u, w);
+                adjacency[u].insert(s, w);
             }
         }
+        let group = std::mem::take(&mut groups[t]);
+        groups[s].extend(group);
+        active.retain(|&v| v != t);
+    }
+
+    best.map(|(cut, side)| (cut, side.into_iter().map(|i| graph.from_index(i)).collect()))
+}
+
+/// Length of the longest simple path from `start` to `goal`, or `None` if there is no
+/// path or the graph has more than 64 nodes.
+///
+/// An exhaustive depth-first search over all simple paths, keeping the visited nodes in
+/// a bitmask. Its cost grows with the number of simple paths, so it is meant for small
+/// graphs, e.g. after [`contract_corridors`]. Memoising on the visited set does not pay
+/// off for such graphs, where almost every state is reached once.
+pub fn exhaustive_longest_path<G, F, K>(
+    graph: G,
+    start: G::NodeId,
+    goal: G::NodeId,
+    mut weight: F,
+) -> Option<K>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Integer,
+{
+    let n = graph.node_bound();
+    if n > 64 {
+        return None;
+    }
+
+    let mut adjacency = vec![vec![]; n];
+    for node in graph.node_identifiers() {
+        for edge in graph.edges(node) {
+            adjacency[graph.to_index(node)].push((graph.to_index(edge.target()), weight(edge)));
+        }
+    }
+
+    fn search<K: Integer>(
+        adjacency: &[Vec<(usize, K)>],
+        node: usize,
+        goal: usize,
+        visited: u64,
+    ) -> Option<K> {
+        if node == goal {
+            return Some(K::ZERO);
+        }
+        adjacency[node]
+            .iter()
+            .filter(|&&(next, _)| visited & (1 << next) == 0)
+            .filter_map(|&(next, w)| {
+                Some(w + search(adjacency, next, goal, visited | (1 << next))?)
+            })
+            .max()
+    }
+
+    let start = graph.to_index(start);
+    search(&adjacency, start, graph.to_index(goal), 1 << start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use petgraph::graphmap::UnGraphMap;
+    use petgraph::{Directed, Undirected};
+
+    #[test]
+    fn test_parse_graph() {
+        // Not square and without a trailing newline
+        let graph: Graph<u8, (), Directed> = parse_graph("123\n456", |c| c.parse().unwrap());
+        assert_eq!(graph.node_count(), 6);
+        assert_eq!(graph.edge_count(), 2 * 7);
+        assert_eq!(graph[NodeIndex::new(5)], 6);
+
+        let graph: Graph<u8, (), Undirected> = parse_graph("12\n34\n56\n", |c| c.parse().unwrap());
+        assert_eq!(graph.node_count(), 6);
+        assert_eq!(graph.edge_count(), 7);
+    }
+
+    #[test]
+    fn test_grid_graph() {
+        let grid = Grid::parse_with("..#\n.#.\n...", |c| c).unwrap();
+        let open = |c: &char| *c == '.';
+
+        let four: GridGraph<_, _, Undirected> =
+            GridGraph::new(&grid, Neighbors::Four, open, |_, _| 1);
+        assert_eq!(four.graph().node_count(), 7);
+        assert_eq!(four.graph().edge_count(), 6);
+        assert_eq!(four.node((2, 0)), None);
+        assert_eq!(four.node((3, 0)), None);
+
+        let eight: GridGraph<_, _, Undirected> =
+            GridGraph::new(&grid, Neighbors::Eight, open, |_, _| 1);
+        assert_eq!(eight.graph().edge_count(), 10);
+
+        let node = eight.node((2, 1)).unwrap();
+        assert_eq!(eight.position(node), (2, 1));
+        let mut neighbors = eight
+            .graph()
+            .neighbors(node)
+            .map(|n| eight.position(n))
+            .collect::<Vec<_>>();
+        neighbors.sort();
+        assert_eq!(neighbors, [(1, 0), (1, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn test_weights() {
+        let grid = Grid::parse_with("19\n23", |c| c.to_digit(10).unwrap() as i32).unwrap();
+        let graph: GridGraph<_, _, Directed> =
+            GridGraph::new(&grid, Neighbors::Four, |_| true, |from, to| to - from);
+        let (a, b) = (graph.node((0, 0)).unwrap(), graph.node((1, 0)).unwrap());
+
+        let graph = graph.into_graph();
+        assert_eq!(graph[graph.find_edge(a, b).unwrap()], 8);
+        assert_eq!(graph.find_edge(b, a).map(|e| graph[e]), Some(-8));
+    }
+
+    #[test]
+    fn test_contract_corridors() {
+        let grid = Grid::parse_with("#.###\n#...#\n#.#.#\n#.#.#", |c| c).unwrap();
+        let grid_graph: GridGraph<_, _, Undirected> =
+            GridGraph::new(&grid, Neighbors::Four, |&c| c == '.', |_, _| 1);
+        let node = |pos| grid_graph.node(pos).unwrap();
+        let graph = contract_corridors(grid_graph.graph(), |_| false);
+        assert_eq!(graph.node_count(), 4);
+        assert_eq!(graph.edge_count(), 3);
+
+        let contracted = |pos| {
+            graph
+                .node_indices()
+                .find(|&n| graph[n] == node(pos))
+                .unwrap()
+        };
+        let (top, right, left) = (contracted((1, 0)), contracted((3, 3)), contracted((1, 3)));
+        let edge = graph.find_edge(contracted((1, 1)), right).unwrap();
+        assert_eq!(graph[edge], 4);
+        assert_eq!(
+            exhaustive_longest_path(&graph, top, right, |e| *e.weight()),
+            Some(5)
+        );
+
+        let dist = all_pairs_distances(&graph, |e| *e.weight());
+        assert_eq!(dist[top.index()][left.index()], Some(3));
+        assert_eq!(dist[right.index()][left.index()], Some(6));
+
+        let mut graph = graph;
+        graph.remove_edge(edge);
+        assert_eq!(
+            exhaustive_longest_path(&graph, top, right, |e| *e.weight()),
+            None
+        );
+        let dist = all_pairs_distances(&graph, |e| *e.weight());
+        assert_eq!(dist[right.index()][left.index()], None);
+    }
+
+    #[test]
+    fn test_exhaustive_longest_path() {
+        // A cycle with a chord, where going round beats the chord
+        let edges = [(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 0, 1), (0, 2, 1)];
+        let graph = Graph::<(), u32, Undirected>::from_edges(edges);
+        let (start, goal) = (NodeIndex::new(0), NodeIndex::new(2));
+        assert_eq!(
+            exhaustive_longest_path(&graph, start, goal, |e| *e.weight()),
+            Some(2)
+        );
+
+        let path = Graph::<(), u32, Undirected>::from_edges((0..64).map(|i| (i, i + 1, 1)));
+        let goal = NodeIndex::new(64);
+        assert_eq!(
+            exhaustive_longest_path(&path, start, goal, |e| *e.weight()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_cliques() {
+        let adjacency = FxHashMap::from_iter([
+            ("a", vec!["b", "c"]),
+            ("b", vec!["a", "c"]),
+            ("c", vec!["a", "b", "d"]),
+            ("d", vec!["c"]),
+            ("e", vec![]),
+        ]);
+        let graph: GraphMap<_, _, Undirected> = from_adjacency(&adjacency);
+
+        let mut cliques = maximal_cliques(&graph);
+        cliques.iter_mut().for_each(|clique| clique.sort());
+        cliques.sort();
+        assert_eq!(cliques, [vec!["a", "b", "c"], vec!["c", "d"], vec!["e"]]);
+
+        let mut clique = max_clique(&graph);
+        clique.sort();
+        assert_eq!(clique, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_min_cut() {
+        let graph = UnGraphMap::<_, u32>::from_edges([
+            ("a", "b", 1),
+            ("b", "c", 1),
+            ("c", "a", 1),
+            ("c", "d", 1),
+            ("d", "e", 1),
+            ("e", "f", 1),
+            ("f", "d", 1),
+        ]);
+        let (cut, mut side) = min_cut(&graph, |_| 1u32).unwrap();
+        side.sort();
+        assert!(cut == 1 && (side == ["a", "b", "c"] || side == ["d", "e", "f"]));
+
+        // A heavy bridge makes cutting off a single node cheaper
+        let (cut, side) =
+            min_cut(&graph, |(a, b, _)| if [a, b] == ["c", "d"] { 5 } else { 1 }).unwrap();
+        assert_eq!((cut, side.len()), (2, 1));
+
+        let graph = UnGraphMap::<_, ()>::from_edges([("a", "b")]);
+        let (cut, _) = min_cut(&graph, |_| 1u32).unwrap();
+        assert_eq!(cut, 1);
+        let mut graph = graph;
+        graph.add_node("c");
+        assert_eq!(min_cut(&graph, |_| 1u32), Some((0, vec!["c"])));
+        assert_eq!(min_cut(&UnGraphMap::<&str, ()>::new(), |_| 1u32), None);
     }
-    g.shrink_to_fit();
-    g
 }
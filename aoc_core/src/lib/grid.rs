@@ -0,0 +1,435 @@
+//! A 2D grid backed by a flat `Vec<T>`, indexed by `(x, y)` positions where `x` is
+//! the column and `y` the row, with `(0, 0)` in the top-left corner.
+use crate::point::Point2;
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+/// Position in a grid as `(x, y)`.
+pub type Pos = (usize, usize);
+
+/// Offsets of the 4 orthogonal neighbours: right, down, left, up.
+pub const DIRS4: [(isize, isize); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+/// Offsets of all 8 neighbours, orthogonal and diagonal.
+pub const DIRS8: [(isize, isize); 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    Empty,
+    Ragged { row: usize },
+    NonAscii { row: usize },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Grid is empty"),
+            Self::Ragged { row } => write!(f, "Row {row} has a different width"),
+            Self::NonAscii { row } => write!(f, "Row {row} has a non-ASCII character"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    data: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Create a grid from row-major data. Both dimensions must be positive.
+    pub fn from_vec(width: usize, height: usize, data: Vec<T>) -> Self {
+        assert!(width > 0 && height > 0, "grid dimensions must be positive");
+        assert_eq!(data.len(), width * height, "data does not match grid size");
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    /// Parse a grid from puzzle text, mapping each character to a cell. Only trailing
+    /// line breaks are dropped, so spaces at the edges remain cells.
+    pub fn parse_with<F>(text: &str, f: F) -> Result<Self, GridError>
+    where
+        F: Fn(char) -> T,
+    {
+        let mut lines = text.trim_end_matches(['\n', '\r']).lines().peekable();
+        let width = lines.peek().map_or(0, |line| line.chars().count());
+        let mut data = Vec::with_capacity(text.len());
+        let mut height = 0;
+
+        if width == 0 {
+            return Err(GridError::Empty);
+        }
+
+        for (row, line) in lines.enumerate() {
+            let len = data.len();
+            data.extend(line.chars().map(&f));
+            if data.len() - len != width {
+                return Err(GridError::Ragged { row });
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            width,
+            height,
+            data,
+        })
+    }
+
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[inline]
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    #[inline]
+    fn idx(&self, (x, y): Pos) -> usize {
+        y * self.width + x
+    }
+
+    #[inline]
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos).then(|| &self.data[self.idx(pos)])
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            let idx = self.idx(pos);
+            Some(&mut self.data[idx])
+        } else {
+            None
+        }
+    }
+
+    /// Position one step from `pos` in direction `(dx, dy)`, if inside the grid.
+    #[inline]
+    pub fn step(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// Orthogonal neighbours of `pos` which are inside the grid.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRS4.into_iter().filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// Orthogonal and diagonal neighbours of `pos` which are inside the grid.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRS8.into_iter().filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + use<T> {
+        let (width, height) = (self.width, self.height);
+        (0..height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All cells with their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.data.iter())
+    }
+
+    pub fn values(&self) -> std::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.data[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> std::slice::Chunks<'_, T> {
+        self.data.chunks(self.width)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column out of bounds");
+        self.data.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Position of the first cell (in row-major order) matching a predicate.
+    pub fn position<P>(&self, predicate: P) -> Option<Pos>
+    where
+        P: Fn(&T) -> bool,
+    {
+        self.iter().find(|(_, v)| predicate(v)).map(|(pos, _)| pos)
+    }
+
+    /// Positions of all cells matching a predicate.
+    pub fn positions_where<P>(&self, predicate: P) -> impl Iterator<Item = Pos>
+    where
+        P: Fn(&T) -> bool,
+    {
+        self.iter()
+            .filter(move |(_, v)| predicate(v))
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: Fn(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            data: self.data.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Position of the first cell equal to `value`.
+    pub fn find(&self, value: &T) -> Option<Pos> {
+        self.position(|v| v == value)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self::from_vec(width, height, vec![fill; width * height])
+    }
+
+    /// Build a new grid of the given shape where cell `(x, y)` is taken from `self[f(x, y)]`.
+    fn remap<F>(&self, width: usize, height: usize, f: F) -> Self
+    where
+        F: Fn(usize, usize) -> Pos,
+    {
+        let data = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| self[f(x, y)].clone())
+            .collect();
+        Self::from_vec(width, height, data)
+    }
+
+    /// Swap rows and columns.
+    pub fn transpose(&self) -> Self {
+        self.remap(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// Rotate by 90° clockwise.
+    pub fn rotate_right(&self) -> Self {
+        let h = self.height;
+        self.remap(self.height, self.width, |x, y| (y, h - 1 - x))
+    }
+
+    /// Rotate by 90° counterclockwise.
+    pub fn rotate_left(&self) -> Self {
+        let w = self.width;
+        self.remap(self.height, self.width, |x, y| (w - 1 - y, x))
+    }
+
+    /// Mirror left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        let w = self.width;
+        self.remap(self.width, self.height, |x, y| (w - 1 - x, y))
+    }
+
+    /// Mirror top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        let h = self.height;
+        self.remap(self.width, self.height, |x, y| (x, h - 1 - y))
+    }
+}
+
+impl Grid<u8> {
+    /// Parse a grid of raw bytes. Every character must be ASCII.
+    pub fn parse_bytes(text: &str) -> Result<Self, GridError> {
+        if let Some(row) = text.lines().position(|line| !line.is_ascii()) {
+            return Err(GridError::NonAscii { row });
+        }
+        Self::parse_with(text, |ch| ch as u8)
+    }
+}
+
+impl FromStr for Grid<u8> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_bytes(s)
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, |ch| ch)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, pos: Pos) -> &Self::Output {
+        assert!(self.contains(pos), "position {pos:?} out of bounds");
+        &self.data[self.idx(pos)]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    #[inline]
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        assert!(self.contains(pos), "position {pos:?} out of bounds");
+        let idx = self.idx(pos);
+        &mut self.data[idx]
+    }
+}
+
+impl<T> Index<Point2<usize>> for Grid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, pos: Point2<usize>) -> &Self::Output {
+        &self[(pos.x, pos.y)]
+    }
+}
+
+impl<T> IndexMut<Point2<usize>> for Grid<T> {
+    #[inline]
+    fn index_mut(&mut self, pos: Point2<usize>) -> &mut Self::Output {
+        &mut self[(pos.x, pos.y)]
+    }
+}
+
+impl<T> Display for Grid<T>
+where
+    T: Copy + Into<char>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for &cell in row {
+                write!(f, "{}", cell.into())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = "\
+abc
+def
+";
+
+    #[test]
+    fn test_parse() {
+        let grid: Grid<u8> = EXAMPLE.parse().unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], b'f');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.to_string(), EXAMPLE.trim());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(Grid::<u8>::parse_bytes(""), Err(GridError::Empty));
+        assert_eq!(
+            Grid::<u8>::parse_bytes("ab\nc"),
+            Err(GridError::Ragged { row: 1 })
+        );
+        assert_eq!(
+            Grid::<u8>::parse_bytes("ab\nc\u{e9}"),
+            Err(GridError::NonAscii { row: 1 })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "grid dimensions must be positive")]
+    fn test_zero_width() {
+        Grid::new(0, 3, b'.');
+    }
+
+    #[test]
+    fn test_parse_with() {
+        let grid = Grid::parse_with("12\n34", |ch| ch.to_digit(10).unwrap()).unwrap();
+        assert_eq!(grid.values().sum::<u32>(), 10);
+        assert_eq!(grid.find(&3), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_parse_spaces() {
+        // Blank cells at the edges, as in ASCII-art maps
+        let grid = Grid::parse_with("  #\n#  \n   \n\n", |ch| ch).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid[(2, 0)], '#');
+        assert_eq!(grid[(0, 1)], '#');
+
+        let grid = Grid::parse_with(" a\r\nb \r\n", |ch| ch).unwrap();
+        assert_eq!(grid.to_string(), " a\nb ");
+    }
+
+    #[test]
+    fn test_rows_columns() {
+        let grid: Grid<char> = EXAMPLE.parse().unwrap();
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.columns().count(), 3);
+        assert_eq!(grid.rows().count(), 2);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(grid.neighbors4((0, 0)).count(), 2);
+        assert_eq!(grid.neighbors4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbors8((0, 0)).count(), 3);
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+        assert_eq!(grid.step((0, 0), (-1, 0)), None);
+        assert_eq!(grid.step((0, 0), (1, 1)), Some((1, 1)));
+    }
+
+    #[test]
+    fn test_transformations() {
+        let grid: Grid<char> = EXAMPLE.parse().unwrap();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_right().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_left().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+    }
+
+    #[test]
+    fn test_mutation() {
+        let mut grid: Grid<char> = EXAMPLE.parse().unwrap();
+        grid[(0, 0)] = '#';
+        *grid.get_mut((1, 1)).unwrap() = '#';
+        assert_eq!(grid[Point2::new(1, 1)], '#');
+        assert_eq!(grid.positions_where(|&ch| ch == '#').count(), 2);
+        assert_eq!(grid.position(|&ch| ch == '#'), Some((0, 0)));
+    }
+}
@@ -6,9 +6,16 @@
 //! [Dijkstra]: https://en.wikipedia.org/wiki/Dijkstra%27s_algorithm
 //! [A*]: https://en.wikipedia.org/wiki/A*_search_algorithm
 //!
+//! [`MaxHeap`] is the same with the order reversed, and [`IndexedMinHeap`] additionally
+//! tracks the position of every value so that its key can be lowered in place
+//! (decrease-key) instead of pushing duplicates and skipping stale entries.
+//!
 //! Source: https://github.com/maneatingape/advent-of-code-rust/blob/main/src/util/heap.rs
-use std::cmp::Ordering;
+use rustc_hash::FxHashMap;
+use std::cmp::{Ordering, Reverse};
 use std::collections::BinaryHeap;
+use std::fmt;
+use std::hash::Hash;
 
 struct Wrapper<K: Ord, V> {
     key: K,
@@ -38,11 +45,16 @@ impl<K: Ord, V> Ord for Wrapper<K, V> {
     }
 }
 
-#[derive(Default)]
 pub struct MinHeap<K: Ord, V> {
     heap: BinaryHeap<Wrapper<K, V>>,
 }
 
+impl<K: Ord, V> Default for MinHeap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<K: Ord, V> MinHeap<K, V> {
     pub fn new() -> Self {
         MinHeap {
@@ -65,6 +77,49 @@ impl<K: Ord, V> MinHeap<K, V> {
     pub fn pop(&mut self) -> Option<(K, V)> {
         self.heap.pop().map(|w| (w.key, w.value))
     }
+
+    /// The entry with the smallest key, without removing it.
+    #[inline]
+    pub fn peek(&self) -> Option<(&K, &V)> {
+        self.heap.peek().map(|w| (&w.key, &w.value))
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.heap.clear();
+    }
+
+    /// All entries ordered by ascending key.
+    pub fn into_sorted_vec(self) -> Vec<(K, V)> {
+        // The wrapper reverses the order, so the max heap's ascending order is descending
+        let mut vec = self.heap.into_sorted_vec();
+        vec.reverse();
+        vec.into_iter().map(|w| (w.key, w.value)).collect()
+    }
+}
+
+impl<K: Ord + fmt::Debug, V: fmt::Debug> fmt::Debug for MinHeap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.heap.iter().map(|w| (&w.key, &w.value)))
+            .finish()
+    }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for MinHeap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        self.heap
+            .extend(iter.into_iter().map(|(key, value)| Wrapper { key, value }));
+    }
 }
 
 impl<K: Ord, V, const N: usize> From<[(K, V); N]> for MinHeap<K, V> {
@@ -86,3 +141,312 @@ impl<K: Ord, V> From<Vec<(K, V)>> for MinHeap<K, V> {
         MinHeap { heap }
     }
 }
+
+/// Max heap counterpart of [`MinHeap`], popping the entry with the largest key first.
+pub struct MaxHeap<K: Ord, V> {
+    heap: MinHeap<Reverse<K>, V>,
+}
+
+impl<K: Ord, V> Default for MaxHeap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> MaxHeap<K, V> {
+    pub fn new() -> Self {
+        MaxHeap {
+            heap: MinHeap::new(),
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        MaxHeap {
+            heap: MinHeap::with_capacity(capacity),
+        }
+    }
+
+    #[inline]
+    pub fn push(&mut self, key: K, value: V) {
+        self.heap.push(Reverse(key), value);
+    }
+
+    #[inline]
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        self.heap.pop().map(|(Reverse(key), value)| (key, value))
+    }
+
+    /// The entry with the largest key, without removing it.
+    #[inline]
+    pub fn peek(&self) -> Option<(&K, &V)> {
+        self.heap.peek().map(|(key, value)| (&key.0, value))
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.heap.clear();
+    }
+
+    /// All entries ordered by descending key.
+    pub fn into_sorted_vec(self) -> Vec<(K, V)> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|(Reverse(key), value)| (key, value))
+            .collect()
+    }
+}
+
+impl<K: Ord + fmt::Debug, V: fmt::Debug> fmt::Debug for MaxHeap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.heap.heap.iter().map(|w| (&w.key.0, &w.value)))
+            .finish()
+    }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for MaxHeap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        self.heap
+            .extend(iter.into_iter().map(|(key, value)| (Reverse(key), value)));
+    }
+}
+
+impl<K: Ord, V, const N: usize> From<[(K, V); N]> for MaxHeap<K, V> {
+    fn from(arr: [(K, V); N]) -> Self {
+        Self::from_iter(arr)
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for MaxHeap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut heap = MaxHeap::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+/// Binary min heap holding every value at most once, with the position of each value
+/// indexed so its key can be changed in place.
+pub struct IndexedMinHeap<K: Ord, V: Clone + Hash + Eq> {
+    heap: Vec<(K, V)>,
+    index: FxHashMap<V, usize>,
+}
+
+impl<K: Ord, V: Clone + Hash + Eq> Default for IndexedMinHeap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V: Clone + Hash + Eq> IndexedMinHeap<K, V> {
+    pub fn new() -> Self {
+        IndexedMinHeap {
+            heap: vec![],
+            index: FxHashMap::default(),
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.heap.clear();
+        self.index.clear();
+    }
+
+    #[inline]
+    pub fn contains(&self, value: &V) -> bool {
+        self.index.contains_key(value)
+    }
+
+    /// Current key of `value`, if it is queued.
+    pub fn get(&self, value: &V) -> Option<&K> {
+        self.index.get(value).map(|&i| &self.heap[i].0)
+    }
+
+    /// The entry with the smallest key, without removing it.
+    pub fn peek(&self) -> Option<(&K, &V)> {
+        self.heap.first().map(|(key, value)| (key, value))
+    }
+
+    /// Queue `value` with `key`, replacing the key if it is already queued. Returns the
+    /// previous key.
+    pub fn push(&mut self, key: K, value: V) -> Option<K> {
+        match self.index.get(&value) {
+            Some(&i) => {
+                let old = std::mem::replace(&mut self.heap[i].0, key);
+                let i = self.sift_up(i);
+                self.sift_down(i);
+                Some(old)
+            }
+            None => {
+                self.index.insert(value.clone(), self.heap.len());
+                self.heap.push((key, value));
+                self.sift_up(self.heap.len() - 1);
+                None
+            }
+        }
+    }
+
+    /// Queue `value` with `key` unless it is already queued with a key at most as small.
+    /// Returns whether the queue changed.
+    pub fn decrease_key(&mut self, key: K, value: V) -> bool {
+        match self.index.get(&value) {
+            Some(&i) if self.heap[i].0 <= key => false,
+            Some(&i) => {
+                self.heap[i].0 = key;
+                self.sift_up(i);
+                true
+            }
+            None => {
+                self.push(key, value);
+                true
+            }
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        self.remove_at(0)
+    }
+
+    /// Remove `value` from the queue, returning its key.
+    pub fn remove(&mut self, value: &V) -> Option<K> {
+        let i = *self.index.get(value)?;
+        self.remove_at(i).map(|(key, _)| key)
+    }
+
+    fn remove_at(&mut self, i: usize) -> Option<(K, V)> {
+        if i >= self.heap.len() {
+            return None;
+        }
+
+        let last = self.heap.len() - 1;
+        self.swap(i, last);
+        let (key, value) = self.heap.pop()?;
+        self.index.remove(&value);
+
+        if i < self.heap.len() {
+            let i = self.sift_up(i);
+            self.sift_down(i);
+        }
+        Some((key, value))
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.heap.swap(i, j);
+        *self.index.get_mut(&self.heap[i].1).unwrap() = i;
+        *self.index.get_mut(&self.heap[j].1).unwrap() = j;
+    }
+
+    fn sift_up(&mut self, mut i: usize) -> usize {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if self.heap[parent].0 <= self.heap[i].0 {
+                break;
+            }
+            self.swap(i, parent);
+            i = parent;
+        }
+        i
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let (left, right) = (2 * i + 1, 2 * i + 2);
+            let mut smallest = i;
+            if left < self.heap.len() && self.heap[left].0 < self.heap[smallest].0 {
+                smallest = left;
+            }
+            if right < self.heap.len() && self.heap[right].0 < self.heap[smallest].0 {
+                smallest = right;
+            }
+            if smallest == i {
+                break;
+            }
+            self.swap(i, smallest);
+            i = smallest;
+        }
+    }
+}
+
+impl<K, V> fmt::Debug for IndexedMinHeap<K, V>
+where
+    K: Ord + fmt::Debug,
+    V: Clone + Hash + Eq + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.heap.iter().map(|(key, value)| (key, value)))
+            .finish()
+    }
+}
+
+impl<K: Ord, V: Clone + Hash + Eq> Extend<(K, V)> for IndexedMinHeap<K, V> {
+    /// Pushes every entry, so later keys replace earlier ones for the same value.
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.push(key, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_heap() {
+        let mut heap = MinHeap::from([(3, 'c'), (1, 'a')]);
+        heap.extend([(2, 'b'), (0, 'z')]);
+        assert_eq!(heap.len(), 4);
+        assert_eq!(heap.peek(), Some((&0, &'z')));
+        assert_eq!(heap.pop(), Some((0, 'z')));
+        assert_eq!(heap.into_sorted_vec(), [(1, 'a'), (2, 'b'), (3, 'c')]);
+    }
+
+    #[test]
+    fn test_max_heap() {
+        let mut heap = MaxHeap::from([(3, 'c'), (1, 'a'), (2, 'b')]);
+        assert_eq!(heap.peek(), Some((&3, &'c')));
+        assert_eq!(heap.pop(), Some((3, 'c')));
+        assert_eq!(heap.into_sorted_vec(), [(2, 'b'), (1, 'a')]);
+    }
+
+    #[test]
+    fn test_indexed_heap() {
+        let mut heap = IndexedMinHeap::new();
+        heap.extend([(5, 'a'), (3, 'b'), (4, 'c'), (6, 'd')]);
+
+        assert!(heap.decrease_key(1, 'd'));
+        assert!(!heap.decrease_key(7, 'a'));
+        assert_eq!(heap.push(9, 'b'), Some(3));
+        assert_eq!(heap.get(&'b'), Some(&9));
+        assert_eq!(heap.remove(&'c'), Some(4));
+        assert!(!heap.contains(&'c'));
+        assert_eq!(heap.len(), 3);
+
+        let mut order = vec![];
+        while let Some(entry) = heap.pop() {
+            order.push(entry);
+        }
+        assert_eq!(order, [(1, 'd'), (5, 'a'), (9, 'b')]);
+        assert!(heap.is_empty());
+    }
+}
@@ -1,26 +1,599 @@
-pub struct IntcodeComputer;
+//! [Intcode] virtual machine shared by the 2019 puzzles.
+//!
+//! Memory holds `i64` values and grows on demand: reads past the end return zero and
+//! writes extend it. Inputs are consumed from a queue; when it runs dry the machine
+//! pauses with [`State::NeedsInput`] so that several machines can be chained (amplifiers,
+//! network computers) by moving outputs to inputs and resuming. Machines are plain
+//! values, so `clone` takes a snapshot to branch from during a search.
+//!
+//! For debugging, [`disassemble`] renders a program as mnemonics and
+//! [`IntcodeComputer::set_tracing`] records every executed instruction, memory write and
+//! I/O value as an [`Event`].
+//!
+//! [Intcode]: https://adventofcode.com/2019/day/9
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+
+/// What the machine did, or why it stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    /// An instruction was executed and the machine can continue.
+    Running,
+    /// An input instruction found the input queue empty. Resumes at that instruction.
+    NeedsInput,
+    /// A value was written to the output queue.
+    Output(i64),
+    /// Opcode 99 was reached. Further steps do nothing.
+    Halted,
+    /// The value `code` at `address` is not a valid instruction: unknown opcode or
+    /// parameter mode, or an immediate write. Further steps fault again.
+    Fault { code: i64, address: usize },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    Add,
+    Mul,
+    Input,
+    Output,
+    JumpIfTrue,
+    JumpIfFalse,
+    LessThan,
+    Equals,
+    AdjustBase,
+    Halt,
+}
+
+impl Opcode {
+    fn new(code: i64) -> Option<Self> {
+        Some(match code {
+            1 => Opcode::Add,
+            2 => Opcode::Mul,
+            3 => Opcode::Input,
+            4 => Opcode::Output,
+            5 => Opcode::JumpIfTrue,
+            6 => Opcode::JumpIfFalse,
+            7 => Opcode::LessThan,
+            8 => Opcode::Equals,
+            9 => Opcode::AdjustBase,
+            99 => Opcode::Halt,
+            _ => return None,
+        })
+    }
+
+    /// Number of parameters.
+    pub fn arity(self) -> usize {
+        match self {
+            Opcode::Add | Opcode::Mul | Opcode::LessThan | Opcode::Equals => 3,
+            Opcode::JumpIfTrue | Opcode::JumpIfFalse => 2,
+            Opcode::Input | Opcode::Output | Opcode::AdjustBase => 1,
+            Opcode::Halt => 0,
+        }
+    }
+
+    /// Whether the last parameter is the address written to.
+    pub fn writes(self) -> bool {
+        matches!(
+            self,
+            Opcode::Add | Opcode::Mul | Opcode::Input | Opcode::LessThan | Opcode::Equals
+        )
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Opcode::Add => "add",
+            Opcode::Mul => "mul",
+            Opcode::Input => "in",
+            Opcode::Output => "out",
+            Opcode::JumpIfTrue => "jnz",
+            Opcode::JumpIfFalse => "jz",
+            Opcode::LessThan => "lt",
+            Opcode::Equals => "eq",
+            Opcode::AdjustBase => "arb",
+            Opcode::Halt => "halt",
+        }
+    }
+}
+
+/// Parameter addressing mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Mode {
+    #[default]
+    Position,
+    Immediate,
+    Relative,
+}
+
+impl Mode {
+    fn new(digit: i64) -> Option<Self> {
+        match digit {
+            0 => Some(Mode::Position),
+            1 => Some(Mode::Immediate),
+            2 => Some(Mode::Relative),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Param {
+    pub mode: Mode,
+    pub value: i64,
+}
+
+impl fmt::Display for Param {
+    /// `[9]` reads address 9, `9` is the value itself and `[rb+9]` is relative to the base.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.mode {
+            Mode::Position => write!(f, "[{}]", self.value),
+            Mode::Immediate => write!(f, "{}", self.value),
+            Mode::Relative if self.value < 0 => write!(f, "[rb{}]", self.value),
+            Mode::Relative => write!(f, "[rb+{}]", self.value),
+        }
+    }
+}
+
+/// A decoded instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub address: usize,
+    pub opcode: Opcode,
+    params: [Param; 3],
+}
+
+impl Instruction {
+    /// Decode the instruction at `address`, or `None` if the opcode or a parameter mode
+    /// is invalid, e.g. because the address holds data.
+    pub fn decode(memory: &[i64], address: usize) -> Option<Self> {
+        let read = |i: usize| memory.get(i).copied().unwrap_or(0);
+        let code = read(address);
+        let opcode = Opcode::new(code % 100)?;
+
+        let mut params = [Param::default(); 3];
+        for (i, param) in params.iter_mut().enumerate().take(opcode.arity()) {
+            param.mode = Mode::new(code / 10i64.pow(i as u32 + 2) % 10)?;
+            param.value = read(address + 1 + i);
+        }
+        if opcode.writes() && params[opcode.arity() - 1].mode == Mode::Immediate {
+            return None;
+        }
+
+        Some(Self {
+            address,
+            opcode,
+            params,
+        })
+    }
+
+    pub fn params(&self) -> &[Param] {
+        &self.params[..self.opcode.arity()]
+    }
+
+    /// Number of memory cells taken by the instruction and its parameters.
+    pub fn width(&self) -> usize {
+        1 + self.opcode.arity()
+    }
+}
+
+impl fmt::Display for Instruction {
+    /// E.g. `   12: add  [9], 3 -> [rb+1]`, with the written address after the arrow.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:>5}: {:<4}", self.address, self.opcode.mnemonic())?;
+
+        let params = self.params();
+        let (sources, dest) = match self.opcode.writes() {
+            true => (&params[..params.len() - 1], params.last()),
+            false => (params, None),
+        };
+        for (i, param) in sources.iter().enumerate() {
+            let sep = if i == 0 { " " } else { ", " };
+            write!(f, "{sep}{param}")?;
+        }
+        if let Some(dest) = dest {
+            write!(f, " -> {dest}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Render `memory` as one line per instruction, starting at address 0. Cells which do not
+/// decode to an instruction are shown as `data`. Since Intcode programs mix code and data
+/// and may modify themselves, this is a best effort listing.
+pub fn disassemble(memory: &[i64]) -> Vec<String> {
+    let mut lines = vec![];
+    let mut address = 0;
+
+    while address < memory.len() {
+        match Instruction::decode(memory, address) {
+            Some(instruction) => {
+                lines.push(instruction.to_string());
+                address += instruction.width();
+            }
+            None => {
+                lines.push(format!("{address:>5}: data {}", memory[address]));
+                address += 1;
+            }
+        }
+    }
+
+    lines
+}
+
+/// Something the machine did, recorded while tracing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Execute(Instruction),
+    Write { address: usize, value: i64 },
+    Input(i64),
+    Output(i64),
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::Execute(instruction) => write!(f, "{instruction}"),
+            Event::Write { address, value } => write!(f, "{:>7}[{address}] = {value}", ""),
+            Event::Input(value) => write!(f, "{:>7}input {value}", ""),
+            Event::Output(value) => write!(f, "{:>7}output {value}", ""),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntcodeComputer {
+    memory: Vec<i64>,
+    ip: usize,
+    relative_base: i64,
+    halted: bool,
+    input: VecDeque<i64>,
+    output: VecDeque<i64>,
+    trace: Option<Vec<Event>>,
+}
+
+impl FromStr for IntcodeComputer {
+    type Err = std::num::ParseIntError;
+
+    /// Parse a comma-separated program.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let program = s
+            .trim()
+            .split(',')
+            .map(|x| x.trim().parse())
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new(program))
+    }
+}
 
 impl IntcodeComputer {
-    pub fn eval(input: &mut [usize]) -> usize {
-        let mut i = 0;
+    pub fn new(program: impl Into<Vec<i64>>) -> Self {
+        Self {
+            memory: program.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Value at `address`, zero beyond the loaded program.
+    pub fn read(&self, address: usize) -> i64 {
+        self.memory.get(address).copied().unwrap_or(0)
+    }
+
+    /// Store `value` at `address`, growing memory as needed.
+    pub fn write(&mut self, address: usize, value: i64) {
+        if address >= self.memory.len() {
+            self.memory.resize(address + 1, 0);
+        }
+        self.memory[address] = value;
+        self.record(Event::Write { address, value });
+    }
+
+    pub fn memory(&self) -> &[i64] {
+        &self.memory
+    }
+
+    /// Address of the next instruction.
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    pub fn relative_base(&self) -> i64 {
+        self.relative_base
+    }
 
+    /// The instruction about to be executed, if it decodes.
+    pub fn current(&self) -> Option<Instruction> {
+        Instruction::decode(&self.memory, self.ip)
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    /// Start or stop recording [`Event`]s. Stopping discards the trace.
+    pub fn set_tracing(&mut self, enabled: bool) {
+        self.trace = enabled.then(Vec::new);
+    }
+
+    /// Events recorded since tracing was enabled or the trace was last taken.
+    pub fn trace(&self) -> &[Event] {
+        self.trace.as_deref().unwrap_or_default()
+    }
+
+    pub fn take_trace(&mut self) -> Vec<Event> {
+        self.trace.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    pub fn push_input(&mut self, value: i64) {
+        self.input.push_back(value);
+    }
+
+    pub fn extend_input(&mut self, values: impl IntoIterator<Item = i64>) {
+        self.input.extend(values);
+    }
+
+    /// Queue the characters of `line` followed by a newline, for ASCII-capable programs.
+    pub fn push_line(&mut self, line: &str) {
+        self.extend_input(line.bytes().chain([b'\n']).map(i64::from));
+    }
+
+    /// Oldest output not yet taken.
+    pub fn pop_output(&mut self) -> Option<i64> {
+        self.output.pop_front()
+    }
+
+    /// All queued outputs, oldest first.
+    pub fn take_output(&mut self) -> Vec<i64> {
+        self.output.drain(..).collect()
+    }
+
+    /// Execute a single instruction. Stops with [`State::Fault`] on an invalid
+    /// instruction; panics on a negative address.
+    pub fn step(&mut self) -> State {
+        if self.halted {
+            return State::Halted;
+        }
+
+        let Some(instruction) = self.current() else {
+            return State::Fault {
+                code: self.read(self.ip),
+                address: self.ip,
+            };
+        };
+        if instruction.opcode == Opcode::Input && self.input.is_empty() {
+            return State::NeedsInput;
+        }
+        self.record(Event::Execute(instruction));
+
+        let params = instruction.params;
+        let mut state = State::Running;
+        self.ip += instruction.width();
+
+        match instruction.opcode {
+            op @ (Opcode::Add | Opcode::Mul | Opcode::LessThan | Opcode::Equals) => {
+                let (a, b) = (self.value(params[0]), self.value(params[1]));
+                let value = match op {
+                    Opcode::Add => a + b,
+                    Opcode::Mul => a * b,
+                    Opcode::LessThan => (a < b) as i64,
+                    _ => (a == b) as i64,
+                };
+                self.write(self.address(params[2]), value);
+            }
+            Opcode::Input => {
+                let value = self.input.pop_front().unwrap();
+                self.record(Event::Input(value));
+                self.write(self.address(params[0]), value);
+            }
+            Opcode::Output => {
+                let value = self.value(params[0]);
+                self.record(Event::Output(value));
+                self.output.push_back(value);
+                state = State::Output(value);
+            }
+            op @ (Opcode::JumpIfTrue | Opcode::JumpIfFalse) => {
+                let cond = self.value(params[0]) != 0;
+                if cond == (op == Opcode::JumpIfTrue) {
+                    self.ip = to_address(self.value(params[1]));
+                }
+            }
+            Opcode::AdjustBase => self.relative_base += self.value(params[0]),
+            Opcode::Halt => {
+                self.ip = instruction.address;
+                self.halted = true;
+                state = State::Halted;
+            }
+        }
+
+        state
+    }
+
+    /// Run until the machine needs input, halts or faults, queueing all outputs.
+    pub fn run(&mut self) -> State {
         loop {
-            let op = input[i];
+            match self.step() {
+                State::Running | State::Output(_) => {}
+                state => return state,
+            }
+        }
+    }
 
-            if op == 99 {
-                break;
+    /// Run until the next output, or until the machine needs input, halts or faults.
+    pub fn run_until_output(&mut self) -> State {
+        loop {
+            match self.step() {
+                State::Running => {}
+                state => return state,
             }
+        }
+    }
 
-            let (j, k, m) = (input[i + 1], input[i + 2], input[i + 3]);
-            input[m] = match op {
-                1 => input[j] + input[k],
-                2 => input[j] * input[k],
-                _ => unreachable!("invalid op code"),
-            };
+    /// Queue `inputs`, run until the machine needs more or halts, and return all
+    /// outputs produced so far. Panics on an invalid instruction.
+    pub fn run_with(&mut self, inputs: impl IntoIterator<Item = i64>) -> Vec<i64> {
+        self.extend_input(inputs);
+        if let State::Fault { code, address } = self.run() {
+            panic!("Invalid Intcode instruction {code} at {address}");
+        }
+        self.take_output()
+    }
+
+    #[inline]
+    fn record(&mut self, event: Event) {
+        if let Some(trace) = &mut self.trace {
+            trace.push(event);
+        }
+    }
+
+    fn value(&self, param: Param) -> i64 {
+        match param.mode {
+            Mode::Immediate => param.value,
+            _ => self.read(self.address(param)),
+        }
+    }
 
-            i += 4;
+    fn address(&self, param: Param) -> usize {
+        match param.mode {
+            Mode::Position => to_address(param.value),
+            Mode::Relative => to_address(self.relative_base + param.value),
+            Mode::Immediate => unreachable!("Immediate write parameters do not decode"),
         }
+    }
+}
+
+fn to_address(value: i64) -> usize {
+    usize::try_from(value).unwrap_or_else(|_| panic!("Negative Intcode address {value}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        input[0]
+    fn computer(program: &str) -> IntcodeComputer {
+        program.parse().unwrap()
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let mut vm = computer("1,9,10,3,2,3,11,0,99,30,40,50");
+        assert_eq!(vm.run(), State::Halted);
+        assert_eq!(vm.read(0), 3500);
+        assert_eq!(vm.step(), State::Halted);
+    }
+
+    #[test]
+    fn test_comparisons_and_jumps() {
+        // Outputs 999, 1000 or 1001 for inputs below, equal to or above 8
+        let vm = computer(
+            "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,1106,0,36,98,0,0,1002,21,125,\
+             20,4,20,1105,1,46,104,999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99",
+        );
+        for (input, expected) in [(7, 999), (8, 1000), (9, 1001)] {
+            assert_eq!(vm.clone().run_with([input]), [expected]);
+        }
+    }
+
+    #[test]
+    fn test_relative_base() {
+        let quine = "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99";
+        let output = computer(quine).run_with([]);
+        let program = quine
+            .split(',')
+            .map(|x| x.parse().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(output, program);
+
+        assert_eq!(
+            computer("104,1125899906842624,99").run_with([]),
+            [1125899906842624]
+        );
+    }
+
+    #[test]
+    fn test_feedback_loop() {
+        let program = computer(
+            "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,\
+             0,0,5",
+        );
+        let mut amps = [9, 8, 7, 6, 5].map(|phase| {
+            let mut amp = program.clone();
+            amp.push_input(phase);
+            amp
+        });
+
+        // Every amplifier pauses for input after emitting one signal, until they halt
+        let mut signal = 0;
+        while !amps[4].is_halted() {
+            for amp in &mut amps {
+                signal = *amp.run_with([signal]).last().unwrap();
+            }
+        }
+        assert_eq!(signal, 139629729);
+    }
+
+    #[test]
+    fn test_needs_input() {
+        let mut vm = computer("3,0,4,0,99");
+        assert_eq!(vm.run(), State::NeedsInput);
+        vm.push_input(42);
+        assert_eq!(vm.run_until_output(), State::Output(42));
+        assert_eq!(vm.run(), State::Halted);
+        assert_eq!(vm.take_output(), [42]);
+    }
+
+    #[test]
+    fn test_fault() {
+        let mut vm = computer("1101,2,3,5,104,0,1103,0");
+        let fault = State::Fault {
+            code: 1103,
+            address: 6,
+        };
+        assert_eq!(vm.run(), fault);
+        assert_eq!(vm.take_output(), [5]);
+        assert_eq!(vm.step(), fault);
+        assert_eq!(
+            computer("42").step(),
+            State::Fault {
+                code: 42,
+                address: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_disassemble() {
+        let listing = disassemble(&[1002, 4, 3, 4, 33, 109, -2, 21101, 1, 2, 0, 4, 6, 99]);
+        assert_eq!(
+            listing,
+            [
+                "    0: mul  [4], 3 -> [4]",
+                "    4: data 33",
+                "    5: arb  -2",
+                "    7: add  1, 2 -> [rb+0]",
+                "   11: out  [6]",
+                "   13: halt",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trace() {
+        let mut vm = computer("3,0,4,0,99");
+        vm.set_tracing(true);
+        assert_eq!(vm.run_with([7]), [7]);
+        assert_eq!(vm.ip(), 4);
+
+        let trace = vm.take_trace();
+        assert_eq!(trace.len(), 6);
+        assert_eq!(trace[1], Event::Input(7));
+        assert_eq!(
+            trace[2],
+            Event::Write {
+                address: 0,
+                value: 7
+            }
+        );
+        assert_eq!(trace[4], Event::Output(7));
+        assert_eq!(trace[5].to_string(), "    4: halt");
+        assert!(vm.trace().is_empty());
     }
 }
@@ -1,3 +1,8 @@
+//! Integer intervals and sets of them.
+//!
+//! All intervals are half-open, `[start, end)`, like Rust's `start..end`: `start` is
+//! included, `end` is not, and an interval with `start >= end` is empty. Inclusive input
+//! ranges such as `3-7` become `Interval::new(3, 8)`.
 use crate::num::Integer;
 use std::cmp::{max, min};
 
@@ -52,19 +57,37 @@ where
         Self { start, end }
     }
 
+    /// Number of values in the interval, zero if empty.
     #[inline]
     pub fn len(&self) -> T {
-        self.end - self.start
+        if self.is_empty() {
+            T::ZERO
+        } else {
+            self.end - self.start
+        }
     }
 
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    /// Whether `other` lies completely inside this interval.
     #[inline]
     pub fn contains(&self, other: &Self) -> bool {
         self.start <= other.start && self.end >= other.end
     }
 
+    #[inline]
+    pub fn contains_point(&self, x: T) -> bool {
+        self.start <= x && x < self.end
+    }
+
+    /// Whether the intervals share at least one value. Touching intervals such as
+    /// `[1, 3)` and `[3, 5)` do not.
     #[inline]
     pub fn intersects(&self, other: &Self) -> bool {
-        max(self.start, other.start) <= min(self.end, other.end)
+        max(self.start, other.start) < min(self.end, other.end)
     }
 
     #[inline]
@@ -90,6 +113,203 @@ where
     }
 }
 
+/// A set of values stored as sorted, disjoint and non-adjacent intervals.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct IntervalSet<T = i32>
+where
+    T: Integer,
+{
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T>
+where
+    T: Integer,
+{
+    fn default() -> Self {
+        Self { intervals: vec![] }
+    }
+}
+
+impl<T> From<Interval<T>> for IntervalSet<T>
+where
+    T: Integer,
+{
+    fn from(interval: Interval<T>) -> Self {
+        let mut set = Self::new();
+        set.insert(interval);
+        set
+    }
+}
+
+impl<T> FromIterator<Interval<T>> for IntervalSet<T>
+where
+    T: Integer,
+{
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T> Extend<Interval<T>> for IntervalSet<T>
+where
+    T: Integer,
+{
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, iter: I) {
+        for interval in iter {
+            self.insert(interval);
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T>
+where
+    T: Integer,
+{
+    type Item = &'a Interval<T>;
+    type IntoIter = std::slice::Iter<'a, Interval<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.iter()
+    }
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Integer,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The disjoint intervals in increasing order.
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Interval<T>> {
+        self.intervals.iter()
+    }
+
+    /// Total number of values in the set.
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::ZERO, |acc, interval| acc + interval.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.intervals.last().map(|interval| interval.end - T::ONE)
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        let i = self.intervals.partition_point(|interval| interval.end <= x);
+        self.intervals
+            .get(i)
+            .is_some_and(|interval| interval.contains_point(x))
+    }
+
+    /// Add all values of `interval`, merging it with overlapping and adjacent intervals.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        let lo = self.intervals.partition_point(|i| i.end < interval.start);
+        let hi = self.intervals.partition_point(|i| i.start <= interval.end);
+        let mut merged = interval;
+        if lo < hi {
+            merged.start = min(merged.start, self.intervals[lo].start);
+            merged.end = max(merged.end, self.intervals[hi - 1].end);
+        }
+        self.intervals.splice(lo..hi, [merged]);
+    }
+
+    /// Remove all values of `interval`, splitting intervals which only partially overlap.
+    pub fn remove(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        let lo = self.intervals.partition_point(|i| i.end <= interval.start);
+        let hi = self.intervals.partition_point(|i| i.start < interval.end);
+        if lo == hi {
+            return;
+        }
+
+        let (first, last) = (self.intervals[lo], self.intervals[hi - 1]);
+        let left = Interval::try_from((first.start, interval.start)).ok();
+        let right = Interval::try_from((interval.end, last.end)).ok();
+        self.intervals.splice(lo..hi, left.into_iter().chain(right));
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+        set.extend(other.iter().copied());
+        set
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+        for &interval in other {
+            set.remove(interval);
+        }
+        set
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut i, mut j) = (0, 0);
+        let mut intervals = vec![];
+
+        while let (Some(a), Some(b)) = (self.intervals.get(i), other.intervals.get(j)) {
+            intervals.extend(a.intersect(b));
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        // Pieces of disjoint, non-adjacent intervals are disjoint and non-adjacent as well
+        Self { intervals }
+    }
+
+    /// All values within `bounds` which are not in the set.
+    pub fn complement(&self, bounds: Interval<T>) -> Self {
+        Self::from(bounds).difference(self)
+    }
+
+    /// Map values through a list of `(source, destination start)` rules, shifting every
+    /// value inside a `source` interval by `destination - source.start`. The first rule
+    /// covering a value wins and values not covered by any rule map to themselves.
+    pub fn map_ranges(&self, rules: impl IntoIterator<Item = (Interval<T>, T)>) -> Self {
+        let mut unmapped = self.clone();
+        let mut mapped = Self::new();
+
+        for (source, dest) in rules {
+            for part in unmapped.intersection(&Self::from(source)).iter() {
+                mapped.insert(Interval::new(
+                    dest + (part.start - source.start),
+                    dest + (part.end - source.start),
+                ));
+            }
+            unmapped.remove(source);
+        }
+
+        mapped.union(&unmapped)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,4 +356,85 @@ mod tests {
         assert_eq!(union_parts.center, Some(Interval::new(2, 6)));
         assert_eq!(union_parts.right, Some(Interval::new(6, 10)));
     }
+
+    #[test]
+    fn test_endpoints() {
+        let a = Interval::new(1, 3);
+        assert!(a.contains_point(1));
+        assert!(!a.contains_point(3));
+        assert!(!a.intersects(&Interval::new(3, 5)));
+        assert!(a.intersects(&Interval::new(2, 5)));
+        assert_eq!(a.intersect(&Interval::new(3, 5)), None);
+        assert!(Interval::new(4, 2).is_empty());
+        assert_eq!(Interval::new(4, 2).len(), 0);
+    }
+
+    #[test]
+    fn test_set_insert_remove() {
+        let mut set = IntervalSet::new();
+        set.insert(Interval::new(5, 8));
+        set.insert(Interval::new(0, 2));
+        set.insert(Interval::new(2, 3));
+        set.insert(Interval::new(10, 10));
+        assert_eq!(set.intervals(), [Interval::new(0, 3), Interval::new(5, 8)]);
+        assert_eq!(set.len(), 6);
+
+        set.insert(Interval::new(1, 6));
+        assert_eq!(set.intervals(), [Interval::new(0, 8)]);
+
+        set.remove(Interval::new(2, 4));
+        set.remove(Interval::new(7, 20));
+        assert_eq!(set.intervals(), [Interval::new(0, 2), Interval::new(4, 7)]);
+        assert!(set.contains(1));
+        assert!(!set.contains(2));
+        assert!(!set.contains(7));
+        assert_eq!((set.min(), set.max()), (Some(0), Some(6)));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = IntervalSet::from_iter([Interval::new(0, 4), Interval::new(6, 10)]);
+        let b = IntervalSet::from_iter([Interval::new(2, 7), Interval::new(9, 12)]);
+
+        assert_eq!(a.union(&b).intervals(), [Interval::new(0, 12)]);
+        assert_eq!(
+            a.intersection(&b).intervals(),
+            [
+                Interval::new(2, 4),
+                Interval::new(6, 7),
+                Interval::new(9, 10)
+            ]
+        );
+        assert_eq!(
+            a.difference(&b).intervals(),
+            [Interval::new(0, 2), Interval::new(7, 9)]
+        );
+        assert_eq!(
+            a.complement(Interval::new(-5, 8)).intervals(),
+            [Interval::new(-5, 0), Interval::new(4, 6)]
+        );
+    }
+
+    #[test]
+    fn test_map_ranges() {
+        // The seed-to-soil map of 2023/5: 98..100 -> 50..52, 50..98 -> 52..100
+        let seeds = IntervalSet::from_iter([Interval::new(79u64, 93), Interval::new(55, 68)]);
+        let rules = [(Interval::new(98, 100), 50), (Interval::new(50, 98), 52)];
+        assert_eq!(
+            seeds.map_ranges(rules).intervals(),
+            [Interval::new(57, 70), Interval::new(81, 95)]
+        );
+
+        // Unmapped values stay where they are
+        let set = IntervalSet::from(Interval::new(0, 10));
+        let shifted = set.map_ranges([(Interval::new(5, 8), 100)]);
+        assert_eq!(
+            shifted.intervals(),
+            [
+                Interval::new(0, 5),
+                Interval::new(8, 10),
+                Interval::new(100, 103)
+            ]
+        );
+    }
 }
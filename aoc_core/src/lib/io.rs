@@ -81,7 +81,7 @@ fn download_file(url: &str, session_cookie: &str) -> io::Result<String> {
                 Check if the session cookie is up to date",
                 err
             );
-            return Err(io::Error::new(io::ErrorKind::Other, error));
+            return Err(io::Error::other(error));
         }
     };
     let body = response.into_string().unwrap_or_default();
@@ -110,10 +110,7 @@ pub fn get_aoc_input(
         create_store(&cache_dir)?;
 
         if session_cookie.is_none() {
-            return Err(io::Error::new(
-                io::ErrorKind::Other,
-                "Empty AoC session cookie",
-            ));
+            return Err(io::Error::other("Empty AoC session cookie"));
         }
         let puzzle_input = download_aoc_input(session_cookie.unwrap(), year, day)?;
         save_to_cache(cache_dir, year, day, &puzzle_input)?;
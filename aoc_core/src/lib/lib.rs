@@ -1,12 +1,25 @@
+pub mod circuit;
 pub mod cli;
 pub mod counter;
+pub mod cycle;
 pub mod date;
+pub mod direction;
+pub mod dsu;
+pub mod expr;
 pub mod graph;
+pub mod grid;
 pub mod heap;
 pub mod intcode;
 pub mod interval;
 pub mod io;
+pub mod linalg;
 pub mod macros;
+pub mod math;
 pub mod num;
+pub mod ocr;
+pub mod point;
+pub mod pulse;
+pub mod search;
 pub mod solution;
 pub mod utils;
+pub mod vm;
@@ -0,0 +1,641 @@
+//! Exact linear algebra over the rationals, for puzzles which boil down to a system of
+//! linear equations and must not depend on floating point rounding.
+//!
+//! [`Rational`] is backed by `i128` and kept in lowest terms. Arithmetic panics on
+//! overflow instead of silently wrapping, so keep the magnitudes of the inputs moderate;
+//! cross products of puzzle-sized coordinates fit comfortably.
+use crate::math::{gcd, lcm};
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use std::ops::{Add, AddAssign, Div, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+
+static OVERFLOW: &str = "Rational arithmetic overflows i128";
+
+#[inline]
+fn mul(a: i128, b: i128) -> i128 {
+    a.checked_mul(b).expect(OVERFLOW)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    pub const ZERO: Self = Self { num: 0, den: 1 };
+    pub const ONE: Self = Self { num: 1, den: 1 };
+
+    /// `num / den` in lowest terms with a positive denominator. Panics if `den` is zero.
+    pub fn new(num: i128, den: i128) -> Self {
+        assert!(den != 0, "Rational with a zero denominator");
+        let g = gcd(num, den);
+        let sign = den.signum();
+        Self {
+            num: sign * num / g,
+            den: sign * den / g,
+        }
+    }
+
+    pub fn numer(&self) -> i128 {
+        self.num
+    }
+
+    pub fn denom(&self) -> i128 {
+        self.den
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.num == 0
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+
+    pub fn to_integer(&self) -> Option<i128> {
+        self.is_integer().then_some(self.num)
+    }
+
+    pub fn abs(&self) -> Self {
+        Self {
+            num: self.num.abs(),
+            den: self.den,
+        }
+    }
+}
+
+impl Default for Rational {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Rational {
+            fn from(value: $t) -> Self {
+                Self { num: value as i128, den: 1 }
+            }
+        })*
+    };
+}
+
+impl_from_int!(i8, i16, i32, i64, i128, u8, u16, u32, u64, usize, isize);
+
+impl Add for Rational {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        // Reduce by the common factor of the denominators first to keep products small
+        let g = gcd(self.den, rhs.den);
+        let num = mul(self.num, rhs.den / g)
+            .checked_add(mul(rhs.num, self.den / g))
+            .expect(OVERFLOW);
+        Self::new(num, mul(self.den / g, rhs.den))
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let g1 = gcd(self.num, rhs.den).max(1);
+        let g2 = gcd(rhs.num, self.den).max(1);
+        Self::new(
+            mul(self.num / g1, rhs.num / g2),
+            mul(self.den / g2, rhs.den / g1),
+        )
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        assert!(!rhs.is_zero(), "Division of a rational by zero");
+        self * Self::new(rhs.den, rhs.num)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+impl AddAssign for Rational {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Rational {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        mul(self.num, other.den).cmp(&mul(other.num, self.den))
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.den {
+            1 => write!(f, "{}", self.num),
+            den => write!(f, "{}/{den}", self.num),
+        }
+    }
+}
+
+/// Dense matrix of rationals, indexed by `(row, col)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<Rational>,
+}
+
+impl Matrix {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![Rational::ZERO; rows * cols],
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::zeros(n, n);
+        for i in 0..n {
+            m[(i, i)] = Rational::ONE;
+        }
+        m
+    }
+
+    /// Build a matrix from rows of anything convertible to [`Rational`]. Panics if the
+    /// rows differ in length.
+    pub fn from_rows<R, T>(rows: impl IntoIterator<Item = R>) -> Self
+    where
+        R: IntoIterator<Item = T>,
+        T: Into<Rational>,
+    {
+        let (mut n_rows, mut cols, mut data) = (0, None, vec![]);
+
+        for row in rows {
+            let before = data.len();
+            data.extend(row.into_iter().map(Into::into));
+            let len = data.len() - before;
+            assert_eq!(*cols.get_or_insert(len), len, "Ragged matrix row {n_rows}");
+            n_rows += 1;
+        }
+
+        Self {
+            rows: n_rows,
+            cols: cols.unwrap_or(0),
+            data,
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn row(&self, row: usize) -> &[Rational] {
+        &self.data[row * self.cols..(row + 1) * self.cols]
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        for col in 0..self.cols {
+            self.data.swap(a * self.cols + col, b * self.cols + col);
+        }
+    }
+
+    /// `self` with `b` appended as an extra column.
+    fn augment(&self, b: &[Rational]) -> Self {
+        assert_eq!(
+            b.len(),
+            self.rows,
+            "Right-hand side length differs from row count"
+        );
+        Self::from_rows((0..self.rows).map(|r| self.row(r).iter().copied().chain([b[r]])))
+    }
+
+    /// Reduced row echelon form together with the pivot column of every non-zero row.
+    pub fn rref(&self) -> (Self, Vec<usize>) {
+        let mut m = self.clone();
+        let mut pivots = vec![];
+
+        for col in 0..m.cols {
+            let row = pivots.len();
+            if row == m.rows {
+                break;
+            }
+            let Some(p) = (row..m.rows).find(|&r| !m[(r, col)].is_zero()) else {
+                continue;
+            };
+            m.swap_rows(row, p);
+
+            let pivot = m[(row, col)];
+            for c in col..m.cols {
+                m[(row, c)] = m[(row, c)] / pivot;
+            }
+
+            for r in (0..m.rows).filter(|&r| r != row) {
+                let factor = m[(r, col)];
+                if factor.is_zero() {
+                    continue;
+                }
+                for c in col..m.cols {
+                    let delta = factor * m[(row, c)];
+                    m[(r, c)] -= delta;
+                }
+            }
+            pivots.push(col);
+        }
+
+        (m, pivots)
+    }
+
+    pub fn rank(&self) -> usize {
+        self.rref().1.len()
+    }
+
+    /// Determinant of a square matrix, `None` otherwise.
+    pub fn determinant(&self) -> Option<Rational> {
+        if self.rows != self.cols {
+            return None;
+        }
+
+        let mut m = self.clone();
+        let mut det = Rational::ONE;
+
+        for col in 0..m.cols {
+            let Some(p) = (col..m.rows).find(|&r| !m[(r, col)].is_zero()) else {
+                return Some(Rational::ZERO);
+            };
+            if p != col {
+                m.swap_rows(col, p);
+                det = -det;
+            }
+
+            let pivot = m[(col, col)];
+            det = det * pivot;
+            for r in col + 1..m.rows {
+                let factor = m[(r, col)] / pivot;
+                for c in col..m.cols {
+                    let delta = factor * m[(col, c)];
+                    m[(r, c)] -= delta;
+                }
+            }
+        }
+
+        Some(det)
+    }
+
+    /// Basis of the solutions of `self * x = 0`, one vector per free variable.
+    pub fn nullspace(&self) -> Vec<Vec<Rational>> {
+        let (r, pivots) = self.rref();
+        Self::basis(&r, &pivots, self.cols)
+    }
+
+    fn basis(r: &Self, pivots: &[usize], cols: usize) -> Vec<Vec<Rational>> {
+        (0..cols)
+            .filter(|col| !pivots.contains(col))
+            .map(|free| {
+                let mut v = vec![Rational::ZERO; cols];
+                v[free] = Rational::ONE;
+                for (row, &p) in pivots.iter().enumerate() {
+                    v[p] = -r[(row, free)];
+                }
+                v
+            })
+            .collect()
+    }
+
+    /// All solutions of `self * x = b` as a particular solution (free variables set to
+    /// zero) plus a basis of the nullspace, or `None` if the system is inconsistent.
+    pub fn solution_space(&self, b: &[Rational]) -> Option<(Vec<Rational>, Vec<Vec<Rational>>)> {
+        let (r, pivots) = self.augment(b).rref();
+        if pivots.last() == Some(&self.cols) {
+            return None;
+        }
+
+        let mut x = vec![Rational::ZERO; self.cols];
+        for (row, &p) in pivots.iter().enumerate() {
+            x[p] = r[(row, self.cols)];
+        }
+
+        Some((x, Self::basis(&r, &pivots, self.cols)))
+    }
+
+    /// The unique solution of `self * x = b`, or `None` if there are none or infinitely many.
+    pub fn solve(&self, b: &[Rational]) -> Option<Vec<Rational>> {
+        let (x, nullspace) = self.solution_space(b)?;
+        nullspace.is_empty().then_some(x)
+    }
+
+    /// Minimise `cost · x` over integer solutions of `self * x = b` with
+    /// `0 <= x[i] <= upper[i]`. Returns the minimal cost and a solution achieving it.
+    ///
+    /// Runs a bounded search over the free variables, so it is only practical when there
+    /// are few of them or their bounds are small.
+    pub fn min_integer_solution(
+        &self,
+        b: &[i128],
+        upper: &[i128],
+        cost: &[i128],
+    ) -> Option<(i128, Vec<i128>)> {
+        let b = b.iter().copied().map(Rational::from).collect::<Vec<_>>();
+        let (r, pivots) = self.augment(&b).rref();
+        if pivots.last() == Some(&self.cols) {
+            return None;
+        }
+
+        // Branch on the most constrained free variables first
+        let mut free = (0..self.cols)
+            .filter(|col| !pivots.contains(col))
+            .collect::<Vec<_>>();
+        free.sort_by_key(|&f| upper[f]);
+
+        // Scale every row to integers: scale * x[pivot] + sum(coeffs[k] * x[free[k]]) = rhs
+        let rows = pivots
+            .iter()
+            .enumerate()
+            .map(|(row, &pivot)| {
+                let scale = free
+                    .iter()
+                    .chain([&self.cols])
+                    .fold(1, |acc, &c| lcm(acc, r[(row, c)].denom()));
+                let int = |c: usize| (r[(row, c)] * Rational::from(scale)).numer();
+                let coeffs = free.iter().map(|&f| int(f)).collect::<Vec<_>>();
+
+                // Range of the free variables' contribution from the k-th one onwards
+                let mut reach = vec![(0, 0); free.len() + 1];
+                for k in (0..free.len()).rev() {
+                    let term = coeffs[k] * upper[free[k]];
+                    let (lo, hi) = reach[k + 1];
+                    reach[k] = (lo + term.min(0), hi + term.max(0));
+                }
+
+                ScaledRow {
+                    pivot,
+                    scale,
+                    coeffs,
+                    rhs: int(self.cols),
+                    reach,
+                    sum: 0,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // Substituting the pivots makes the objective affine in the free variables:
+        // denom * cost·x = offset + sum(reduced[k] * x[free[k]])
+        let denom = rows.iter().fold(1, |acc, row| lcm(acc, row.scale));
+        let weight = |row: &ScaledRow| cost[row.pivot] * (denom / row.scale);
+        let offset = rows.iter().map(|row| weight(row) * row.rhs).sum();
+        let reduced = free
+            .iter()
+            .enumerate()
+            .map(|(k, &f)| {
+                let pivots = rows.iter().map(|row| weight(row) * row.coeffs[k]);
+                denom * cost[f] - pivots.sum::<i128>()
+            })
+            .collect::<Vec<_>>();
+
+        // Lowest possible contribution of the free variables from the k-th one onwards
+        let mut optimistic = vec![0; free.len() + 1];
+        for k in (0..free.len()).rev() {
+            optimistic[k] = optimistic[k + 1] + (reduced[k] * upper[free[k]]).min(0);
+        }
+
+        let mut search = BoundedSearch {
+            rows,
+            free,
+            upper,
+            cost,
+            denom,
+            reduced,
+            optimistic,
+            x: vec![0; self.cols],
+            best: None,
+        };
+        search.run(0, offset);
+        search.best
+    }
+}
+
+/// Range of integers `v` with `low <= c * v <= high`, unbounded when `c` is zero.
+fn multiples_between(c: i128, low: i128, high: i128) -> (i128, i128) {
+    match c.signum() {
+        0 if low <= 0 && 0 <= high => (i128::MIN, i128::MAX),
+        0 => (1, 0),
+        1 => (-(-low).div_euclid(c), high.div_euclid(c)),
+        _ => multiples_between(-c, -high, -low),
+    }
+}
+
+struct ScaledRow {
+    pivot: usize,
+    scale: i128,
+    coeffs: Vec<i128>,
+    rhs: i128,
+    reach: Vec<(i128, i128)>,
+    /// Contribution of the free variables assigned so far
+    sum: i128,
+}
+
+struct BoundedSearch<'a> {
+    rows: Vec<ScaledRow>,
+    free: Vec<usize>,
+    upper: &'a [i128],
+    cost: &'a [i128],
+    denom: i128,
+    reduced: Vec<i128>,
+    optimistic: Vec<i128>,
+    x: Vec<i128>,
+    best: Option<(i128, Vec<i128>)>,
+}
+
+impl BoundedSearch<'_> {
+    /// Can a solution with the given (scaled) objective bound still beat the best one?
+    fn improves(&self, bound: i128) -> bool {
+        self.best
+            .as_ref()
+            .is_none_or(|(best, _)| bound < self.denom * best)
+    }
+
+    fn run(&mut self, k: usize, objective: i128) {
+        if !self.improves(objective + self.optimistic[k]) {
+            return;
+        }
+
+        // Prune unless every pivot can still land within its bounds
+        let feasible = self.rows.iter().all(|row| {
+            let (lo, hi) = row.reach[k];
+            row.sum + lo <= row.rhs && row.sum + hi >= row.rhs - row.scale * self.upper[row.pivot]
+        });
+        if !feasible {
+            return;
+        }
+
+        if k < self.free.len() {
+            let f = self.free[k];
+            let base = self.rows.iter().map(|row| row.sum).collect::<Vec<_>>();
+
+            // Narrow the range so that every pivot can still land within its bounds
+            let (mut min, mut max) = (0, self.upper[f]);
+            for row in &self.rows {
+                let (lo, hi) = row.reach[k + 1];
+                let low = row.rhs - row.scale * self.upper[row.pivot] - row.sum - hi;
+                let high = row.rhs - row.sum - lo;
+                let (a, b) = multiples_between(row.coeffs[k], low, high);
+                (min, max) = (min.max(a), max.min(b));
+            }
+
+            // Visit values from the cheapest, so the objective only grows from here
+            let rc = self.reduced[k];
+            for i in 0..=(max - min) {
+                let value = if rc >= 0 { min + i } else { max - i };
+                let objective = objective + rc * value;
+                if !self.improves(objective + self.optimistic[k + 1]) {
+                    break;
+                }
+                for (row, base) in self.rows.iter_mut().zip(&base) {
+                    row.sum = base + row.coeffs[k] * value;
+                }
+                self.x[f] = value;
+                self.run(k + 1, objective);
+            }
+
+            for (row, base) in self.rows.iter_mut().zip(base) {
+                row.sum = base;
+            }
+            return;
+        }
+
+        for row in &self.rows {
+            let num = row.rhs - row.sum;
+            if num % row.scale != 0 {
+                return;
+            }
+            self.x[row.pivot] = num / row.scale;
+        }
+
+        let total = self.x.iter().zip(self.cost).map(|(x, c)| x * c).sum();
+        if self.best.as_ref().is_none_or(|(best, _)| total < *best) {
+            self.best = Some((total, self.x.clone()));
+        }
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = Rational;
+
+    #[inline]
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        &self.data[row * self.cols + col]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    #[inline]
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        &mut self.data[row * self.cols + col]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn q(num: i128, den: i128) -> Rational {
+        Rational::new(num, den)
+    }
+
+    #[test]
+    fn test_rational() {
+        assert_eq!(q(2, -4), q(-1, 2));
+        assert_eq!(q(1, 2) + q(1, 3), q(5, 6));
+        assert_eq!(q(1, 2) - q(3, 4), q(-1, 4));
+        assert_eq!(q(2, 3) * q(9, 4), q(3, 2));
+        assert_eq!(q(2, 3) / q(4, 9), q(3, 2));
+        assert!(q(1, 3) < q(1, 2));
+        assert_eq!(q(6, 3).to_integer(), Some(2));
+        assert_eq!(q(7, 3).to_string(), "7/3");
+    }
+
+    #[test]
+    fn test_solve() {
+        // 3x + 2y + z = 39, 2x + 3y + z = 34, x + 2y + 3z = 26
+        let a = Matrix::from_rows([[3, 2, 1], [2, 3, 1], [1, 2, 3]]);
+        let b = [39, 34, 26].map(Rational::from);
+        assert_eq!(a.solve(&b), Some(vec![q(37, 4), q(17, 4), q(11, 4)]));
+        assert_eq!(a.determinant(), Some(q(12, 1)));
+        assert_eq!(a.rank(), 3);
+    }
+
+    #[test]
+    fn test_singular() {
+        let a = Matrix::from_rows([[1, 2, 3], [2, 4, 6], [1, 0, 1]]);
+        assert_eq!(a.determinant(), Some(Rational::ZERO));
+        assert_eq!(a.rank(), 2);
+
+        let nullspace = a.nullspace();
+        assert_eq!(nullspace, vec![vec![q(-1, 1), q(-1, 1), Rational::ONE]]);
+
+        let b = [6, 12, 2].map(Rational::from);
+        assert_eq!(a.solve(&b), None);
+        let (x, basis) = a.solution_space(&b).unwrap();
+        assert_eq!(x, vec![q(2, 1), q(2, 1), Rational::ZERO]);
+        assert_eq!(basis, nullspace);
+
+        assert!(a.solution_space(&[6, 13, 2].map(Rational::from)).is_none());
+    }
+
+    #[test]
+    fn test_min_integer_solution() {
+        // x + y + z = 10 and x - y = 2 with z <= 3, as cheaply as possible with x costing 2
+        let a = Matrix::from_rows([[1, 1, 1], [1, -1, 0]]);
+        let (cost, x) = a
+            .min_integer_solution(&[10, 2], &[10, 10, 3], &[2, 1, 1])
+            .unwrap();
+        assert_eq!((cost, x), (15, vec![5, 3, 2]));
+
+        // Negative costs maximise instead
+        let (cost, _) = a
+            .min_integer_solution(&[10, 2], &[10, 10, 3], &[-1, -1, 0])
+            .unwrap();
+        assert_eq!(cost, -10);
+
+        // Only half-integer solutions
+        let a = Matrix::from_rows([[2, 2]]);
+        assert!(a.min_integer_solution(&[3], &[5, 5], &[1, 1]).is_none());
+    }
+}
@@ -0,0 +1,269 @@
+//! Number theory helpers generic over [`Integer`]: gcd/lcm, extended Euclid, modular
+//! arithmetic, the [Chinese remainder theorem] and integer square roots.
+//!
+//! Modular functions expect a positive modulus `m` and never overflow as long as `2 * m`
+//! fits the integer type. Products which would overflow are computed by doubling instead.
+//!
+//! [Chinese remainder theorem]: https://en.wikipedia.org/wiki/Chinese_remainder_theorem
+use crate::num::{Integer, Signed};
+
+/// Absolute value, or `None` for the minimum of a signed type.
+#[inline]
+fn checked_abs<T: Integer>(a: T) -> Option<T> {
+    if a < T::ZERO {
+        T::ZERO.checked_sub(a)
+    } else {
+        Some(a)
+    }
+}
+
+/// Greatest common divisor, always non-negative. Panics if an argument is the minimum of
+/// a signed type, whose absolute value does not fit.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let abs = |n| checked_abs(n).expect("Absolute value overflows");
+    let (mut a, mut b) = (abs(a), abs(b));
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, always non-negative. Panics on overflow.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    checked_lcm(a, b).expect("Least common multiple overflows")
+}
+
+/// Least common multiple, or `None` if it does not fit the type.
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    let (a, b) = (checked_abs(a)?, checked_abs(b)?);
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Extended Euclidean algorithm: `(g, x, y)` such that `a * x + b * y = g = gcd(a, b)`.
+pub fn extended_gcd<T: Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (T::ONE, T::ZERO);
+    let (mut old_t, mut t) = (T::ZERO, T::ONE);
+
+    while r != T::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+        (old_t, t) = (t, old_t - q * t);
+    }
+
+    if old_r < T::ZERO {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// Non-negative remainder of `a` modulo `m`.
+#[inline]
+pub fn modulo<T: Integer>(a: T, m: T) -> T {
+    let r = a % m;
+    if r < T::ZERO { r + m } else { r }
+}
+
+#[inline]
+fn add_mod<T: Integer>(a: T, b: T, m: T) -> T {
+    // Both operands are already reduced, so a + b < 2m, but it might not fit the type
+    if a >= m - b { a - (m - b) } else { a + b }
+}
+
+/// `a * b mod m` without overflowing the intermediate product.
+pub fn mul_mod<T: Integer>(a: T, b: T, m: T) -> T {
+    let (mut a, mut b) = (modulo(a, m), modulo(b, m));
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+
+    let two = T::ONE + T::ONE;
+    let mut result = T::ZERO;
+    while b > T::ZERO {
+        if b % two == T::ONE {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b = b / two;
+    }
+    result
+}
+
+/// `base ^ exp mod m` by repeated squaring. `exp` must be non-negative.
+pub fn mod_pow<T: Integer>(base: T, exp: T, m: T) -> T {
+    let two = T::ONE + T::ONE;
+    let (mut base, mut exp) = (modulo(base, m), exp);
+    let mut result = T::ONE % m;
+
+    while exp > T::ZERO {
+        if exp % two == T::ONE {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp = exp / two;
+    }
+    result
+}
+
+/// Multiplicative inverse of `a` modulo `m`, if `a` and `m` are coprime.
+pub fn mod_inverse<T: Signed>(a: T, m: T) -> Option<T> {
+    let (g, x, _) = extended_gcd(modulo(a, m), m);
+    (g == T::ONE).then(|| modulo(x, m))
+}
+
+/// Solve the system `x ≡ a (mod m)` for all `(a, m)` pairs. The moduli need not be
+/// coprime. Returns the smallest non-negative solution and the lcm of the moduli, or `None`
+/// if the congruences are inconsistent or the lcm does not fit the type.
+pub fn crt<T: Signed>(congruences: impl IntoIterator<Item = (T, T)>) -> Option<(T, T)> {
+    let (mut x, mut m) = (T::ZERO, T::ONE);
+
+    for (a, n) in congruences {
+        let a = modulo(a, n);
+        let g = gcd(m, n);
+        let diff = a - x;
+        if diff % g != T::ZERO {
+            return None;
+        }
+
+        // Solve m * t ≡ diff (mod n), reduced by the common factor g
+        let n_g = n / g;
+        let inv = mod_inverse(m / g, n_g)?;
+        let t = mul_mod(diff / g, inv, n_g);
+        let lcm = (m / g).checked_mul(n)?;
+
+        // m * t < m * n_g = lcm, so neither step overflows
+        x = x + m * t;
+        m = lcm;
+    }
+
+    Some((x, m))
+}
+
+/// Integer square root: the largest `r` with `r * r <= n`. Panics if `n` is negative.
+pub fn isqrt<T: Integer>(n: T) -> T {
+    checked_isqrt(n).expect("Square root of a negative number")
+}
+
+/// Integer square root, or `None` if `n` is negative.
+pub fn checked_isqrt<T: Integer>(n: T) -> Option<T> {
+    if n < T::ZERO {
+        return None;
+    }
+
+    let two = T::ONE + T::ONE;
+    if n < two {
+        return Some(n);
+    }
+
+    // Newton's method from above; n / 2 + 1 >= sqrt(n) and keeps x + n / x from overflowing
+    let mut x = n / two + T::ONE;
+    loop {
+        let y = (x + n / x) / two;
+        if y >= x {
+            return Some(x);
+        }
+        x = y;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0u8, 7), 7);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!(checked_lcm(u8::MAX, 2), None);
+        assert_eq!(checked_lcm(i8::MIN, 2), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Absolute value overflows")]
+    fn test_gcd_min() {
+        gcd(i8::MIN, 2);
+    }
+
+    #[test]
+    fn test_modular() {
+        assert_eq!(modulo(-7, 3), 2);
+        assert_eq!(mod_pow(2u64, 10, 1000), 24);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(4, 8), None);
+        let m = u64::MAX / 2;
+        assert_eq!(mul_mod(m - 1, m - 1, m), 1);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // Non-coprime moduli
+        assert_eq!(crt([(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt::<i64>([]), Some((0, 1)));
+        assert_eq!(crt([(0, i8::MAX), (0, i8::MAX - 1)]), None);
+    }
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16u32), 4);
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+        assert_eq!(checked_isqrt(-1), None);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_extended_gcd(a in -10_000i64..10_000, b in -10_000i64..10_000) {
+            let (g, x, y) = extended_gcd(a, b);
+            prop_assert_eq!(g, gcd(a, b));
+            prop_assert_eq!(a * x + b * y, g);
+        }
+
+        #[test]
+        fn prop_mod_inverse(a in -10_000i64..10_000, m in 1i64..10_000) {
+            match mod_inverse(a, m) {
+                Some(inv) => prop_assert_eq!(modulo(a * inv, m), 1 % m),
+                None => prop_assert!(gcd(a, m) != 1),
+            }
+        }
+
+        #[test]
+        fn prop_mod_pow(base in any::<u64>(), exp in 0u64..64, m in 1u64..=u64::MAX / 2) {
+            let naive = (0..exp).fold(1 % m as u128, |acc, _| acc * (base % m) as u128 % m as u128);
+            prop_assert_eq!(mod_pow(base, exp, m) as u128, naive);
+        }
+
+        #[test]
+        fn prop_crt(eqs in prop::collection::vec((0i64..100, 1i64..30), 1..4)) {
+            let brute = |bound: i64| (0..bound).find(|x| eqs.iter().all(|&(a, m)| x % m == a % m));
+            let bound = eqs.iter().fold(1, |acc, &(_, m)| lcm(acc, m));
+
+            match crt(eqs.iter().copied()) {
+                Some((x, m)) => {
+                    prop_assert_eq!(m, bound);
+                    prop_assert_eq!(Some(x), brute(bound));
+                }
+                None => prop_assert_eq!(brute(bound), None),
+            }
+        }
+
+        #[test]
+        fn prop_isqrt(n in any::<u64>()) {
+            let r = isqrt(n) as u128;
+            prop_assert!(r * r <= n as u128);
+            prop_assert!((r + 1) * (r + 1) > n as u128);
+        }
+    }
+}
@@ -1,7 +1,6 @@
 use std::{
     fmt::{Debug, Display},
-    ops::{Add, Div, Mul, Sub},
-    u128,
+    ops::{Add, Div, Mul, Neg, Rem, Sub},
 };
 
 pub trait Unsigned: Display + Debug + Copy + Default {}
@@ -21,19 +20,51 @@ pub trait Integer:
     + Sub<Output = Self>
     + Mul<Output = Self>
     + Div<Output = Self>
+    + Rem<Output = Self>
     + PartialOrd
     + Ord
 {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+pub trait Signed: Integer + Neg<Output = Self> {}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            #[inline]
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            #[inline]
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+
+            #[inline]
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+        })*
+    };
 }
-impl Integer for u8 {}
-impl Integer for i8 {}
-impl Integer for u16 {}
-impl Integer for i16 {}
-impl Integer for u32 {}
-impl Integer for i32 {}
-impl Integer for u64 {}
-impl Integer for i64 {}
-impl Integer for u128 {}
-impl Integer for i128 {}
-impl Integer for usize {}
-impl Integer for isize {}
+
+macro_rules! impl_signed {
+    ($($t:ty),*) => {
+        $(impl Signed for $t {})*
+    };
+}
+
+impl_integer!(
+    u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize
+);
+impl_signed!(i8, i16, i32, i64, i128, isize);
@@ -0,0 +1,252 @@
+//! Recognition of the letters drawn by display puzzles (2021/13, 2022/10).
+//!
+//! Two fonts are known: letters 4 pixels wide and 6 tall, and letters 6 wide and 10 tall.
+//! The font is chosen by the height of the lit area. Letters are separated by columns
+//! without any lit pixel, so spacing and a frame of dark pixels do not matter.
+use crate::grid::Grid;
+use std::fmt;
+
+/// Letters 4 pixels wide and 6 tall, with rows separated by spaces.
+const SMALL: [(char, &str); 18] = [
+    ('A', ".##. #..# #..# #### #..# #..#"),
+    ('B', "###. #..# ###. #..# #..# ###."),
+    ('C', ".##. #..# #... #... #..# .##."),
+    ('E', "#### #... ###. #... #... ####"),
+    ('F', "#### #... ###. #... #... #..."),
+    ('G', ".##. #..# #... #.## #..# .###"),
+    ('H', "#..# #..# #### #..# #..# #..#"),
+    ('I', "### .#. .#. .#. .#. ###"),
+    ('J', "..## ...# ...# ...# #..# .##."),
+    ('K', "#..# #.#. ##.. #.#. #.#. #..#"),
+    ('L', "#... #... #... #... #... ####"),
+    ('O', ".##. #..# #..# #..# #..# .##."),
+    ('P', "###. #..# #..# ###. #... #..."),
+    ('R', "###. #..# #..# ###. #.#. #..#"),
+    ('S', ".### #... #... .##. ...# ###."),
+    ('U', "#..# #..# #..# #..# #..# .##."),
+    ('Y', "#...# #...# .#.#. ..#.. ..#.. ..#.."),
+    ('Z', "#### ...# ..#. .#.. #... ####"),
+];
+
+/// Letters 6 pixels wide and 10 tall, with rows separated by spaces.
+const LARGE: [(char, &str); 15] = [
+    (
+        'A',
+        "..##.. .#..#. #....# #....# #....# ###### #....# #....# #....# #....#",
+    ),
+    (
+        'B',
+        "#####. #....# #....# #....# #####. #....# #....# #....# #....# #####.",
+    ),
+    (
+        'C',
+        ".####. #....# #..... #..... #..... #..... #..... #..... #....# .####.",
+    ),
+    (
+        'E',
+        "###### #..... #..... #..... #####. #..... #..... #..... #..... ######",
+    ),
+    (
+        'F',
+        "###### #..... #..... #..... #####. #..... #..... #..... #..... #.....",
+    ),
+    (
+        'G',
+        ".####. #....# #..... #..... #..... #..### #....# #....# #...## .###.#",
+    ),
+    (
+        'H',
+        "#....# #....# #....# #....# ###### #....# #....# #....# #....# #....#",
+    ),
+    (
+        'J',
+        "...### ....#. ....#. ....#. ....#. ....#. ....#. #...#. #...#. .###..",
+    ),
+    (
+        'K',
+        "#....# #...#. #..#.. #.#... ##.... ##.... #.#... #..#.. #...#. #....#",
+    ),
+    (
+        'L',
+        "#..... #..... #..... #..... #..... #..... #..... #..... #..... ######",
+    ),
+    (
+        'N',
+        "#....# ##...# ##...# #.#..# #.#..# #..#.# #..#.# #...## #...## #....#",
+    ),
+    (
+        'P',
+        "#####. #....# #....# #....# #####. #..... #..... #..... #..... #.....",
+    ),
+    (
+        'R',
+        "#####. #....# #....# #....# #####. #..#.. #...#. #...#. #....# #....#",
+    ),
+    (
+        'X',
+        "#....# #....# .#..#. .#..#. ..##.. ..##.. .#..#. .#..#. #....# #....#",
+    ),
+    (
+        'Z',
+        "###### .....# .....# ....#. ...#.. ..#... .#.... #..... #..... ######",
+    ),
+];
+
+/// A glyph which is not a letter of the font.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unknown {
+    /// Column of the leftmost lit pixel.
+    pub x: usize,
+    /// The glyph drawn with `#` and `.`, one line per row.
+    pub art: String,
+}
+
+impl fmt::Display for Unknown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown glyph at column {}:\n{}", self.x, self.art)
+    }
+}
+
+/// The glyph drawn with `#` and `.`, one line per row.
+fn draw(rows: &[Vec<bool>], columns: std::ops::Range<usize>) -> String {
+    rows.iter()
+        .map(|row| {
+            row[columns.clone()]
+                .iter()
+                .map(|&lit| if lit { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Letter drawn in `art`, a glyph without blank columns at its sides.
+fn letter(art: &str, height: usize) -> Option<char> {
+    let font: &[(char, &str)] = match height {
+        6 => &SMALL,
+        10 => &LARGE,
+        _ => return None,
+    };
+    font.iter()
+        .find(|(_, glyph)| glyph.replace(' ', "\n") == art)
+        .map(|&(c, _)| c)
+}
+
+/// Letters drawn by the lit pixels of `rows`. Unknown glyphs are all reported, in order.
+pub fn recognize(rows: &[Vec<bool>]) -> Result<String, Vec<Unknown>> {
+    let lit_rows = rows
+        .iter()
+        .enumerate()
+        .filter(|(_, row)| row.contains(&true))
+        .map(|(y, _)| y)
+        .collect::<Vec<_>>();
+    let (Some(&top), Some(&bottom)) = (lit_rows.first(), lit_rows.last()) else {
+        return Ok(String::new());
+    };
+    let rows = &rows[top..=bottom];
+
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let rows = rows
+        .iter()
+        .map(|row| {
+            let mut row = row.clone();
+            row.resize(width, false);
+            row
+        })
+        .collect::<Vec<_>>();
+    let lit_column = |x: usize| rows.iter().any(|row| row[x]);
+
+    let (mut text, mut unknown) = (String::new(), vec![]);
+    let mut x = 0;
+    while x < width {
+        if !lit_column(x) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < width && lit_column(x) {
+            x += 1;
+        }
+
+        let art = draw(&rows, start..x);
+        match letter(&art, rows.len()) {
+            Some(c) => text.push(c),
+            None => unknown.push(Unknown { x: start, art }),
+        }
+    }
+
+    if unknown.is_empty() {
+        Ok(text)
+    } else {
+        Err(unknown)
+    }
+}
+
+pub fn recognize_grid(grid: &Grid<bool>) -> Result<String, Vec<Unknown>> {
+    recognize(&grid.rows().map(<[bool]>::to_vec).collect::<Vec<_>>())
+}
+
+/// Like [`recognize`], with `#` or `█` for lit pixels and anything else for dark ones.
+pub fn recognize_text(text: &str) -> Result<String, Vec<Unknown>> {
+    let rows = text
+        .lines()
+        .map(|line| line.chars().map(|c| c == '#' || c == '█').collect())
+        .collect::<Vec<_>>();
+    recognize(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_font() {
+        let text = "
+.##..###..####.#..#.
+#..#.#..#.#....#..#.
+#..#.###..###..####.
+####.#..#.#....#..#.
+#..#.#..#.#....#..#.
+#..#.###..####.#..#.";
+        assert_eq!(recognize_text(text), Ok("ABEH".to_string()));
+
+        let crt = text.replace('#', "█").replace('.', " ");
+        assert_eq!(recognize_text(&crt), Ok("ABEH".to_string()));
+
+        let grid = Grid::parse_with(text.trim(), |c| c == '#').unwrap();
+        assert_eq!(recognize_grid(&grid), Ok("ABEH".to_string()));
+    }
+
+    #[test]
+    fn test_large_font() {
+        let text = "
+#....#..######
+#....#.......#
+.#..#........#
+.#..#.......#.
+..##.......#..
+..##......#...
+.#..#....#....
+.#..#...#.....
+#....#..#.....
+#....#..######";
+        assert_eq!(recognize_text(text), Ok("XZ".to_string()));
+        assert_eq!(recognize_text("...\n..."), Ok(String::new()));
+    }
+
+    #[test]
+    fn test_unknown() {
+        let text = "
+#####..##.
+#...#.#..#
+#...#.#..#
+#...#.####
+#...#.#..#
+#####.#..#";
+        let unknown = Unknown {
+            x: 0,
+            art: "#####\n#...#\n#...#\n#...#\n#...#\n#####".to_string(),
+        };
+        assert_eq!(recognize_text(text), Err(vec![unknown]));
+    }
+}
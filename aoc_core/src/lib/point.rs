@@ -0,0 +1,275 @@
+//! 2D and 3D integer points with vector arithmetic and grid distances.
+use crate::num::{Integer, Signed};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+#[inline]
+fn abs_diff<T: Integer>(a: T, b: T) -> T {
+    if a > b { a - b } else { b - a }
+}
+
+#[inline]
+fn signum<T: Signed>(a: T) -> T {
+    match a.cmp(&T::ZERO) {
+        std::cmp::Ordering::Less => -T::ONE,
+        std::cmp::Ordering::Equal => T::ZERO,
+        std::cmp::Ordering::Greater => T::ONE,
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2<T = i32>
+where
+    T: Integer,
+{
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T = i32>
+where
+    T: Integer,
+{
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Integer> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    pub fn origin() -> Self {
+        Self::new(T::ZERO, T::ZERO)
+    }
+
+    /// Taxicab distance: `|dx| + |dy|`.
+    #[inline]
+    pub fn manhattan(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// Chessboard distance: `max(|dx|, |dy|)`.
+    #[inline]
+    pub fn chebyshev(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+}
+
+impl<T: Signed> Point2<T> {
+    /// Component-wise sign: each coordinate becomes -1, 0 or 1.
+    pub fn signum(&self) -> Self {
+        Self::new(signum(self.x), signum(self.y))
+    }
+
+    /// Orthogonal neighbours: right, down, left, up.
+    pub fn neighbors4(&self) -> [Self; 4] {
+        let (zero, one) = (T::ZERO, T::ONE);
+        [
+            *self + Self::new(one, zero),
+            *self + Self::new(zero, one),
+            *self + Self::new(-one, zero),
+            *self + Self::new(zero, -one),
+        ]
+    }
+
+    /// Orthogonal and diagonal neighbours.
+    pub fn neighbors8(&self) -> [Self; 8] {
+        let (zero, one) = (T::ZERO, T::ONE);
+        [
+            *self + Self::new(one, zero),
+            *self + Self::new(one, one),
+            *self + Self::new(zero, one),
+            *self + Self::new(-one, one),
+            *self + Self::new(-one, zero),
+            *self + Self::new(-one, -one),
+            *self + Self::new(zero, -one),
+            *self + Self::new(one, -one),
+        ]
+    }
+}
+
+impl<T: Integer> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn origin() -> Self {
+        Self::new(T::ZERO, T::ZERO, T::ZERO)
+    }
+
+    /// Taxicab distance: `|dx| + |dy| + |dz|`.
+    #[inline]
+    pub fn manhattan(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+
+    /// Chessboard distance: `max(|dx|, |dy|, |dz|)`.
+    #[inline]
+    pub fn chebyshev(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x)
+            .max(abs_diff(self.y, other.y))
+            .max(abs_diff(self.z, other.z))
+    }
+
+    #[inline]
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+}
+
+impl<T: Signed> Point3<T> {
+    #[inline]
+    pub fn cross(&self, other: &Self) -> Self {
+        Self::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+}
+
+macro_rules! impl_ops {
+    ($point:ident { $($field:ident),+ }) => {
+        impl<T: Integer> Add for $point<T> {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, rhs: Self) -> Self::Output {
+                Self { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl<T: Integer> Sub for $point<T> {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, rhs: Self) -> Self::Output {
+                Self { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl<T: Integer> Mul<T> for $point<T> {
+            type Output = Self;
+
+            #[inline]
+            fn mul(self, rhs: T) -> Self::Output {
+                Self { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl<T: Signed> Neg for $point<T> {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self::Output {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+
+        impl<T: Integer> AddAssign for $point<T> {
+            #[inline]
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<T: Integer> SubAssign for $point<T> {
+            #[inline]
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+    };
+}
+
+impl_ops!(Point2 { x, y });
+impl_ops!(Point3 { x, y, z });
+
+impl<T: Integer> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T: Integer> From<Point2<T>> for (T, T) {
+    fn from(p: Point2<T>) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl<T: Integer> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl<T: Integer> From<Point3<T>> for (T, T, T) {
+    fn from(p: Point3<T>) -> Self {
+        (p.x, p.y, p.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(3, -4);
+
+        assert_eq!(a + b, Point2::new(4, -2));
+        assert_eq!(a - b, Point2::new(-2, 6));
+        assert_eq!(a * 3, Point2::new(3, 6));
+        assert_eq!(-a, Point2::new(-1, -2));
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(3, -4);
+        assert_eq!(a.manhattan(&b), 8);
+        assert_eq!(a.chebyshev(&b), 6);
+
+        let a = Point3::<u64>::new(5, 0, 2);
+        let b = Point3::new(1, 3, 2);
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.chebyshev(&b), 4);
+    }
+
+    #[test]
+    fn test_products() {
+        let u = Point3::new(1, 2, 3);
+        let v = Point3::new(4, 5, 6);
+        assert_eq!(u.dot(&v), 32);
+        assert_eq!(u.cross(&v), Point3::new(-3, 6, -3));
+        assert_eq!(u.cross(&v).dot(&u), 0);
+    }
+
+    #[test]
+    fn test_signum() {
+        assert_eq!(Point2::new(-7, 0).signum(), Point2::new(-1, 0));
+        assert_eq!(Point2::new(3, 12).signum(), Point2::new(1, 1));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let p = Point2::<i64>::origin();
+        assert!(p.neighbors4().iter().all(|n| n.manhattan(&p) == 1));
+        assert!(p.neighbors8().iter().all(|n| n.chebyshev(&p) == 1));
+    }
+
+    #[test]
+    fn test_conversions() {
+        let p: Point2<usize> = (3, 4).into();
+        assert_eq!(<(usize, usize)>::from(p), (3, 4));
+        assert_eq!(Point3::from((1, 2, 3)).z, 3);
+    }
+}
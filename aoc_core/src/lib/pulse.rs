@@ -0,0 +1,404 @@
+//! Event-driven simulation of pulse-propagating module networks (2023/20).
+//!
+//! Pressing the button sends a low pulse to the broadcaster. Pulses are delivered in the
+//! order they are sent: flip-flops toggle on low pulses and send their new state,
+//! conjunctions remember the last pulse from every input and send low only when all of
+//! them were high, and the broadcaster forwards what it receives.
+use crate::cycle::Cycle;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::str::FromStr;
+
+pub const BROADCASTER: &str = "broadcaster";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Pulse {
+    #[default]
+    Low,
+    High,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kind {
+    Broadcaster,
+    FlipFlop,
+    Conjunction,
+    /// Receives pulses without reacting, e.g. `rx`.
+    Sink,
+}
+
+/// A pulse in flight between two modules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signal {
+    pub source: usize,
+    pub pulse: Pulse,
+    pub dest: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Network {
+    names: Vec<String>,
+    index: FxHashMap<String, usize>,
+    kinds: Vec<Kind>,
+    inputs: Vec<Vec<usize>>,
+    outputs: Vec<Vec<usize>>,
+    /// Flip-flop states.
+    on: Vec<bool>,
+    /// Last pulse received from every input, for conjunctions.
+    memory: Vec<Vec<Pulse>>,
+    presses: usize,
+}
+
+impl Network {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index of the module, adding it as a sink if unseen.
+    fn module(&mut self, name: &str) -> usize {
+        if let Some(&i) = self.index.get(name) {
+            return i;
+        }
+        self.index.insert(name.to_string(), self.names.len());
+        self.names.push(name.to_string());
+        self.kinds.push(Kind::Sink);
+        self.inputs.push(vec![]);
+        self.outputs.push(vec![]);
+        self.on.push(false);
+        self.memory.push(vec![]);
+        self.names.len() - 1
+    }
+
+    /// Add a module sending to `outputs`, or set the kind of a module seen as an output.
+    pub fn add(&mut self, name: &str, kind: Kind, outputs: &[&str]) {
+        let i = self.module(name);
+        self.kinds[i] = kind;
+        for output in outputs {
+            let j = self.module(output);
+            self.outputs[i].push(j);
+            self.inputs[j].push(i);
+            self.memory[j].push(Pulse::Low);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.index.get(name).copied()
+    }
+
+    pub fn name(&self, i: usize) -> &str {
+        &self.names[i]
+    }
+
+    pub fn kind(&self, name: &str) -> Option<Kind> {
+        Some(self.kinds[self.index(name)?])
+    }
+
+    /// Modules sending pulses to the module.
+    pub fn inputs(&self, name: &str) -> Vec<&str> {
+        self.index(name).map_or(vec![], |i| {
+            self.inputs[i].iter().map(|&j| self.name(j)).collect()
+        })
+    }
+
+    pub fn outputs(&self, name: &str) -> Vec<&str> {
+        self.index(name).map_or(vec![], |i| {
+            self.outputs[i].iter().map(|&j| self.name(j)).collect()
+        })
+    }
+
+    /// Number of button presses since the last reset.
+    pub fn presses(&self) -> usize {
+        self.presses
+    }
+
+    /// Turn all flip-flops off and make conjunctions remember low pulses only.
+    pub fn reset(&mut self) {
+        self.on.fill(false);
+        for memory in &mut self.memory {
+            memory.fill(Pulse::Low);
+        }
+        self.presses = 0;
+    }
+
+    /// Flip-flop states and conjunction memories, which determine all future pulses.
+    pub fn state(&self) -> Vec<bool> {
+        let flip_flops = (0..self.len())
+            .filter(|&i| self.kinds[i] == Kind::FlipFlop)
+            .map(|i| self.on[i]);
+        let memories = (0..self.len())
+            .filter(|&i| self.kinds[i] == Kind::Conjunction)
+            .flat_map(|i| self.memory[i].iter().map(|&pulse| pulse == Pulse::High));
+        flip_flops.chain(memories).collect()
+    }
+
+    /// Press the button once, calling `observe` for every pulse as it is delivered.
+    pub fn push_button_with(&mut self, mut observe: impl FnMut(&Signal)) {
+        self.presses += 1;
+        let Some(broadcaster) = self.index(BROADCASTER) else {
+            return;
+        };
+
+        let mut queue = VecDeque::from([Signal {
+            source: broadcaster,
+            pulse: Pulse::Low,
+            dest: broadcaster,
+        }]);
+        while let Some(signal) = queue.pop_front() {
+            observe(&signal);
+            let Signal {
+                source,
+                pulse,
+                dest,
+            } = signal;
+
+            let output = match self.kinds[dest] {
+                Kind::Broadcaster => pulse,
+                Kind::FlipFlop if pulse == Pulse::Low => {
+                    self.on[dest] = !self.on[dest];
+                    if self.on[dest] {
+                        Pulse::High
+                    } else {
+                        Pulse::Low
+                    }
+                }
+                Kind::Conjunction => {
+                    let k = self.inputs[dest].iter().position(|&j| j == source).unwrap();
+                    self.memory[dest][k] = pulse;
+                    if self.memory[dest].iter().all(|&p| p == Pulse::High) {
+                        Pulse::Low
+                    } else {
+                        Pulse::High
+                    }
+                }
+                Kind::FlipFlop | Kind::Sink => continue,
+            };
+
+            queue.extend(self.outputs[dest].iter().map(|&next| Signal {
+                source: dest,
+                pulse: output,
+                dest: next,
+            }));
+        }
+    }
+
+    /// Press the button once. Returns the number of low and high pulses, including the
+    /// one from the button.
+    pub fn push_button(&mut self) -> (usize, usize) {
+        let (mut low, mut high) = (0, 0);
+        self.push_button_with(|signal| match signal.pulse {
+            Pulse::Low => low += 1,
+            Pulse::High => high += 1,
+        });
+        (low, high)
+    }
+
+    /// Total low and high pulses over `presses` button presses. Once the state repeats,
+    /// the remaining presses are extrapolated instead of simulated.
+    pub fn pulse_counts(&mut self, presses: usize) -> (usize, usize) {
+        let mut seen = FxHashMap::default();
+        let (mut low, mut high) = (vec![0], vec![0]);
+
+        for i in 0..presses {
+            if let Some(start) = seen.insert(self.state(), i) {
+                let cycle = Cycle {
+                    start,
+                    period: i - start,
+                };
+                return (
+                    cycle.extrapolate(presses, &low),
+                    cycle.extrapolate(presses, &high),
+                );
+            }
+
+            let (l, h) = self.push_button();
+            low.push(low[i] + l);
+            high.push(high[i] + h);
+        }
+
+        (low[presses], high[presses])
+    }
+
+    /// Cycle of every module in `modules` sending `pulse`: the press on which it first
+    /// does and the number of presses until it does again. Stops pressing the button once
+    /// `limit` presses were made since the last reset; modules not seen twice by then have
+    /// no cycle.
+    pub fn watch<S: AsRef<str>>(
+        &mut self,
+        modules: &[S],
+        pulse: Pulse,
+        limit: usize,
+    ) -> Vec<Option<Cycle>> {
+        let watched = modules
+            .iter()
+            .map(|name| self.index(name.as_ref()))
+            .collect::<Vec<_>>();
+        let mut seen: Vec<Vec<usize>> = vec![vec![]; modules.len()];
+
+        while self.presses < limit && seen.iter().any(|presses| presses.len() < 2) {
+            let press = self.presses + 1;
+            self.push_button_with(|signal| {
+                for (k, &module) in watched.iter().enumerate() {
+                    if module == Some(signal.source)
+                        && signal.pulse == pulse
+                        && seen[k].last() != Some(&press)
+                    {
+                        seen[k].push(press);
+                    }
+                }
+            });
+        }
+
+        seen.into_iter()
+            .map(|presses| match presses[..] {
+                [start, next, ..] => Some(Cycle {
+                    start,
+                    period: next - start,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Graphviz description of the module graph, with a shape per kind of module.
+    pub fn to_dot(&self) -> String {
+        let mut dot = "digraph network {\n".to_string();
+        for (i, name) in self.names.iter().enumerate() {
+            let shape = match self.kinds[i] {
+                Kind::Broadcaster => "doublecircle",
+                Kind::FlipFlop => "box",
+                Kind::Conjunction => "diamond",
+                Kind::Sink => "circle",
+            };
+            writeln!(dot, "  \"{name}\" [shape={shape}];").unwrap();
+            for &j in &self.outputs[i] {
+                writeln!(dot, "  \"{name}\" -> \"{}\";", self.names[j]).unwrap();
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl FromStr for Network {
+    type Err = String;
+
+    /// One module per line: `broadcaster -> a, b`, `%a -> b` or `&inv -> a`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut network = Network::new();
+        let mut defined = FxHashSet::default();
+        for line in s.lines().filter(|line| !line.trim().is_empty()) {
+            let (module, outputs) = line
+                .trim()
+                .split_once(" -> ")
+                .ok_or(format!("Invalid module: {line}"))?;
+            let outputs = outputs.split(", ").collect::<Vec<_>>();
+
+            let (kind, name) = if let Some(name) = module.strip_prefix('%') {
+                (Kind::FlipFlop, name)
+            } else if let Some(name) = module.strip_prefix('&') {
+                (Kind::Conjunction, name)
+            } else if module == BROADCASTER {
+                (Kind::Broadcaster, module)
+            } else {
+                return Err(format!("Unknown module type: {line}"));
+            };
+            if name.is_empty() || outputs.contains(&"") {
+                return Err(format!("Missing module name: {line}"));
+            }
+            if !defined.insert(name) {
+                return Err(format!("Module defined twice: {name}"));
+            }
+            network.add(name, kind, &outputs);
+        }
+        Ok(network)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE1: &str = "broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a";
+
+    static EXAMPLE2: &str = "broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output";
+
+    #[test]
+    fn test_push_button() {
+        let mut network: Network = EXAMPLE1.parse().unwrap();
+        assert_eq!(network.push_button(), (8, 4));
+        assert_eq!(network.push_button(), (8, 4));
+
+        let mut network: Network = EXAMPLE2.parse().unwrap();
+        assert_eq!(network.kind("output"), Some(Kind::Sink));
+        assert_eq!(network.inputs("con"), ["a", "b"]);
+        assert_eq!(network.push_button(), (4, 4));
+        assert_eq!(network.push_button(), (4, 2));
+        assert_eq!(network.push_button(), (5, 3));
+        assert_eq!(network.push_button(), (4, 2));
+        assert!(network.to_dot().contains("\"inv\" [shape=diamond];"));
+    }
+
+    #[test]
+    fn test_invalid_modules() {
+        for line in [
+            " -> a",
+            "% -> a",
+            "&inv -> ",
+            "é -> a",
+            "%a b",
+            "button -> a",
+        ] {
+            assert!(line.parse::<Network>().is_err(), "{line}");
+        }
+        assert!("%a -> b\n&a -> c".parse::<Network>().is_err());
+    }
+
+    #[test]
+    fn test_pulse_counts() {
+        let mut network: Network = EXAMPLE1.parse().unwrap();
+        assert_eq!(network.pulse_counts(1000), (8000, 4000));
+
+        let mut network: Network = EXAMPLE2.parse().unwrap();
+        assert_eq!(network.pulse_counts(1000), (4250, 2750));
+
+        let mut simulated = (0, 0);
+        network.reset();
+        for _ in 0..1000 {
+            let (low, high) = network.push_button();
+            simulated = (simulated.0 + low, simulated.1 + high);
+        }
+        assert_eq!(simulated, (4250, 2750));
+    }
+
+    #[test]
+    fn test_watch() {
+        let mut network: Network = EXAMPLE2.parse().unwrap();
+        let cycles = network.watch(&["b", "con", "output"], Pulse::High, 100);
+        let b = Cycle {
+            start: 1,
+            period: 4,
+        };
+        let con = Cycle {
+            start: 1,
+            period: 1,
+        };
+        assert_eq!(cycles, [Some(b), Some(con), None]);
+    }
+}
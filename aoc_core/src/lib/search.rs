@@ -0,0 +1,296 @@
+//! Generic shortest-path searches over implicit state spaces: [BFS], [Dijkstra] and [A*].
+//!
+//! Every search takes the start states (more than one for a multi-source search), a
+//! successor closure and a goal predicate. States only need to be `Clone + Hash + Eq`.
+//! Searches stop once all goals reachable at the minimal cost have been found; pass
+//! `|_| false` as the goal to explore the whole reachable space instead.
+//!
+//! The returned [`Search`] keeps the distance to every visited state together with *all*
+//! predecessors lying on a shortest path, so both a single path and the union of all
+//! shortest paths can be reconstructed.
+//!
+//! [BFS]: https://en.wikipedia.org/wiki/Breadth-first_search
+//! [Dijkstra]: https://en.wikipedia.org/wiki/Dijkstra%27s_algorithm
+//! [A*]: https://en.wikipedia.org/wiki/A*_search_algorithm
+use crate::heap::MinHeap;
+use crate::num::Integer;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+pub struct Search<S, C> {
+    /// Shortest distance from the nearest start to every visited state.
+    pub dist: FxHashMap<S, C>,
+    /// All predecessors of a state lying on one of its shortest paths. Starts have none.
+    pub pred: FxHashMap<S, Vec<S>>,
+    /// Goal states reached at the minimal cost, in the order they were found.
+    pub goals: Vec<S>,
+}
+
+impl<S, C> Default for Search<S, C> {
+    fn default() -> Self {
+        Self {
+            dist: FxHashMap::default(),
+            pred: FxHashMap::default(),
+            goals: vec![],
+        }
+    }
+}
+
+impl<S: Clone + Hash + Eq, C: Copy> Search<S, C> {
+    /// The first goal found, if any was reachable.
+    pub fn goal(&self) -> Option<&S> {
+        self.goals.first()
+    }
+
+    /// Cost of the cheapest path to a goal.
+    pub fn cost(&self) -> Option<C> {
+        self.goal().map(|goal| self.dist[goal])
+    }
+
+    pub fn distance(&self, state: &S) -> Option<C> {
+        self.dist.get(state).copied()
+    }
+
+    /// One shortest path from a start to the first goal, both ends included.
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.goal().and_then(|goal| self.path_to(goal))
+    }
+
+    /// One shortest path from a start to `target`, both ends included.
+    pub fn path_to(&self, target: &S) -> Option<Vec<S>> {
+        if !self.dist.contains_key(target) {
+            return None;
+        }
+
+        let mut path = vec![target.clone()];
+        let mut current = target;
+        while let Some(prev) = self.pred.get(current).and_then(|p| p.first()) {
+            path.push(prev.clone());
+            current = prev;
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every state lying on any shortest path to any of the goals.
+    pub fn states_on_shortest_paths(&self) -> FxHashSet<S> {
+        self.states_on_paths_to(&self.goals)
+    }
+
+    /// Every state lying on any shortest path to any of the `targets`.
+    pub fn states_on_paths_to<'a>(&self, targets: impl IntoIterator<Item = &'a S>) -> FxHashSet<S>
+    where
+        S: 'a,
+    {
+        let mut seen = FxHashSet::default();
+        let mut stack = targets
+            .into_iter()
+            .filter(|target| self.dist.contains_key(target))
+            .collect::<Vec<_>>();
+
+        while let Some(state) = stack.pop() {
+            if seen.insert(state.clone()) {
+                stack.extend(self.pred.get(state).into_iter().flatten());
+            }
+        }
+
+        seen
+    }
+
+    fn add_pred(&mut self, state: &S, prev: &S) {
+        let preds = self.pred.entry(state.clone()).or_default();
+        if !preds.contains(prev) {
+            preds.push(prev.clone());
+        }
+    }
+}
+
+/// Breadth-first search where every step costs one.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, usize>
+where
+    S: Clone + Hash + Eq,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search::default();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if search.dist.insert(start.clone(), 0).is_none() {
+            queue.push_back(start);
+        }
+    }
+
+    let mut best = None;
+
+    while let Some(state) = queue.pop_front() {
+        let dist = search.dist[&state];
+        if best.is_some_and(|best| dist > best) {
+            break;
+        }
+        if is_goal(&state) {
+            best = Some(dist);
+            search.goals.push(state);
+            continue;
+        }
+
+        for next in successors(&state) {
+            match search.dist.get(&next) {
+                None => {
+                    search.dist.insert(next.clone(), dist + 1);
+                    search.pred.insert(next.clone(), vec![state.clone()]);
+                    queue.push_back(next);
+                }
+                Some(&d) if d == dist + 1 => search.add_pred(&next, &state),
+                Some(_) => {}
+            }
+        }
+    }
+
+    search
+}
+
+/// Dijkstra's algorithm. Successors are `(state, cost)` pairs with non-negative costs.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Hash + Eq,
+    C: Integer,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, successors, |_| C::ZERO, is_goal)
+}
+
+/// A* search. The `heuristic` must be consistent (never overestimate the cost of a single
+/// step), which also makes it admissible, for the distances to be exact.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Hash + Eq,
+    C: Integer,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut search = Search::default();
+    let mut closed = FxHashSet::default();
+    let mut heap = MinHeap::new();
+
+    for start in starts {
+        if search.dist.insert(start.clone(), C::ZERO).is_none() {
+            heap.push(heuristic(&start), start);
+        }
+    }
+
+    let mut best = None;
+
+    while let Some((estimate, state)) = heap.pop() {
+        if best.is_some_and(|best| estimate > best) {
+            break;
+        }
+        if !closed.insert(state.clone()) {
+            continue;
+        }
+
+        let dist = search.dist[&state];
+        if is_goal(&state) {
+            best = Some(dist);
+            search.goals.push(state);
+            continue;
+        }
+
+        for (next, cost) in successors(&state) {
+            let next_dist = dist + cost;
+            match search.dist.get(&next) {
+                Some(&d) if next_dist > d => {}
+                Some(&d) if next_dist == d => search.add_pred(&next, &state),
+                _ => {
+                    search.dist.insert(next.clone(), next_dist);
+                    search.pred.insert(next.clone(), vec![state.clone()]);
+                    heap.push(next_dist + heuristic(&next), next);
+                }
+            }
+        }
+    }
+
+    search
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Two equally short routes from 0 to 3 (via 1 or 2) and a longer detour via 4.
+    fn successors(n: &u8) -> Vec<(u8, u32)> {
+        match n {
+            0 => vec![(1, 1), (2, 1), (4, 1)],
+            1 | 2 => vec![(3, 1)],
+            4 => vec![(3, 5)],
+            _ => vec![],
+        }
+    }
+
+    fn unweighted(n: &u8) -> Vec<u8> {
+        successors(n).into_iter().map(|(n, _)| n).collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let search = bfs([0], unweighted, |&n| n == 3);
+        assert_eq!(search.cost(), Some(2));
+        assert_eq!(search.path(), Some(vec![0, 1, 3]));
+
+        // Unweighted, the detour via 4 is as short as the other two routes
+        let mut states = search
+            .states_on_shortest_paths()
+            .into_iter()
+            .collect::<Vec<_>>();
+        states.sort();
+        assert_eq!(states, vec![0, 1, 2, 3, 4]);
+
+        assert!(bfs([3], unweighted, |&n| n == 0).goal().is_none());
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let search = dijkstra([0], successors, |&n| n == 3);
+        assert_eq!(search.cost(), Some(2));
+        assert_eq!(search.states_on_shortest_paths().len(), 4);
+
+        // Exploring everything keeps the best distance to every state
+        let search = dijkstra([0], successors, |_| false);
+        assert_eq!(search.distance(&3), Some(2));
+        assert_eq!(search.distance(&4), Some(1));
+        assert_eq!(search.pred[&3].len(), 2);
+    }
+
+    #[test]
+    fn test_multi_source() {
+        let search = dijkstra([4, 1], successors, |&n| n == 3);
+        assert_eq!(search.cost(), Some(1));
+        assert_eq!(search.path(), Some(vec![1, 3]));
+    }
+
+    #[test]
+    fn test_astar() {
+        // Walk on a line towards 10 with steps of +1 or +3
+        let search = astar(
+            [0i32],
+            |&n| [(n + 1, 1), (n + 3, 1)],
+            |&n| (10 - n).max(0) / 3,
+            |&n| n == 10,
+        );
+        assert_eq!(search.cost(), Some(4));
+        assert_eq!(search.path().unwrap().len(), 5);
+    }
+}
@@ -1,15 +1,91 @@
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::Display;
+use std::time::Duration;
+
+/// Name of the variant registered without an explicit `variant = "..."`.
+pub const DEFAULT_VARIANT: &str = "default";
+
+/// Value returned by a solution, converted to the answers printed by the runner.
+///
+/// Solutions which print their answers themselves return `()` and have none.
+pub trait Answers {
+    fn into_answers(self) -> Vec<String>;
+}
+
+impl Answers for () {
+    fn into_answers(self) -> Vec<String> {
+        vec![]
+    }
+}
+
+impl<A: Display, B: Display> Answers for (A, B) {
+    fn into_answers(self) -> Vec<String> {
+        vec![self.0.to_string(), self.1.to_string()]
+    }
+}
+
 pub struct Solution {
     pub year: u16,
     pub day: u16,
-    f: fn(),
+    pub variant: Option<&'static str>,
+    pub title: Option<&'static str>,
+    pub tags: &'static [&'static str],
+    pub complexity: Option<&'static str>,
+    f: fn() -> Vec<String>,
 }
 
 impl Solution {
-    pub const fn new(year: u16, day: u16, f: fn()) -> Self {
-        Self { year, day, f }
+    pub const fn new(year: u16, day: u16, f: fn() -> Vec<String>) -> Self {
+        Self {
+            year,
+            day,
+            variant: None,
+            title: None,
+            tags: &[],
+            complexity: None,
+            f,
+        }
+    }
+
+    pub const fn with_variant(self, variant: &'static str) -> Self {
+        Self {
+            variant: Some(variant),
+            ..self
+        }
+    }
+
+    pub const fn with_title(self, title: &'static str) -> Self {
+        Self {
+            title: Some(title),
+            ..self
+        }
+    }
+
+    pub const fn with_tags(self, tags: &'static [&'static str]) -> Self {
+        Self { tags, ..self }
+    }
+
+    pub const fn with_complexity(self, complexity: &'static str) -> Self {
+        Self {
+            complexity: Some(complexity),
+            ..self
+        }
+    }
+
+    pub fn variant_name(&self) -> &'static str {
+        self.variant.unwrap_or(DEFAULT_VARIANT)
     }
 
-    pub fn run(&self) {
+    pub fn is_default(&self) -> bool {
+        self.variant.is_none()
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// Run the solution and return the answers it did not print itself.
+    pub fn run(&self) -> Vec<String> {
         (self.f)()
     }
 }
@@ -21,3 +97,117 @@ impl std::cmp::PartialEq<(u16, u16)> for Solution {
 }
 
 inventory::collect!(Solution);
+
+/// Print a labelled duration in the most readable unit.
+pub fn report_duration(label: &str, duration: Duration) {
+    if duration.as_secs() > 0 {
+        println!("{label}: \x1b[1m{}s\x1b[0m", duration.as_secs());
+    } else if duration.as_millis() > 0 {
+        println!("{label}: \x1b[1m{}ms\x1b[0m", duration.as_millis());
+    } else {
+        println!("{label}: \x1b[1m{}μs\x1b[0m", duration.as_micros());
+    }
+}
+
+/// Find (year, day, variant) triples with more than one registered solution.
+pub fn duplicates<'a>(
+    solutions: impl IntoIterator<Item = &'a Solution>,
+) -> Vec<(u16, u16, &'static str)> {
+    let mut seen = HashSet::new();
+    let mut dups = solutions
+        .into_iter()
+        .map(|sol| (sol.year, sol.day, sol.variant_name()))
+        .filter(|key| !seen.insert(*key))
+        .collect::<Vec<_>>();
+    dups.sort_unstable();
+    dups.dedup();
+    dups
+}
+
+/// Find (year, day) pairs which only have named variants and no default one.
+pub fn missing_defaults<'a>(solutions: impl IntoIterator<Item = &'a Solution>) -> Vec<(u16, u16)> {
+    let (mut days, mut defaults) = (BTreeSet::new(), HashSet::new());
+
+    for sol in solutions {
+        days.insert((sol.year, sol.day));
+        if sol.is_default() {
+            defaults.insert((sol.year, sol.day));
+        }
+    }
+
+    days.into_iter()
+        .filter(|key| !defaults.contains(key))
+        .collect()
+}
+
+/// Check that every (year, day, variant) is registered at most once and that every day
+/// has a default variant.
+pub fn validate_registry() -> Result<(), String> {
+    let mut errors = vec![];
+
+    let dups = duplicates(inventory::iter::<Solution>());
+    if !dups.is_empty() {
+        let days = dups
+            .iter()
+            .map(|(year, day, variant)| format!("{year}/{day} ({variant})"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        errors.push(format!(
+            "Multiple solutions registered for the same day: {days}"
+        ));
+    }
+
+    let missing = missing_defaults(inventory::iter::<Solution>());
+    if !missing.is_empty() {
+        let days = missing
+            .iter()
+            .map(|(year, day)| format!("{year}/{day}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        errors.push(format!("No default variant registered for: {days}"));
+    }
+
+    match errors.is_empty() {
+        true => Ok(()),
+        false => Err(errors.join("\n")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicates() {
+        let solutions = [
+            Solution::new(2024, 1, Vec::new),
+            Solution::new(2024, 2, Vec::new),
+            Solution::new(2024, 1, Vec::new),
+            Solution::new(2024, 1, Vec::new),
+        ];
+
+        assert_eq!(duplicates(&solutions), vec![(2024, 1, DEFAULT_VARIANT)]);
+        assert!(duplicates(&solutions[..2]).is_empty());
+    }
+
+    #[test]
+    fn test_variants() {
+        let solutions = [
+            Solution::new(2024, 1, Vec::new),
+            Solution::new(2024, 1, Vec::new).with_variant("fast"),
+            Solution::new(2024, 2, Vec::new).with_variant("fast"),
+            Solution::new(2024, 2, Vec::new).with_variant("slow"),
+        ];
+
+        assert!(duplicates(&solutions).is_empty());
+        assert_eq!(missing_defaults(&solutions), vec![(2024, 2)]);
+        assert_eq!(solutions[0].variant_name(), DEFAULT_VARIANT);
+        assert_eq!(solutions[1].variant_name(), "fast");
+    }
+
+    #[test]
+    fn test_answers() {
+        assert!(().into_answers().is_empty());
+        assert_eq!((41, "6").into_answers(), ["41", "6"]);
+    }
+}
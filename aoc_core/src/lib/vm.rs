@@ -0,0 +1,399 @@
+//! Register machine for the assembly-style puzzles (2015/23, 2020/8, 2022/10, 2024/17).
+//!
+//! A puzzle only defines its instruction set by implementing [`Instruction`]; the
+//! [`Machine`] provides the fetch-execute loop with a register file, program counter,
+//! output, cycle counting, breakpoints, infinite-loop detection, step limits and
+//! execution traces. [`tokens`] and [`Operand`] cover the usual `op x, +3` syntax.
+use rustc_hash::FxHashSet;
+use std::str::FromStr;
+
+/// How the program counter moves after an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Jump {
+    Next,
+    /// Offset from the current instruction.
+    Relative(i64),
+    /// Index of the next instruction.
+    Absolute(usize),
+    Halt,
+}
+
+/// Registers and output visible to instructions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cpu<W> {
+    pub registers: Vec<W>,
+    pub output: Vec<W>,
+}
+
+pub trait Instruction {
+    /// Value type of registers and output.
+    type Word: Copy + Default;
+
+    fn execute(&self, cpu: &mut Cpu<Self::Word>) -> Jump;
+
+    /// Clock cycles taken, for puzzles observing registers during execution.
+    fn cycles(&self) -> usize {
+        1
+    }
+}
+
+/// Why [`Machine::run`] stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exit {
+    /// An instruction halted or the program counter left the program.
+    Halted,
+    /// The instruction at this index is about to run a second time.
+    Loop(usize),
+    /// Stopped before the instruction at a breakpoint. Running again resumes.
+    Breakpoint(usize),
+    /// The step limit was reached.
+    StepLimit,
+}
+
+/// Machine state before an instruction was executed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot<W> {
+    pub pc: usize,
+    /// Cycles elapsed before the instruction.
+    pub cycle: usize,
+    pub registers: Vec<W>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Machine<I: Instruction> {
+    program: Vec<I>,
+    cpu: Cpu<I::Word>,
+    pc: usize,
+    halted: bool,
+    steps: usize,
+    cycle: usize,
+    breakpoints: FxHashSet<usize>,
+    visited: Option<FxHashSet<usize>>,
+    step_limit: Option<usize>,
+    trace: Option<Vec<Snapshot<I::Word>>>,
+}
+
+impl<I: Instruction> Machine<I> {
+    /// A machine with `registers` registers set to their default value.
+    pub fn new(program: Vec<I>, registers: usize) -> Self {
+        Self {
+            program,
+            cpu: Cpu {
+                registers: vec![I::Word::default(); registers],
+                output: vec![],
+            },
+            pc: 0,
+            halted: false,
+            steps: 0,
+            cycle: 0,
+            breakpoints: FxHashSet::default(),
+            visited: None,
+            step_limit: None,
+            trace: None,
+        }
+    }
+
+    pub fn program(&self) -> &[I] {
+        &self.program
+    }
+
+    /// Mutable access to the program, e.g. to patch single instructions.
+    pub fn program_mut(&mut self) -> &mut [I] {
+        &mut self.program
+    }
+
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    pub fn registers(&self) -> &[I::Word] {
+        &self.cpu.registers
+    }
+
+    pub fn register(&self, index: usize) -> I::Word {
+        self.cpu.registers[index]
+    }
+
+    pub fn set_register(&mut self, index: usize, value: I::Word) {
+        self.cpu.registers[index] = value;
+    }
+
+    pub fn output(&self) -> &[I::Word] {
+        &self.cpu.output
+    }
+
+    pub fn take_output(&mut self) -> Vec<I::Word> {
+        std::mem::take(&mut self.cpu.output)
+    }
+
+    /// Number of instructions executed.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Clock cycles elapsed, see [`Instruction::cycles`].
+    pub fn cycle(&self) -> usize {
+        self.cycle
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted || self.pc >= self.program.len()
+    }
+
+    pub fn add_breakpoint(&mut self, pc: usize) {
+        self.breakpoints.insert(pc);
+    }
+
+    pub fn remove_breakpoint(&mut self, pc: usize) {
+        self.breakpoints.remove(&pc);
+    }
+
+    /// Stop with [`Exit::Loop`] when an instruction is about to run for the second time.
+    /// Only meaningful for programs whose state does not influence control flow beyond
+    /// the program counter, such as 2020/8.
+    pub fn set_loop_detection(&mut self, enabled: bool) {
+        self.visited = enabled.then(FxHashSet::default);
+    }
+
+    /// Stop with [`Exit::StepLimit`] once this many instructions were executed in total.
+    pub fn set_step_limit(&mut self, limit: Option<usize>) {
+        self.step_limit = limit;
+    }
+
+    /// Start or stop recording a [`Snapshot`] before every instruction.
+    pub fn set_tracing(&mut self, enabled: bool) {
+        self.trace = enabled.then(Vec::new);
+    }
+
+    pub fn trace(&self) -> &[Snapshot<I::Word>] {
+        self.trace.as_deref().unwrap_or_default()
+    }
+
+    /// Rewind to the first instruction with the given register values, clearing output,
+    /// counters, visited instructions and the trace. Breakpoints and limits are kept.
+    pub fn reset(&mut self, registers: &[I::Word]) {
+        self.cpu.registers.copy_from_slice(registers);
+        self.cpu.output.clear();
+        self.pc = 0;
+        self.halted = false;
+        self.steps = 0;
+        self.cycle = 0;
+        if let Some(visited) = &mut self.visited {
+            visited.clear();
+        }
+        if let Some(trace) = &mut self.trace {
+            trace.clear();
+        }
+    }
+
+    /// Execute a single instruction. Returns `false` if the machine has halted.
+    pub fn step(&mut self) -> bool {
+        if self.is_halted() {
+            return false;
+        }
+
+        let instruction = &self.program[self.pc];
+        if let Some(trace) = &mut self.trace {
+            trace.push(Snapshot {
+                pc: self.pc,
+                cycle: self.cycle,
+                registers: self.cpu.registers.clone(),
+            });
+        }
+
+        self.steps += 1;
+        self.cycle += instruction.cycles();
+        match instruction.execute(&mut self.cpu) {
+            Jump::Next => self.pc += 1,
+            Jump::Absolute(pc) => self.pc = pc,
+            Jump::Relative(offset) => match self.pc.checked_add_signed(offset as isize) {
+                Some(pc) => self.pc = pc,
+                None => self.halted = true,
+            },
+            Jump::Halt => self.halted = true,
+        }
+        true
+    }
+
+    /// Run until the program halts, a loop is detected, a breakpoint is hit or the step
+    /// limit is reached.
+    pub fn run(&mut self) -> Exit {
+        let start = self.steps;
+
+        loop {
+            if self.is_halted() {
+                return Exit::Halted;
+            }
+            if self.steps > start && self.breakpoints.contains(&self.pc) {
+                return Exit::Breakpoint(self.pc);
+            }
+            if self.step_limit.is_some_and(|limit| self.steps >= limit) {
+                return Exit::StepLimit;
+            }
+            if let Some(visited) = &mut self.visited
+                && !visited.insert(self.pc)
+            {
+                return Exit::Loop(self.pc);
+            }
+            self.step();
+        }
+    }
+}
+
+/// Split an instruction such as `jio a, +19` into its mnemonic and operands.
+pub fn tokens(line: &str) -> (&str, Vec<&str>) {
+    let mut parts = line
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|part| !part.is_empty());
+    let mnemonic = parts.next().unwrap_or_default();
+    (mnemonic, parts.collect())
+}
+
+/// Parse one instruction per non-empty line.
+pub fn parse_program<I: FromStr>(source: &str) -> Result<Vec<I>, I::Err> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::parse)
+        .collect()
+}
+
+/// Index of a register named by a single letter: `a` or `A` is 0, `b` or `B` is 1, ...
+pub fn register(name: &str) -> Option<usize> {
+    match name.as_bytes() {
+        [c @ b'a'..=b'z'] => Some((c - b'a') as usize),
+        [c @ b'A'..=b'Z'] => Some((c - b'A') as usize),
+        _ => None,
+    }
+}
+
+/// A register or an immediate value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand<W> {
+    Register(usize),
+    Value(W),
+}
+
+impl<W: Copy> Operand<W> {
+    pub fn get(&self, registers: &[W]) -> W {
+        match *self {
+            Operand::Register(index) => registers[index],
+            Operand::Value(value) => value,
+        }
+    }
+}
+
+impl<W: FromStr> FromStr for Operand<W> {
+    type Err = W::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match register(s) {
+            Some(index) => Ok(Operand::Register(index)),
+            None => s.parse().map(Operand::Value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `inc r`, `dec r`, `jnz r, offset` and `out r`, each taking one cycle.
+    enum Op {
+        Inc(usize),
+        Dec(usize),
+        Jnz(Operand<i64>, i64),
+        Out(Operand<i64>),
+    }
+
+    impl Instruction for Op {
+        type Word = i64;
+
+        fn execute(&self, cpu: &mut Cpu<i64>) -> Jump {
+            match *self {
+                Op::Inc(r) => cpu.registers[r] += 1,
+                Op::Dec(r) => cpu.registers[r] -= 1,
+                Op::Jnz(x, offset) if x.get(&cpu.registers) != 0 => return Jump::Relative(offset),
+                Op::Jnz(..) => {}
+                Op::Out(x) => cpu.output.push(x.get(&cpu.registers)),
+            }
+            Jump::Next
+        }
+    }
+
+    impl FromStr for Op {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let reg = |x: &str| register(x).ok_or(format!("Invalid register: {x}"));
+            fn num<T: FromStr>(x: &str) -> Result<T, String> {
+                x.parse().map_err(|_| format!("Invalid operand: {x}"))
+            }
+
+            match tokens(s) {
+                ("inc", ops) => Ok(Op::Inc(reg(ops[0])?)),
+                ("dec", ops) => Ok(Op::Dec(reg(ops[0])?)),
+                ("jnz", ops) => Ok(Op::Jnz(num(ops[0])?, num(ops[1])?)),
+                ("out", ops) => Ok(Op::Out(num(ops[0])?)),
+                (op, _) => Err(format!("Unknown instruction: {op}")),
+            }
+        }
+    }
+
+    // Move a to b, printing a after every decrement
+    static PROGRAM: &str = "inc a
+inc a
+inc a
+dec a
+inc b
+out a
+jnz a, -3";
+
+    fn machine() -> Machine<Op> {
+        Machine::new(parse_program(PROGRAM).unwrap(), 2)
+    }
+
+    #[test]
+    fn test_run() {
+        let mut m = machine();
+        assert_eq!(m.run(), Exit::Halted);
+        assert_eq!(m.registers(), [0, 3]);
+        assert_eq!(m.output(), [2, 1, 0]);
+        assert_eq!(m.steps(), 15);
+        assert!(!m.step());
+
+        m.reset(&[1, 0]);
+        assert_eq!(m.run(), Exit::Halted);
+        assert_eq!(m.registers(), [0, 4]);
+        assert!(parse_program::<Op>("mov a, b").is_err());
+    }
+
+    #[test]
+    fn test_breakpoints_and_limits() {
+        let mut m = machine();
+        m.add_breakpoint(5);
+        assert_eq!(m.run(), Exit::Breakpoint(5));
+        assert_eq!(m.registers(), [2, 1]);
+        assert_eq!(m.run(), Exit::Breakpoint(5));
+        assert_eq!(m.output(), [2]);
+
+        m.remove_breakpoint(5);
+        m.set_step_limit(Some(10));
+        assert_eq!(m.run(), Exit::StepLimit);
+        assert_eq!(m.steps(), 10);
+    }
+
+    #[test]
+    fn test_loop_detection_and_trace() {
+        let mut m = machine();
+        m.set_loop_detection(true);
+        m.set_tracing(true);
+        assert_eq!(m.run(), Exit::Loop(3));
+
+        let pcs = m.trace().iter().map(|s| s.pc).collect::<Vec<_>>();
+        assert_eq!(pcs, [0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(m.trace()[3].registers, [3, 0]);
+        assert_eq!(m.trace()[6].cycle, 6);
+    }
+}
@@ -1,114 +1,417 @@
+use aoc_core::date::{LAST_YEAR, days_in};
+use aoc_core::solution::DEFAULT_VARIANT;
 use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::quote;
-use syn::{parse::Parser, parse_macro_input, punctuated::Punctuated, Expr, ItemFn, Token};
+use syn::{
+    Error, FnArg, GenericArgument, Ident, ItemFn, LitInt, LitStr, PathArguments, Signature, Token,
+    Type, TypePath, bracketed,
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+};
 
-#[proc_macro_attribute]
-pub fn aoc(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let ItemFn {
-        attrs,
-        vis,
-        sig,
-        block,
-    } = parse_macro_input!(item as ItemFn);
+/// Arguments of `#[aoc(YEAR, DAY, key = value, ...)]`, validated at compile time.
+///
+/// Supported keys: `variant = "..."`, `title = "..."`, `tags = ["...", ...]` and
+/// `complexity = "..."`.
+struct AocArgs {
+    year: LitInt,
+    day: LitInt,
+    variant: Option<LitStr>,
+    title: Option<LitStr>,
+    tags: Vec<LitStr>,
+    complexity: Option<LitStr>,
+}
 
-    let exprs = Punctuated::<Expr, Token![,]>::parse_terminated
-        .parse(attr)
-        .unwrap()
-        .into_iter()
-        .collect::<Vec<_>>();
+impl AocArgs {
+    /// Const builder calls attaching metadata to the registered solution.
+    fn metadata(&self) -> TokenStream2 {
+        let variant = self.variant.iter();
+        let title = self.title.iter();
+        let complexity = self.complexity.iter();
+        let tags = &self.tags;
+        let tags = (!tags.is_empty()).then(|| quote!(.with_tags(&[#(#tags),*])));
 
-    let (year, day) = match &*exprs {
-        [year, day] => (year, day),
-        _ => panic!("aoc: Invalid number of parameters; expected 2"),
-    };
+        quote! {
+            #(.with_variant(#variant))* #(.with_title(#title))* #tags
+            #(.with_complexity(#complexity))*
+        }
+    }
+}
 
-    let fn_name = sig.ident.clone();
-    let start = quote!(__measure_time_start_instant);
-    let expanded = quote! {
+impl Parse for AocArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.is_empty() {
+            return Err(input.error("expected `#[aoc(YEAR, DAY)]`"));
+        }
 
-        #(#attrs)*
-        #vis #sig {
-            fn inner() {
-                #block
-            }
+        let year: LitInt = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let day: LitInt = input.parse()?;
 
-            let #start = ::std::time::Instant::now();
-            let ret = inner();
-            let duration = #start.elapsed();
+        let year_value = year.base10_parse::<u16>()?;
+        if !(2015..=LAST_YEAR).contains(&year_value) {
+            return Err(Error::new(
+                year.span(),
+                format!("invalid year {year_value}; expected 2015..={LAST_YEAR}"),
+            ));
+        }
 
-            if duration.as_secs() > 0 {
-                ::std::println!("Elapsed: \x1b[1m{}s\x1b[0m", duration.as_secs());
+        let day_value = day.base10_parse::<u16>()?;
+        let last_day = days_in(year_value);
+        if !(1..=last_day).contains(&day_value) {
+            return Err(Error::new(
+                day.span(),
+                format!("invalid day {day_value}; AoC {year_value} has days 1..={last_day}"),
+            ));
+        }
+
+        let mut args = Self {
+            year,
+            day,
+            variant: None,
+            title: None,
+            tags: vec![],
+            complexity: None,
+        };
+
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
             }
-            else if duration.as_millis() > 0 {
-                ::std::println!("Elapsed: \x1b[1m{}ms\x1b[0m", duration.as_millis());
-            } else {
-                ::std::println!("Elapsed: \x1b[1m{}μs\x1b[0m", duration.as_micros());
+            if !input.peek(Ident) {
+                return Err(input.error(
+                    "unexpected argument; expected `key = value` with key one of `variant`, `title`, `tags` or `complexity`",
+                ));
             }
 
-            ret
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let duplicate = match key.to_string().as_str() {
+                "variant" => {
+                    let variant: LitStr = input.parse()?;
+                    let name = variant.value();
+                    if name.is_empty() || name == DEFAULT_VARIANT {
+                        return Err(Error::new(
+                            variant.span(),
+                            format!(
+                                "invalid variant name {name:?}; omit `variant` for the default one"
+                            ),
+                        ));
+                    }
+                    args.variant.replace(variant).is_some()
+                }
+                "title" => args.title.replace(input.parse()?).is_some(),
+                "complexity" => args.complexity.replace(input.parse()?).is_some(),
+                "tags" => {
+                    let content;
+                    bracketed!(content in input);
+                    let tags = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+                    let previous = std::mem::replace(&mut args.tags, tags.into_iter().collect());
+                    !previous.is_empty()
+                }
+                _ => {
+                    return Err(Error::new(
+                        key.span(),
+                        format!(
+                            "unknown argument `{key}`; expected `variant`, `title`, `tags` or `complexity`"
+                        ),
+                    ));
+                }
+            };
+            if duplicate {
+                return Err(Error::new(
+                    key.span(),
+                    format!("duplicate argument `{key}`"),
+                ));
+            }
         }
 
-        inventory::submit! {
-            aoc_core::solution::Solution::new(#year, #day, #fn_name)
+        Ok(args)
+    }
+}
+
+/// How the puzzle input is passed to a solution, based on the type of its only parameter.
+enum Input {
+    /// `fn()` - the solution loads the input itself.
+    None,
+    /// `fn(&str)`
+    Str,
+    /// `fn(&[u8])`
+    Bytes,
+    /// `fn(Vec<String>)`
+    Lines,
+    /// `fn(T) where T: FromStr`
+    Parsed(Box<Type>),
+}
+
+impl Input {
+    fn from_signature(sig: &Signature) -> syn::Result<Self> {
+        if !sig.generics.params.is_empty() {
+            return Err(Error::new_spanned(
+                &sig.generics,
+                "aoc solutions cannot be generic",
+            ));
+        }
+
+        let mut inputs = sig.inputs.iter();
+        let ty = match (inputs.next(), inputs.next()) {
+            (None, _) => return Ok(Self::None),
+            (Some(FnArg::Typed(arg)), None) => &*arg.ty,
+            (Some(FnArg::Receiver(arg)), _) => {
+                return Err(Error::new_spanned(arg, "aoc solutions cannot take `self`"));
+            }
+            (_, Some(arg)) => {
+                return Err(Error::new_spanned(
+                    arg,
+                    "expected at most one parameter with puzzle input",
+                ));
+            }
+        };
+
+        Ok(match ty {
+            Type::Reference(r) if r.mutability.is_none() => match &*r.elem {
+                Type::Path(p) if p.path.is_ident("str") => Self::Str,
+                Type::Slice(s) if matches!(&*s.elem, Type::Path(p) if p.path.is_ident("u8")) => {
+                    Self::Bytes
+                }
+                _ => {
+                    return Err(Error::new_spanned(
+                        ty,
+                        "unsupported input type; expected `&str`, `&[u8]`, `Vec<String>` or `T: FromStr`",
+                    ));
+                }
+            },
+            Type::Path(p) if is_vec_of_strings(p) => Self::Lines,
+            Type::Reference(_) => {
+                return Err(Error::new_spanned(
+                    ty,
+                    "unsupported input type; expected `&str`, `&[u8]`, `Vec<String>` or `T: FromStr`",
+                ));
+            }
+            _ => Self::Parsed(Box::new(ty.clone())),
+        })
+    }
+
+    /// Expression converting the loaded `String` into the parameter value.
+    fn convert(&self, data: &Ident) -> TokenStream2 {
+        match self {
+            Self::None => quote!(),
+            Self::Str => quote!(&#data),
+            Self::Bytes => quote!(#data.as_bytes()),
+            Self::Lines => quote! {
+                #data.lines().map(::std::string::String::from).collect::<::std::vec::Vec<_>>()
+            },
+            Self::Parsed(ty) => {
+                let msg = format!("Unable to parse puzzle input as `{}`", quote!(#ty));
+                quote! {
+                    match #data.trim().parse::<#ty>() {
+                        Ok(value) => value,
+                        Err(_) => ::std::panic!(#msg),
+                    }
+                }
+            }
         }
+    }
+}
+
+fn is_vec_of_strings(p: &TypePath) -> bool {
+    let Some(segment) = p.path.segments.last() else {
+        return false;
+    };
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return false;
     };
 
-    TokenStream::from(expanded)
+    segment.ident == "Vec"
+        && matches!(
+            args.args.first(),
+            Some(GenericArgument::Type(Type::Path(t))) if t.path.is_ident("String")
+        )
 }
 
-// aoc_main should modify decorated function inputs and plug in loaded data
+/// Register a function as a solution for a given year and day, optionally with
+/// metadata: `#[aoc(2023, 17, title = "Clumsy Crucible", tags = ["dijkstra", "grid"])]`.
+///
+/// The function may take no parameters and load the input itself, or take a single
+/// parameter of type `&str`, `&[u8]`, `Vec<String>` (one item per line) or any
+/// `T: FromStr`, in which case the input is loaded and converted before the call.
+/// Loading time is reported separately from solving time.
+///
+/// The function either prints its answers or returns them as a `(part1, part2)` tuple
+/// of displayable values, which the runner prints and compares across variants.
+///
+/// Alternative approaches for the same day are registered with `variant = "name"`;
+/// the solution without a variant is the default one.
 #[proc_macro_attribute]
-pub fn aoc_main(attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn aoc(attr: TokenStream, item: TokenStream) -> TokenStream {
     let ItemFn {
         attrs,
         vis,
         sig,
         block,
     } = parse_macro_input!(item as ItemFn);
-
-    let exprs = Punctuated::<Expr, Token![,]>::parse_terminated
-        .parse(attr)
-        .unwrap()
-        .into_iter()
-        .collect::<Vec<_>>();
-
-    let (year, day) = match &*exprs {
-        [year, day] => (year, day),
-        _ => panic!("aoc: Invalid number of parameters; expected 2"),
+    let args = parse_macro_input!(attr as AocArgs);
+    let (year, day) = (&args.year, &args.day);
+    let metadata = args.metadata();
+    let input = match Input::from_signature(&sig) {
+        Ok(input) => input,
+        Err(err) => return err.to_compile_error().into(),
     };
 
     let fn_name = sig.ident.clone();
+    let params = &sig.inputs;
+    let output = &sig.output;
+    let outer_sig = Signature {
+        inputs: Default::default(),
+        ..sig.clone()
+    };
     let start = quote!(__measure_time_start_instant);
+    let data = Ident::new("__aoc_puzzle_input", Span::call_site());
+
+    let call = match input {
+        Input::None => quote! {
+            let #start = ::std::time::Instant::now();
+            let ret = inner();
+        },
+        _ => {
+            let value = input.convert(&data);
+            quote! {
+                let #start = ::std::time::Instant::now();
+                let #data = ::aoc_core::aoc_input!(#year, #day)
+                    .expect("Unable to load puzzle input");
+                let #data = #value;
+                ::aoc_core::solution::report_duration("Loading", #start.elapsed());
+
+                let #start = ::std::time::Instant::now();
+                let ret = inner(#data);
+            }
+        }
+    };
+
     let expanded = quote! {
 
         #(#attrs)*
-        #vis #sig {
-            fn inner(data: &str) {
+        #vis #outer_sig {
+            fn inner(#params) #output {
                 #block
             }
 
-            let data = ::aoc_core::aoc_input!(#year, #day);
-
-            let #start = ::std::time::Instant::now();
-            let ret = inner(&data.unwrap());
-            let duration = #start.elapsed();
-
-            if duration.as_secs() > 0 {
-                ::std::println!("Elapsed: \x1b[1m{}s\x1b[0m", duration.as_secs());
-            }
-            else if duration.as_millis() > 0 {
-                ::std::println!("Elapsed: \x1b[1m{}ms\x1b[0m", duration.as_millis());
-            } else {
-                ::std::println!("Elapsed: \x1b[1m{}μs\x1b[0m", duration.as_micros());
-            }
+            #call
+            ::aoc_core::solution::report_duration("Elapsed", #start.elapsed());
 
             ret
         }
 
         inventory::submit! {
-            aoc_core::solution::Solution::new(#year, #day, #fn_name)
+            aoc_core::solution::Solution::new(#year, #day, || {
+                ::aoc_core::solution::Answers::into_answers(#fn_name())
+            })#metadata
         }
     };
 
     TokenStream::from(expanded)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(attr: &str) -> Result<(u16, u16), String> {
+        syn::parse_str::<AocArgs>(attr)
+            .map(|args| {
+                let year = args.year.base10_parse().unwrap();
+                let day = args.day.base10_parse().unwrap();
+                (year, day)
+            })
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_valid_args() {
+        assert_eq!(parse("2024, 25"), Ok((2024, 25)));
+        assert_eq!(parse("2015, 1,"), Ok((2015, 1)));
+    }
+
+    #[test]
+    fn test_invalid_args() {
+        assert!(
+            parse("")
+                .unwrap_err()
+                .contains("expected `#[aoc(YEAR, DAY)]`")
+        );
+        assert!(parse("2024").is_err());
+        assert!(
+            parse("2024, 1, 2")
+                .unwrap_err()
+                .contains("unexpected argument")
+        );
+        assert!(parse("2014, 1").unwrap_err().contains("invalid year 2014"));
+        let next_year = format!("{}, 1", LAST_YEAR + 1);
+        assert!(parse(&next_year).unwrap_err().contains("invalid year"));
+        assert!(parse("2024, 26").unwrap_err().contains("invalid day 26"));
+        assert!(parse("2025, 13").unwrap_err().contains("invalid day 13"));
+        assert!(parse("2024, 0").unwrap_err().contains("invalid day 0"));
+    }
+
+    #[test]
+    fn test_metadata() {
+        let args = syn::parse_str::<AocArgs>(
+            r#"2023, 17, variant = "fast", title = "Clumsy Crucible", tags = ["dijkstra", "grid"], complexity = "O(n log n)""#,
+        )
+        .unwrap();
+        let tags = args.tags.iter().map(|t| t.value()).collect::<Vec<_>>();
+
+        assert_eq!(args.variant.unwrap().value(), "fast");
+        assert_eq!(args.title.unwrap().value(), "Clumsy Crucible");
+        assert_eq!(tags, ["dijkstra", "grid"]);
+        assert_eq!(args.complexity.unwrap().value(), "O(n log n)");
+    }
+
+    #[test]
+    fn test_invalid_metadata() {
+        assert!(
+            parse(r#"2023, 17, name = "x""#)
+                .unwrap_err()
+                .contains("unknown argument `name`")
+        );
+        assert!(
+            parse(r#"2023, 17, title = "a", title = "b""#)
+                .unwrap_err()
+                .contains("duplicate argument `title`")
+        );
+        assert!(parse("2023, 17, tags = [1]").is_err());
+        assert!(parse("2023, 17, title = 1").is_err());
+        assert!(
+            parse(r#"2023, 17, variant = "default""#)
+                .unwrap_err()
+                .contains("invalid variant name")
+        );
+    }
+
+    fn input(sig: &str) -> syn::Result<Input> {
+        Input::from_signature(&syn::parse_str::<Signature>(sig).unwrap())
+    }
+
+    #[test]
+    fn test_input_kinds() {
+        assert!(matches!(input("fn main()"), Ok(Input::None)));
+        assert!(matches!(input("fn main(data: &str)"), Ok(Input::Str)));
+        assert!(matches!(input("fn main(data: &[u8])"), Ok(Input::Bytes)));
+        assert!(matches!(
+            input("fn main(data: Vec<String>)"),
+            Ok(Input::Lines)
+        ));
+        assert!(matches!(input("fn main(data: Grid)"), Ok(Input::Parsed(_))));
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(input("fn main(a: &str, b: &str)").is_err());
+        assert!(input("fn main(data: &mut str)").is_err());
+        assert!(input("fn main(data: &Vec<u8>)").is_err());
+        assert!(input("fn main<T>(data: T)").is_err());
+    }
+}